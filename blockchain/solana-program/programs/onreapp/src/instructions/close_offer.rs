@@ -1,5 +1,11 @@
 use crate::contexts::CloseOfferContext;
-use crate::state::{Offer, State};
+use crate::instructions::dutch_auction::check_auction_settled;
+use crate::instructions::min_raise::check_no_open_receipts;
+use crate::instructions::offer_inventory::verify_buy_token_2_accounts;
+use crate::instructions::offer_registry::deregister_offer;
+use crate::instructions::subscription::check_no_open_subscriptions;
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{Offer, OfferRegistry, Role, State};
 use anchor_lang::prelude::*;
 use anchor_lang::Accounts;
 use anchor_spl::token;
@@ -14,6 +20,22 @@ pub struct TokensTransferred {
     pub amount: u64,
 }
 
+/// Event emitted when the crank bounty is changed.
+#[event]
+pub struct CrankBountySet {
+    pub old_crank_bounty_lamports: u64,
+    pub new_crank_bounty_lamports: u64,
+}
+
+/// Event emitted when an expired offer is closed by a cranker.
+#[event]
+pub struct ExpiredOfferClosed {
+    pub offer_id: u64,
+    pub boss: Pubkey,
+    pub cranker: Pubkey,
+    pub bounty_lamports: u64,
+}
+
 /// Event emitted when an offer is closed.
 #[event]
 pub struct OfferClosed {
@@ -69,10 +91,24 @@ pub struct CloseOfferOne<'info> {
     )]
     pub boss_sell_token_account: Account<'info, TokenAccount>,
 
-    /// Program state, ensures `boss` is authorized.
-    #[account(has_one = boss)]
+    /// Program state, ensures `boss` is the boss and `authority` holds the offer manager role.
+    #[account(
+        has_one = boss,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
     pub state: Account<'info, State>,
 
+    /// Registry of the open offers, shrunk by the ID of the offer with the rent refunded to `boss`.
+    #[account(
+        mut,
+        seeds = [b"offer_registry"],
+        bump,
+        realloc = OfferRegistry::space(offer_registry.offer_count_after_close(offer.offer_id)),
+        realloc::payer = boss,
+        realloc::zero = false
+    )]
+    pub offer_registry: Box<Account<'info, OfferRegistry>>,
+
     /// Derived PDA for token authority, does not store data.
     ///
     /// # Note
@@ -80,9 +116,15 @@ pub struct CloseOfferOne<'info> {
     #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
     pub offer_token_authority: AccountInfo<'info>,
 
-    /// The signer authorizing the closure, typically the boss.
+    /// The boss, receiving the remaining tokens and the rent of the closed accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the `has_one = boss` constraint of `state`.
     #[account(mut)]
-    pub boss: Signer<'info>,
+    pub boss: AccountInfo<'info>,
+
+    /// The boss or an offer manager authorizing the closure.
+    pub authority: Signer<'info>,
 
     /// SPL Token program for token operations.
     pub token_program: Program<'info, Token>,
@@ -94,12 +136,22 @@ pub struct CloseOfferOne<'info> {
 /// Closes a single buy token offer.
 ///
 /// Transfers remaining sell and buy tokens to the boss’s accounts, closes the offer’s token accounts,
-/// and refunds the `offer` account’s rent to `boss`. Emits events for token transfers and offer closure.
+/// removes the offer from the offer registry and refunds the `offer` account’s rent to `boss`.
+/// Emits events for token transfers and offer closure.
 ///
 /// # Errors
 /// - [`CloseOfferErrorCode::InvalidCloseOffer`] if `buy_token_mint_2 != System Program ID`.
 /// - [`CloseOfferErrorCode::InvalidMint`] if token account mints mismatch during transfers.
+/// - [`RoleErrorCode::MissingRole`] if `authority` doesn't hold the offer manager role.
+/// - `DutchAuctionErrorCode::AuctionNotSettled` if the offer is in uniform price mode and not settled.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if receipts of the offer were not settled.
 pub fn close_offer_one(ctx: Context<CloseOfferOne>) -> Result<()> {
+    check_auction_settled(&ctx.accounts.offer)?;
+    check_no_open_subscriptions(&ctx.accounts.offer)?;
+    check_no_open_receipts(&ctx.accounts.offer)?;
+    deregister_offer(&mut ctx.accounts.offer_registry, ctx.accounts.offer.offer_id);
+
     let offer_sell_token_account = &ctx.accounts.offer_sell_token_account;
     let offer_buy_1_token_account = &ctx.accounts.offer_buy_1_token_account;
     let boss_sell_token_account = &ctx.accounts.boss_sell_token_account;
@@ -204,10 +256,24 @@ pub struct CloseOfferTwo<'info> {
   )]
     pub boss_sell_token_account: Account<'info, TokenAccount>,
 
-    /// Program state, ensures `boss` is authorized.
-    #[account(has_one = boss)]
+    /// Program state, ensures `boss` is the boss and `authority` holds the offer manager role.
+    #[account(
+        has_one = boss,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
     pub state: Account<'info, State>,
 
+    /// Registry of the open offers, shrunk by the ID of the offer with the rent refunded to `boss`.
+    #[account(
+        mut,
+        seeds = [b"offer_registry"],
+        bump,
+        realloc = OfferRegistry::space(offer_registry.offer_count_after_close(offer.offer_id)),
+        realloc::payer = boss,
+        realloc::zero = false
+    )]
+    pub offer_registry: Box<Account<'info, OfferRegistry>>,
+
     /// Derived PDA for token authority, does not store data.
     ///
     /// # Note
@@ -215,9 +281,15 @@ pub struct CloseOfferTwo<'info> {
     #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
     pub offer_token_authority: AccountInfo<'info>,
 
-    /// The signer authorizing the closure, typically the boss.
+    /// The boss, receiving the remaining tokens and the rent of the closed accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the `has_one = boss` constraint of `state`.
     #[account(mut)]
-    pub boss: Signer<'info>,
+    pub boss: AccountInfo<'info>,
+
+    /// The boss or an offer manager authorizing the closure.
+    pub authority: Signer<'info>,
 
     /// SPL Token program for token operations.
     pub token_program: Program<'info, Token>,
@@ -229,11 +301,21 @@ pub struct CloseOfferTwo<'info> {
 /// Closes a dual buy token offer.
 ///
 /// Transfers remaining sell and buy tokens to the boss’s accounts, closes the offer’s token accounts,
-/// and refunds the `offer` account’s rent to `boss`. Emits events for token transfers and offer closure.
+/// removes the offer from the offer registry and refunds the `offer` account’s rent to `boss`.
+/// Emits events for token transfers and offer closure.
 ///
 /// # Errors
 /// - [`CloseOfferErrorCode::InvalidMint`] if token account mints mismatch during transfers.
+/// - [`RoleErrorCode::MissingRole`] if `authority` doesn't hold the offer manager role.
+/// - `DutchAuctionErrorCode::AuctionNotSettled` if the offer is in uniform price mode and not settled.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if receipts of the offer were not settled.
 pub fn close_offer_two(ctx: Context<CloseOfferTwo>) -> Result<()> {
+    check_auction_settled(&ctx.accounts.offer)?;
+    check_no_open_subscriptions(&ctx.accounts.offer)?;
+    check_no_open_receipts(&ctx.accounts.offer)?;
+    deregister_offer(&mut ctx.accounts.offer_registry, ctx.accounts.offer.offer_id);
+
     let offer_sell_token_account = &ctx.accounts.offer_sell_token_account;
     let offer_buy_1_token_account = &ctx.accounts.offer_buy_1_token_account;
     let offer_buy_2_token_account = &ctx.accounts.offer_buy_2_token_account;
@@ -286,6 +368,215 @@ pub fn close_offer_two(ctx: Context<CloseOfferTwo>) -> Result<()> {
     Ok(())
 }
 
+/// Account structure for closing an expired offer, callable by anyone.
+///
+/// Remaining tokens go to the ATAs of the boss recorded on the offer, so the caller cannot
+/// redirect them. The buy token 2 accounts are required only for offers with two buy tokens.
+#[derive(Accounts)]
+pub struct CloseExpiredOffer<'info> {
+    /// The expired offer, with the rent left after the bounty refunded to `boss`.
+    #[account(mut, close = boss)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Offer's sell token ATA, controlled by `offer_token_authority`.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 1 ATA, controlled by `offer_token_authority`.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's sell token ATA, receives the remaining sell tokens.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's buy token 1 ATA, receives the remaining buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 2 ATA, controlled by `offer_token_authority`.
+    #[account(mut)]
+    pub offer_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Boss's buy token 2 ATA, receives the remaining buy token 2.
+    #[account(mut)]
+    pub boss_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// The boss recorded on the offer, receiving the remaining tokens and the rent.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated against `offer.boss`.
+    #[account(mut, address = offer.boss @ CloseOfferErrorCode::InvalidBoss)]
+    pub boss: AccountInfo<'info>,
+
+    /// Program state holding the crank bounty.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// Registry of the open offers, shrunk by the ID of the offer with the rent refunded to `boss`.
+    #[account(
+        mut,
+        seeds = [b"offer_registry"],
+        bump,
+        realloc = OfferRegistry::space(offer_registry.offer_count_after_close(offer.offer_id)),
+        realloc::payer = boss,
+        realloc::zero = false
+    )]
+    pub offer_registry: Box<Account<'info, OfferRegistry>>,
+
+    /// The caller closing the offer, receives the crank bounty.
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Solana System program for account operations.
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes an offer once `offer_end_time` has passed.
+///
+/// Transfers the remaining sell and buy tokens to the ATAs of the boss recorded on the offer,
+/// closes the offer's token accounts with their rent refunded to the boss, removes the offer from
+/// the offer registry and closes the offer.
+/// `State::crank_bounty_lamports` of the offer's rent, capped at the rent, are paid to the
+/// cranker and the rest is refunded to the boss. Emits `TokensTransferred` and
+/// `ExpiredOfferClosed` events.
+///
+/// # Errors
+/// - [`CloseOfferErrorCode::OfferNotExpired`] if `offer_end_time` has not passed yet.
+/// - `DutchAuctionErrorCode::AuctionNotSettled` if the offer is in uniform price mode and not settled.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if receipts of the offer were not settled.
+/// - `OfferInventoryErrorCode` errors if the offer has two buy tokens and their accounts are missing or invalid.
+pub fn close_expired_offer(ctx: Context<CloseExpiredOffer>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        current_time >= ctx.accounts.offer.offer_end_time,
+        CloseOfferErrorCode::OfferNotExpired
+    );
+    check_auction_settled(&ctx.accounts.offer)?;
+    check_no_open_subscriptions(&ctx.accounts.offer)?;
+    check_no_open_receipts(&ctx.accounts.offer)?;
+    deregister_offer(&mut ctx.accounts.offer_registry, ctx.accounts.offer.offer_id);
+
+    let buy_token_2_accounts = verify_buy_token_2_accounts(
+        &ctx.accounts.offer,
+        ctx.accounts.offer_buy_token_2_account.as_deref(),
+        ctx.accounts.boss_buy_token_2_account.as_deref(),
+        &ctx.accounts.offer_token_authority.key(),
+        &ctx.accounts.boss.key(),
+    )?;
+
+    transfer_remaining_tokens(&ctx, &ctx.accounts.offer_sell_token_account, &ctx.accounts.boss_sell_token_account)?;
+    transfer_remaining_tokens(&ctx, &ctx.accounts.offer_buy_token_1_account, &ctx.accounts.boss_buy_token_1_account)?;
+    if let Some((offer_buy_token_2_account, boss_buy_token_2_account)) = buy_token_2_accounts {
+        transfer_remaining_tokens(&ctx, offer_buy_token_2_account, boss_buy_token_2_account)?;
+    }
+
+    let offer_id_bytes = &ctx.accounts.offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes.as_ref(),
+        &[ctx.accounts.offer.authority_bump],
+    ];
+    let signer_seeds = &[seeds.as_ref()];
+
+    let mut token_accounts: Vec<&Account<TokenAccount>> = vec![
+        &ctx.accounts.offer_sell_token_account,
+        &ctx.accounts.offer_buy_token_1_account,
+    ];
+    if let Some((offer_buy_token_2_account, _)) = buy_token_2_accounts {
+        token_accounts.push(offer_buy_token_2_account);
+    }
+    for token_account in token_accounts {
+        close_token_account(
+            token_account.clone(),
+            ctx.accounts.offer_token_authority.clone(),
+            ctx.accounts.boss.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+
+    let offer_info = ctx.accounts.offer.to_account_info();
+    let bounty_lamports = ctx
+        .accounts
+        .state
+        .crank_bounty_lamports
+        .min(offer_info.lamports());
+    if bounty_lamports > 0 {
+        **offer_info.try_borrow_mut_lamports()? -= bounty_lamports;
+        **ctx.accounts.cranker.try_borrow_mut_lamports()? += bounty_lamports;
+        msg!("Paying {} lamports crank bounty to {}", bounty_lamports, ctx.accounts.cranker.key());
+    }
+
+    emit!(ExpiredOfferClosed {
+        offer_id: ctx.accounts.offer.offer_id,
+        boss: ctx.accounts.boss.key(),
+        cranker: ctx.accounts.cranker.key(),
+        bounty_lamports,
+    });
+
+    Ok(())
+}
+
+/// Account structure for changing the crank bounty.
+#[derive(Accounts)]
+pub struct SetCrankBounty<'info> {
+    /// Program state holding the bounty, ensures `authority` holds the admin role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the lamports of the offer rent paid to the caller of `close_expired_offer`, `0` for no bounty.
+///
+/// Emits a `CrankBountySet` event.
+pub fn set_crank_bounty(ctx: Context<SetCrankBounty>, crank_bounty_lamports: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let old_crank_bounty_lamports = state.crank_bounty_lamports;
+    state.crank_bounty_lamports = crank_bounty_lamports;
+
+    emit!(CrankBountySet {
+        old_crank_bounty_lamports,
+        new_crank_bounty_lamports: crank_bounty_lamports,
+    });
+
+    Ok(())
+}
+
 /// Trait implementation for `CloseOfferOne` to satisfy `CloseOfferContext`.
 impl<'info> CloseOfferContext<'info> for CloseOfferOne<'info> {
     fn token_program(&self) -> &Program<'info, Token> {
@@ -301,6 +592,21 @@ impl<'info> CloseOfferContext<'info> for CloseOfferOne<'info> {
     }
 }
 
+/// Trait implementation for `CloseExpiredOffer` to satisfy `CloseOfferContext`.
+impl<'info> CloseOfferContext<'info> for CloseExpiredOffer<'info> {
+    fn token_program(&self) -> &Program<'info, Token> {
+        &self.token_program
+    }
+
+    fn offer_token_authority(&self) -> &AccountInfo<'info> {
+        &self.offer_token_authority
+    }
+
+    fn offer(&self) -> &Account<'info, Offer> {
+        &self.offer
+    }
+}
+
 /// Trait implementation for `CloseOfferTwo` to satisfy `CloseOfferContext`.
 impl<'info> CloseOfferContext<'info> for CloseOfferTwo<'info> {
     fn token_program(&self) -> &Program<'info, Token> {
@@ -327,7 +633,7 @@ impl<'info> CloseOfferContext<'info> for CloseOfferTwo<'info> {
 ///
 /// # Errors
 /// - [`CloseOfferErrorCode::InvalidMint`] if the mints of source and destination accounts don’t match.
-fn transfer_remaining_tokens<'info, T: CloseOfferContext<'info> + anchor_lang::Bumps>(
+pub(crate) fn transfer_remaining_tokens<'info, T: CloseOfferContext<'info> + anchor_lang::Bumps>(
     ctx: &Context<T>,
     from_token_account: &Account<'info, TokenAccount>,
     to_token_account: &Account<'info, TokenAccount>,
@@ -378,7 +684,7 @@ fn transfer_remaining_tokens<'info, T: CloseOfferContext<'info> + anchor_lang::B
 /// - `destination`: The account receiving the refunded rent.
 /// - `token_program`: The SPL Token program instance.
 /// - `signer_seeds`: Seeds for signing as the PDA authority.
-fn close_token_account<'info>(
+pub(crate) fn close_token_account<'info>(
     token_account: Account<'info, TokenAccount>,
     authority: AccountInfo<'info>,
     destination: AccountInfo<'info>,
@@ -405,4 +711,12 @@ pub enum CloseOfferErrorCode {
     /// Triggered when the offer type is invalid for the closure instruction.
     #[msg("Invalid close offer")]
     InvalidCloseOffer,
+
+    /// Triggered when closing an offer before its end time.
+    #[msg("The offer has not expired yet")]
+    OfferNotExpired,
+
+    /// Triggered when the boss account is not the boss recorded on the offer.
+    #[msg("Invalid boss")]
+    InvalidBoss,
 }
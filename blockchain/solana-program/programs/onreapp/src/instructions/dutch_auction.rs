@@ -0,0 +1,453 @@
+use crate::instructions::min_raise::check_no_min_raise;
+use crate::instructions::pricing::{mul_div, Rounding};
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{AuctionSettlement, Offer, Position, Role, State};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when the uniform price mode of an offer is changed.
+#[event]
+pub struct OfferUniformPriceSet {
+    pub offer_id: u64,
+    pub uniform_price: bool,
+}
+
+/// Event emitted when the clearing price of a uniform price offer is fixed.
+#[event]
+pub struct AuctionSettled {
+    pub offer_id: u64,
+    /// Sell token amount for `buy_token_1_amount` every filler pays.
+    pub clearing_sell_token_amount: u64,
+    pub buy_token_1_amount: u64,
+    /// Rebates moved to the rebate vault.
+    pub rebate_reserve: u64,
+}
+
+/// Event emitted when a filler claims their rebate.
+#[event]
+pub struct RebateClaimed {
+    pub offer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+/// Account structure for changing the uniform price mode of an offer.
+#[derive(Accounts)]
+pub struct SetOfferUniformPrice<'info> {
+    /// The offer whose uniform price mode is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets whether every filler of an offer pays the clearing price.
+///
+/// In uniform price mode takes are priced by the interval schedule as usual, and once the offer
+/// is settled via `settle_auction` every filler can claim the difference to the clearing price,
+/// the price of the last take. Combined with a descending schedule this runs a uniform price
+/// Dutch auction. Emits an `OfferUniformPriceSet` event.
+///
+/// # Errors
+/// - [`DutchAuctionErrorCode::AuctionStarted`] if the offer was already taken.
+/// - [`DutchAuctionErrorCode::NavPricedOffer`] if the offer is priced against a NAV oracle.
+/// - `MinRaiseErrorCode::MinRaiseOffer` if the offer has a minimum raise.
+pub fn set_offer_uniform_price(ctx: Context<SetOfferUniformPrice>, uniform_price: bool) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(offer.total_raised == 0, DutchAuctionErrorCode::AuctionStarted);
+    require!(
+        offer.nav_oracle == Pubkey::default(),
+        DutchAuctionErrorCode::NavPricedOffer
+    );
+    check_no_min_raise(offer)?;
+    offer.uniform_price = uniform_price;
+
+    emit!(OfferUniformPriceSet {
+        offer_id: offer.offer_id,
+        uniform_price,
+    });
+
+    Ok(())
+}
+
+/// Account structure for settling a uniform price offer.
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    /// The settled offer.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The settlement, created with rent paid by `payer`.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AuctionSettlement::INIT_SPACE,
+        seeds = [b"auction_settlement", offer.key().as_ref()],
+        bump
+    )]
+    pub settlement: Box<Account<'info, AuctionSettlement>>,
+
+    /// Offer's sell token ATA, sends the rebates to the rebate vault.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Rebate vault of the settlement, created with rent paid by `payer`.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = sell_token_mint,
+        associated_token::authority = settlement,
+    )]
+    pub rebate_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Sell token mint of the offer.
+    #[account(address = offer.sell_token_mint)]
+    pub sell_token_mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state, used to check whether `payer` may settle before the offer ends.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// Settles the offer and pays for the settlement accounts, anyone once the offer ended.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Associated Token program for the rebate vault creation.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Solana System program for account creation.
+    pub system_program: Program<'info, System>,
+}
+
+/// Fixes the clearing price of a uniform price offer and reserves the rebates of its fillers.
+///
+/// The clearing price is the price of the last take. The rebates owed at that price are moved
+/// from the offer's sell token account to the rebate vault of the settlement, capped at the
+/// account's balance, after which the offer can no longer be taken and its proceeds can be
+/// withdrawn or the offer closed.
+/// Anyone can settle once the offer ended, the boss or an offer manager at any time, e.g. when
+/// it sold out. Emits an `AuctionSettled` event.
+///
+/// # Errors
+/// - [`DutchAuctionErrorCode::NotUniformPrice`] if the offer is not in uniform price mode.
+/// - [`DutchAuctionErrorCode::AuctionNotEnded`] if the offer is still active and `payer` is not an offer manager.
+/// - `PricingErrorCode::CalculationOverflow` if the rebate calculation overflows.
+pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(offer.uniform_price, DutchAuctionErrorCode::NotUniformPrice);
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        current_time >= offer.offer_end_time
+            || ctx.accounts.state.has_role(Role::OfferManager, &ctx.accounts.payer.key()),
+        DutchAuctionErrorCode::AuctionNotEnded
+    );
+
+    let rebates_owed = calculate_rebate(
+        offer.auction_sell_token_total,
+        offer.auction_buy_token_1_total,
+        offer.clearing_sell_token_amount,
+        offer.buy_token_1.amount,
+    )?;
+    let rebate_reserve = rebates_owed.min(ctx.accounts.offer_sell_token_account.amount);
+
+    if rebate_reserve > 0 {
+        let offer_id_bytes = &offer.offer_id.to_le_bytes();
+        let seeds = &[
+            b"offer_authority".as_ref(),
+            offer_id_bytes,
+            &[offer.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_sell_token_account.to_account_info(),
+                    to: ctx.accounts.rebate_vault_token_account.to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rebate_reserve,
+        )?;
+        msg!("Transferring {} sell tokens from offer to rebate vault", rebate_reserve);
+    }
+
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.offer = offer.key();
+    settlement.sell_token_mint = offer.sell_token_mint;
+    settlement.clearing_sell_token_amount = offer.clearing_sell_token_amount;
+    settlement.buy_token_1_amount = offer.buy_token_1.amount;
+    settlement.rebate_reserve = rebate_reserve;
+    settlement.rebates_owed = rebates_owed;
+    settlement.bump = ctx.bumps.settlement;
+
+    emit!(AuctionSettled {
+        offer_id: offer.offer_id,
+        clearing_sell_token_amount: settlement.clearing_sell_token_amount,
+        buy_token_1_amount: settlement.buy_token_1_amount,
+        rebate_reserve,
+    });
+
+    ctx.accounts.offer.auction_settled = true;
+
+    Ok(())
+}
+
+/// Account structure for claiming a rebate of a settled uniform price offer.
+#[derive(Accounts)]
+pub struct ClaimRebate<'info> {
+    /// The settlement of the offer.
+    #[account(
+        mut,
+        seeds = [b"auction_settlement", settlement.offer.as_ref()],
+        bump = settlement.bump
+    )]
+    pub settlement: Box<Account<'info, AuctionSettlement>>,
+
+    /// The user's position in the offer.
+    #[account(
+        mut,
+        seeds = [b"position", settlement.offer.as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Rebate vault of the settlement, sends the rebate.
+    #[account(
+        mut,
+        associated_token::mint = settlement.sell_token_mint,
+        associated_token::authority = settlement,
+    )]
+    pub rebate_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's sell token account, receives the rebate.
+    #[account(
+        mut,
+        associated_token::mint = settlement.sell_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The filler claiming the rebate.
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claims the difference between what a filler paid and the clearing price.
+///
+/// If the rebate reserve falls short of the rebates owed, the rebate is scaled down by the
+/// reserve left over the rebates still owed, rounding down, so every filler gets the same share
+/// whatever the order of the claims. Emits a `RebateClaimed` event.
+///
+/// # Errors
+/// - [`DutchAuctionErrorCode::RebateAlreadyClaimed`] if the rebate was already claimed.
+/// - [`DutchAuctionErrorCode::NothingToClaim`] if the user paid the clearing price or their share of the reserve rounds to zero.
+/// - [`DutchAuctionErrorCode::CalculationOverflow`] if the rebate exceeds the reserve.
+/// - `PricingErrorCode::CalculationOverflow` if the rebate calculation or its pro-rata scaling overflows.
+pub fn claim_rebate(ctx: Context<ClaimRebate>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    require!(!position.rebate_claimed, DutchAuctionErrorCode::RebateAlreadyClaimed);
+
+    let settlement = &ctx.accounts.settlement;
+    let owed = calculate_rebate(
+        position.auction_sell_token_amount,
+        position.auction_buy_token_1_amount,
+        settlement.clearing_sell_token_amount,
+        settlement.buy_token_1_amount,
+    )?;
+    let rebate = if settlement.rebate_reserve < settlement.rebates_owed {
+        mul_div(owed, settlement.rebate_reserve, settlement.rebates_owed, Rounding::Down)?
+    } else {
+        owed
+    }
+    .min(settlement.rebate_reserve);
+    require!(rebate > 0, DutchAuctionErrorCode::NothingToClaim);
+    position.rebate_claimed = true;
+
+    let seeds = &[
+        b"auction_settlement".as_ref(),
+        settlement.offer.as_ref(),
+        &[settlement.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.rebate_vault_token_account.to_account_info(),
+                to: ctx.accounts.user_sell_token_account.to_account_info(),
+                authority: ctx.accounts.settlement.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        rebate,
+    )?;
+    msg!("Transferring {} sell tokens from rebate vault to user", rebate);
+
+    let settlement = &mut ctx.accounts.settlement;
+    settlement.rebate_reserve = settlement
+        .rebate_reserve
+        .checked_sub(rebate)
+        .ok_or(DutchAuctionErrorCode::CalculationOverflow)?;
+    // Fillers who paid less than the clearing price lower the total owed without being owed
+    // anything, so the other fillers' rebates can add up to more than the total.
+    settlement.rebates_owed = settlement.rebates_owed.saturating_sub(owed);
+
+    emit!(RebateClaimed {
+        offer: settlement.offer,
+        user: ctx.accounts.user.key(),
+        amount: rebate,
+    });
+
+    Ok(())
+}
+
+/// Records a take of a uniform price offer, making its price the clearing price.
+///
+/// Does nothing for offers not in uniform price mode.
+///
+/// # Arguments
+/// - `offer`: The taken offer.
+/// - `position`: The user's position in the offer.
+/// - `sell_token_amount`: Sell token amount paid into the offer, net of fees.
+/// - `buy_token_1_amount`: Buy token 1 amount sold.
+/// - `current_sell_token_amount`: Sell token amount for `buy_token_1.amount` at the time of the take.
+///
+/// # Errors
+/// - [`DutchAuctionErrorCode::AuctionAlreadySettled`] if the offer was settled.
+/// - [`DutchAuctionErrorCode::CalculationOverflow`] if a cumulative amount overflows.
+pub fn record_auction_fill(
+    offer: &mut Offer,
+    position: &mut Position,
+    sell_token_amount: u64,
+    buy_token_1_amount: u64,
+    current_sell_token_amount: u64,
+) -> Result<()> {
+    if !offer.uniform_price {
+        return Ok(());
+    }
+    require!(!offer.auction_settled, DutchAuctionErrorCode::AuctionAlreadySettled);
+
+    offer.clearing_sell_token_amount = current_sell_token_amount;
+    offer.auction_sell_token_total = offer
+        .auction_sell_token_total
+        .checked_add(sell_token_amount)
+        .ok_or(DutchAuctionErrorCode::CalculationOverflow)?;
+    offer.auction_buy_token_1_total = offer
+        .auction_buy_token_1_total
+        .checked_add(buy_token_1_amount)
+        .ok_or(DutchAuctionErrorCode::CalculationOverflow)?;
+    position.auction_sell_token_amount = position
+        .auction_sell_token_amount
+        .checked_add(sell_token_amount)
+        .ok_or(DutchAuctionErrorCode::CalculationOverflow)?;
+    position.auction_buy_token_1_amount = position
+        .auction_buy_token_1_amount
+        .checked_add(buy_token_1_amount)
+        .ok_or(DutchAuctionErrorCode::CalculationOverflow)?;
+
+    Ok(())
+}
+
+/// Checks that a uniform price offer was settled before its sell tokens leave the offer.
+///
+/// # Errors
+/// - [`DutchAuctionErrorCode::AuctionNotSettled`] if the offer is in uniform price mode and not settled.
+pub fn check_auction_settled(offer: &Offer) -> Result<()> {
+    require!(
+        !offer.uniform_price || offer.auction_settled,
+        DutchAuctionErrorCode::AuctionNotSettled
+    );
+    Ok(())
+}
+
+/// Calculates the rebate of `sell_token_amount` paid for `buy_token_1_amount` at the clearing price.
+///
+/// The cost at the clearing price is rounded up, so rebates never exceed what was overpaid.
+///
+/// # Errors
+/// - `PricingErrorCode::CalculationOverflow` if the calculation overflows.
+fn calculate_rebate(
+    sell_token_amount: u64,
+    buy_token_1_amount: u64,
+    clearing_sell_token_amount: u64,
+    offer_buy_token_1_amount: u64,
+) -> Result<u64> {
+    if offer_buy_token_1_amount == 0 {
+        return Ok(0);
+    }
+    let cost = mul_div(
+        buy_token_1_amount,
+        clearing_sell_token_amount,
+        offer_buy_token_1_amount,
+        Rounding::Up,
+    )?;
+    Ok(sell_token_amount.saturating_sub(cost))
+}
+
+/// Error codes for Dutch auction operations.
+#[error_code]
+pub enum DutchAuctionErrorCode {
+    /// Triggered when changing the uniform price mode of an offer that was already taken.
+    #[msg("The offer was already taken")]
+    AuctionStarted,
+
+    /// Triggered when enabling the uniform price mode of a NAV priced offer.
+    #[msg("NAV priced offers cannot use uniform pricing")]
+    NavPricedOffer,
+
+    /// Triggered when settling an offer not in uniform price mode.
+    #[msg("The offer is not in uniform price mode")]
+    NotUniformPrice,
+
+    /// Triggered when settling an active offer without the offer manager role.
+    #[msg("The auction has not ended")]
+    AuctionNotEnded,
+
+    /// Triggered when taking a settled offer.
+    #[msg("The auction was already settled")]
+    AuctionAlreadySettled,
+
+    /// Triggered when withdrawing from or closing a uniform price offer before it was settled.
+    #[msg("The auction was not settled")]
+    AuctionNotSettled,
+
+    /// Triggered when claiming an already claimed rebate.
+    #[msg("Rebate already claimed")]
+    RebateAlreadyClaimed,
+
+    /// Triggered when claiming with no rebate due.
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    /// Triggered when a rebate calculation overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
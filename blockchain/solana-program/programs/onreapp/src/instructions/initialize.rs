@@ -1,4 +1,5 @@
-use crate::state::{PermissionlessAccount, State};
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{OfferRegistry, PermissionlessAccount, Role, State};
 use anchor_lang::prelude::*;
 use anchor_lang::Accounts;
 
@@ -34,6 +35,19 @@ pub struct Initialize<'info> {
     )]
     pub state: Account<'info, State>,
 
+    /// The registry of the open offers, initialized empty.
+    ///
+    /// # Note
+    /// - Seeded with `"offer_registry"` and a bump for PDA derivation.
+    #[account(
+        init,
+        payer = boss,
+        space = OfferRegistry::space(0),
+        seeds = [b"offer_registry"],
+        bump
+    )]
+    pub offer_registry: Account<'info, OfferRegistry>,
+
     /// The signer funding and authorizing the state initialization, becomes the boss.
     #[account(mut)]
     pub boss: Signer<'info>,
@@ -45,7 +59,7 @@ pub struct Initialize<'info> {
 /// Initializes the program state with the boss’s public key.
 ///
 /// Sets the `boss` field in the `state` account to the signer’s key if it’s not already set.
-/// The account is created as a PDA with the seed `"state"`.
+/// The account is created as a PDA with the seed `"state"`, along with the empty offer registry.
 ///
 /// # Arguments
 /// - `ctx`: Context containing the accounts to initialize the state.
@@ -70,26 +84,31 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
 /// - Only the boss can initialize permissionless accounts
 /// - The permissionless account must not exist prior to execution
 #[derive(Accounts)]
+#[instruction(index: u64)]
 pub struct InitializePermissionlessAccount<'info> {
     /// The permissionless account to be created.
     ///
     /// # Note
     /// - Space is allocated as `8 + PermissionlessAccount::INIT_SPACE` bytes
-    /// - Seeded with hardcoded "permissionless-1" for PDA derivation
+    /// - Seeded with `"permissionless"` and `index` for PDA derivation
     #[account(
         init,
         payer = boss,
         space = 8 + PermissionlessAccount::INIT_SPACE,
-        seeds = [b"permissionless-1"],
+        seeds = [b"permissionless", index.to_le_bytes().as_ref()],
         bump
     )]
     pub permissionless_account: Account<'info, PermissionlessAccount>,
 
-    /// The program state account, used to verify boss authorization.
-    #[account(has_one = boss)]
+    /// The program state account, used to verify `boss` holds the admin role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
     pub state: Account<'info, State>,
 
-    /// The boss account that authorizes and pays for the permissionless account creation.
+    /// The boss or an admin, authorizing and paying for the permissionless account creation.
     #[account(mut)]
     pub boss: Signer<'info>,
 
@@ -101,20 +120,23 @@ pub struct InitializePermissionlessAccount<'info> {
 ///
 /// Creates a permissionless account that can serve as an intermediary authority
 /// for token routing operations. Only the boss can create these accounts.
-/// The PDA is always derived using the hardcoded seed "permissionless-1".
+/// The PDA is derived from `index`, so several routing channels can coexist.
+/// The account is created enabled.
 ///
 /// # Arguments
 /// - `ctx`: Context containing the accounts for permissionless account creation
+/// - `index`: The index the PDA is derived from
 /// - `name`: The name to store in the permissionless account (separate from PDA seeds)
 ///
 /// # Returns
 /// A `Result` indicating success or failure.
 ///
 /// # Errors
-/// - Fails if the caller is not the boss (enforced by `has_one = boss` constraint)
+/// - Fails if the caller doesn't hold the admin role (`RoleErrorCode::MissingRole`)
 /// - Fails if the permissionless account already exists
 pub fn initialize_permissionless_account(
     ctx: Context<InitializePermissionlessAccount>,
+    index: u64,
     name: String,
 ) -> Result<()> {
     let name_cleaned = name.trim();
@@ -124,5 +146,8 @@ pub fn initialize_permissionless_account(
     );
     let permissionless_account = &mut ctx.accounts.permissionless_account;
     permissionless_account.name = name_cleaned.to_string();
+    permissionless_account.index = index;
+    permissionless_account.enabled = true;
+    permissionless_account.bump = ctx.bumps.permissionless_account;
     Ok(())
 }
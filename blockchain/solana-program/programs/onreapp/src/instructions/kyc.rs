@@ -0,0 +1,340 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{KycRecord, Offer, Role, State};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+/// Maximum number of KYC records approved or revoked in one instruction.
+pub const MAX_KYC_BATCH_SIZE: usize = 20;
+
+/// Event emitted when a wallet's KYC record is created or updated.
+#[event]
+pub struct KycApproved {
+    pub wallet: Pubkey,
+    pub level: u8,
+    pub expiry: u64,
+    pub jurisdiction: u16,
+}
+
+/// Event emitted when a wallet's KYC record is revoked.
+#[event]
+pub struct KycRevoked {
+    pub wallet: Pubkey,
+}
+
+/// Event emitted when the minimum KYC level of an offer is changed.
+#[event]
+pub struct OfferMinKycLevelSet {
+    pub offer_id: u64,
+    pub min_kyc_level: u8,
+}
+
+/// Account structure for approving KYC records in batch.
+///
+/// The KYC record PDAs of the approved wallets, derived from `["kyc", wallet]`, are passed as
+/// writable remaining accounts in the same order as the `wallets` argument.
+#[derive(Accounts)]
+pub struct ApproveKyc<'info> {
+    /// Program state, ensures `compliance` holds the compliance role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Compliance, &compliance.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or a compliance officer, pays for newly created records.
+    #[account(mut)]
+    pub compliance: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the KYC records of a batch of wallets.
+///
+/// All wallets of the batch are approved with the same level, expiry and jurisdiction.
+/// Emits a `KycApproved` event per wallet.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts, with the KYC records as remaining accounts.
+/// - `wallets`: Wallets to approve.
+/// - `level`: KYC level granted, must be greater than zero.
+/// - `expiry`: Unix timestamp after which the records expire, `0` if they never expire.
+/// - `jurisdiction`: ISO 3166-1 numeric code of the wallets' jurisdiction.
+///
+/// # Errors
+/// - [`KycErrorCode::InvalidBatchSize`] if the batch is empty, too large, or doesn't match the remaining accounts.
+/// - [`KycErrorCode::InvalidKycLevel`] if `level` is zero.
+/// - [`KycErrorCode::InvalidExpiry`] if `expiry` is set and not in the future.
+/// - [`KycErrorCode::InvalidKycRecord`] if a remaining account is not the KYC record PDA of its wallet.
+pub fn approve_kyc<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ApproveKyc<'info>>,
+    wallets: Vec<Pubkey>,
+    level: u8,
+    expiry: u64,
+    jurisdiction: u16,
+) -> Result<()> {
+    validate_batch(&wallets, ctx.remaining_accounts)?;
+    require!(level > 0, KycErrorCode::InvalidKycLevel);
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(expiry == 0 || expiry > current_time, KycErrorCode::InvalidExpiry);
+
+    for (wallet, kyc_record_info) in wallets.iter().zip(ctx.remaining_accounts.iter()) {
+        let (expected_kyc_record, bump) =
+            Pubkey::find_program_address(&[b"kyc", wallet.as_ref()], ctx.program_id);
+        require_keys_eq!(
+            kyc_record_info.key(),
+            expected_kyc_record,
+            KycErrorCode::InvalidKycRecord
+        );
+
+        if kyc_record_info.owner == ctx.program_id {
+            KycRecord::try_deserialize(&mut &kyc_record_info.try_borrow_data()?[..])?;
+        } else {
+            create_kyc_record(
+                kyc_record_info,
+                wallet,
+                bump,
+                &ctx.accounts.compliance,
+                &ctx.accounts.system_program,
+            )?;
+        }
+
+        let kyc_record = KycRecord {
+            wallet: *wallet,
+            level,
+            expiry,
+            jurisdiction,
+            bump,
+        };
+        kyc_record.try_serialize(&mut &mut kyc_record_info.try_borrow_mut_data()?[..])?;
+
+        emit!(KycApproved {
+            wallet: *wallet,
+            level,
+            expiry,
+            jurisdiction,
+        });
+    }
+
+    Ok(())
+}
+
+/// Account structure for revoking KYC records in batch.
+///
+/// The KYC records of the revoked wallets are passed as writable remaining accounts in the
+/// same order as the `wallets` argument.
+#[derive(Accounts)]
+pub struct RevokeKyc<'info> {
+    /// Program state, ensures `compliance` holds the compliance role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Compliance, &compliance.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or a compliance officer revoking the records.
+    pub compliance: Signer<'info>,
+}
+
+/// Revokes the KYC records of a batch of wallets.
+///
+/// Revoked records are kept with a level of `0`, so they no longer satisfy any offer requiring KYC.
+/// Emits a `KycRevoked` event per wallet.
+///
+/// # Errors
+/// - [`KycErrorCode::InvalidBatchSize`] if the batch is empty, too large, or doesn't match the remaining accounts.
+/// - [`KycErrorCode::InvalidKycRecord`] if a remaining account is not the KYC record of its wallet.
+pub fn revoke_kyc<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevokeKyc<'info>>,
+    wallets: Vec<Pubkey>,
+) -> Result<()> {
+    validate_batch(&wallets, ctx.remaining_accounts)?;
+
+    for (wallet, kyc_record_info) in wallets.iter().zip(ctx.remaining_accounts.iter()) {
+        let mut kyc_record = Account::<KycRecord>::try_from(kyc_record_info)?;
+        require_keys_eq!(kyc_record.wallet, *wallet, KycErrorCode::InvalidKycRecord);
+
+        kyc_record.level = 0;
+        kyc_record.exit(ctx.program_id)?;
+
+        emit!(KycRevoked { wallet: *wallet });
+    }
+
+    Ok(())
+}
+
+/// Account structure for changing the minimum KYC level of an offer.
+#[derive(Accounts)]
+pub struct SetOfferMinKycLevel<'info> {
+    /// The offer whose requirement is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the minimum KYC level required to take an offer, `0` to allow any wallet.
+///
+/// Emits an `OfferMinKycLevelSet` event.
+pub fn set_offer_min_kyc_level(ctx: Context<SetOfferMinKycLevel>, min_kyc_level: u8) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    offer.min_kyc_level = min_kyc_level;
+
+    emit!(OfferMinKycLevelSet {
+        offer_id: offer.offer_id,
+        min_kyc_level,
+    });
+
+    Ok(())
+}
+
+/// Checks that a taker satisfies the KYC requirement of an offer.
+///
+/// # Errors
+/// - [`KycErrorCode::MissingKycRecord`] if KYC is required and no record was passed.
+/// - [`KycErrorCode::InvalidKycRecord`] if the record belongs to another wallet.
+/// - [`KycErrorCode::InsufficientKycLevel`] if the record's level is below `min_kyc_level`.
+/// - [`KycErrorCode::KycExpired`] if the record has expired.
+pub fn verify_kyc(
+    min_kyc_level: u8,
+    kyc_record: Option<&Account<KycRecord>>,
+    user: Pubkey,
+    current_time: u64,
+) -> Result<()> {
+    if min_kyc_level == 0 {
+        return Ok(());
+    }
+
+    let kyc_record = kyc_record.ok_or(KycErrorCode::MissingKycRecord)?;
+    require_keys_eq!(kyc_record.wallet, user, KycErrorCode::InvalidKycRecord);
+    require!(
+        kyc_record.level >= min_kyc_level,
+        KycErrorCode::InsufficientKycLevel
+    );
+    require!(
+        kyc_record.expiry == 0 || current_time < kyc_record.expiry,
+        KycErrorCode::KycExpired
+    );
+
+    Ok(())
+}
+
+fn validate_batch(wallets: &[Pubkey], remaining_accounts: &[AccountInfo]) -> Result<()> {
+    require!(
+        !wallets.is_empty()
+            && wallets.len() <= MAX_KYC_BATCH_SIZE
+            && wallets.len() == remaining_accounts.len(),
+        KycErrorCode::InvalidBatchSize
+    );
+    Ok(())
+}
+
+/// Creates a KYC record PDA owned by the program, with rent paid by `payer`.
+///
+/// Accounts already holding lamports are topped up, allocated and assigned instead, so a
+/// record cannot be blocked by transferring lamports to its address beforehand.
+fn create_kyc_record<'info>(
+    kyc_record_info: &AccountInfo<'info>,
+    wallet: &Pubkey,
+    bump: u8,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let space = 8 + KycRecord::INIT_SPACE;
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let seeds = &[b"kyc".as_ref(), wallet.as_ref(), &[bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let current_lamports = kyc_record_info.lamports();
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: kyc_record_info.clone(),
+                },
+                signer_seeds,
+            ),
+            required_lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if current_lamports < required_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: kyc_record_info.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: kyc_record_info.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: kyc_record_info.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
+
+/// Error codes for KYC operations.
+#[error_code]
+pub enum KycErrorCode {
+    /// Triggered when the batch is empty, too large, or doesn't match the remaining accounts.
+    #[msg("Invalid batch size")]
+    InvalidBatchSize,
+
+    /// Triggered when approving with a level of zero.
+    #[msg("KYC level must be greater than zero")]
+    InvalidKycLevel,
+
+    /// Triggered when approving with an expiry in the past.
+    #[msg("KYC expiry must be in the future")]
+    InvalidExpiry,
+
+    /// Triggered when a passed account is not the KYC record of its wallet.
+    #[msg("Invalid KYC record account")]
+    InvalidKycRecord,
+
+    /// Triggered when taking an offer requiring KYC without a KYC record.
+    #[msg("The offer requires a KYC record")]
+    MissingKycRecord,
+
+    /// Triggered when the taker's KYC level is below the offer's minimum.
+    #[msg("KYC level is insufficient for this offer")]
+    InsufficientKycLevel,
+
+    /// Triggered when the taker's KYC record has expired.
+    #[msg("KYC record has expired")]
+    KycExpired,
+}
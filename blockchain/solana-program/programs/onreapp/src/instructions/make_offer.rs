@@ -1,5 +1,7 @@
 use crate::contexts::MakeOfferContext;
-use crate::state::{Offer, OfferToken, State};
+use crate::instructions::offer_registry::register_offer;
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{Offer, OfferRegistry, OfferStatus, OfferToken, Role, State};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 
@@ -40,18 +42,17 @@ pub struct OfferMadeTwo {
 /// - All Associated Token Accounts (ATAs) must be initialized prior to execution.
 ///   This includes `offer_sell_token_account`, `offer_buy_token_1_account`, and `boss_buy_token_1_account`.
 #[derive(Accounts)]
-#[instruction(offer_id: u64)]
 pub struct MakeOfferOne<'info> {
     /// The offer account to be initialized, with rent paid by `boss`.
     ///
     /// # Note
     /// - Space is allocated as `8 + Offer::INIT_SPACE` bytes, where 8 bytes are for the discriminator.
-    /// - Seeded with `"offer"` and `offer_id` for PDA derivation.
+    /// - Seeded with `"offer"` and `state.next_offer_id`, the ID handed out to the offer, for PDA derivation.
     #[account(
         init,
         payer = boss,
         space = 8 + Offer::INIT_SPACE,
-        seeds = [b"offer", offer_id.to_le_bytes().as_ref()],
+        seeds = [b"offer", state.next_offer_id.to_le_bytes().as_ref()],
         bump
   )]
     pub offer: Account<'info, Offer>,
@@ -79,7 +80,7 @@ pub struct MakeOfferOne<'info> {
     /// # Note
     /// This account is marked with `CHECK` as it's validated by the seed derivation.
     #[account(
-        seeds = [b"offer_authority", offer_id.to_le_bytes().as_ref()],
+        seeds = [b"offer_authority", state.next_offer_id.to_le_bytes().as_ref()],
         bump
   )]
     pub offer_token_authority: AccountInfo<'info>,
@@ -98,11 +99,27 @@ pub struct MakeOfferOne<'info> {
     /// Mint of the buy token 1 for the offer.
     pub buy_token_1_mint: Box<Account<'info, Mint>>,
 
-    /// Program state, ensures `boss` is authorized.
-    #[account(has_one = boss)]
+    /// Program state handing out the offer ID, ensures `boss` holds the offer manager role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
     pub state: Box<Account<'info, State>>,
 
-    /// The signer funding and authorizing the offer creation, typically the boss.
+    /// Registry of the open offers, grown by the ID of the offer with `boss` paying the rent.
+    #[account(
+        mut,
+        seeds = [b"offer_registry"],
+        bump,
+        realloc = OfferRegistry::space(offer_registry.offer_ids.len() + 1),
+        realloc::payer = boss,
+        realloc::zero = false
+    )]
+    pub offer_registry: Box<Account<'info, OfferRegistry>>,
+
+    /// The signer funding and authorizing the offer creation, the boss or an offer manager.
     #[account(mut)]
     pub boss: Signer<'info>,
 
@@ -154,18 +171,17 @@ impl<'info> MakeOfferContext<'info> for MakeOfferTwo<'info> {
 ///   This includes `offer_sell_token_account`, `offer_buy_token_1_account`, `offer_buy_token_2_account`,
 ///   `boss_buy_token_1_account`, and `boss_buy_token_2_account`.
 #[derive(Accounts)]
-#[instruction(offer_id: u64)]
 pub struct MakeOfferTwo<'info> {
     /// The offer account to be initialized, with rent paid by `boss`.
     ///
     /// # Note
     /// - Space is allocated as `8 + Offer::INIT_SPACE` bytes, where 8 bytes are for the discriminator.
-    /// - Seeded with `"offer"` and `offer_id` for PDA derivation.
+    /// - Seeded with `"offer"` and `state.next_offer_id`, the ID handed out to the offer, for PDA derivation.
     #[account(
         init,
         payer = boss,
         space = 8 + Offer::INIT_SPACE,
-        seeds = [b"offer", offer_id.to_le_bytes().as_ref()],
+        seeds = [b"offer", state.next_offer_id.to_le_bytes().as_ref()],
         bump
   )]
     pub offer: Account<'info, Offer>,
@@ -201,7 +217,7 @@ pub struct MakeOfferTwo<'info> {
     /// # Note
     /// This account is marked with `CHECK` as it's validated by the seed derivation.
     #[account(
-        seeds = [b"offer_authority", offer_id.to_le_bytes().as_ref()],
+        seeds = [b"offer_authority", state.next_offer_id.to_le_bytes().as_ref()],
         bump
   )]
     pub offer_token_authority: AccountInfo<'info>,
@@ -231,11 +247,27 @@ pub struct MakeOfferTwo<'info> {
     /// Mint of the buy token 2 for the offer.
     pub buy_token_2_mint: Box<Account<'info, Mint>>,
 
-    /// Program state, ensures `boss` is authorized.
-    #[account(has_one = boss)]
+    /// Program state handing out the offer ID, ensures `boss` holds the offer manager role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
     pub state: Box<Account<'info, State>>,
 
-    /// The signer funding and authorizing the offer creation, typically the boss.
+    /// Registry of the open offers, grown by the ID of the offer with `boss` paying the rent.
+    #[account(
+        mut,
+        seeds = [b"offer_registry"],
+        bump,
+        realloc = OfferRegistry::space(offer_registry.offer_ids.len() + 1),
+        realloc::payer = boss,
+        realloc::zero = false
+    )]
+    pub offer_registry: Box<Account<'info, OfferRegistry>>,
+
+    /// The signer funding and authorizing the offer creation, the boss or an offer manager.
     #[account(mut)]
     pub boss: Signer<'info>,
 
@@ -252,11 +284,10 @@ pub struct MakeOfferTwo<'info> {
 /// required in exchange varies over time, determined by `sell_token_start_amount`,
 /// `sell_token_end_amount`, `offer_start_time`, `offer_end_time`, and `price_fix_duration`.
 /// Transfers the specified `buy_token_total_amount` from the boss to the offer's account
-/// and emits an `OfferMadeOne` event.
+/// and emits an `OfferMadeOne` event. The offer gets the next offer ID and is listed in the offer registry.
 ///
 /// # Arguments
 /// - `ctx`: Context containing the accounts for the offer.
-/// - `offer_id`: Unique identifier for the offer, used in PDA derivation.
 /// - `buy_token_total_amount`: Total amount of the buy token to be offered.
 /// - `sell_token_start_amount`: The amount of sell token expected in exchange at the beginning of the offer.
 /// - `sell_token_end_amount`: The amount of sell token expected in exchange at the end of the offer.
@@ -269,13 +300,13 @@ pub struct MakeOfferTwo<'info> {
 /// # Errors
 /// - [`MakeOfferErrorCode::InsufficientBalance`] if the boss lacks sufficient `buy_token_total_amount`.
 /// - [`MakeOfferErrorCode::InvalidAmount`] if `buy_token_total_amount`, `sell_token_start_amount`,
-///   or `sell_token_end_amount` is zero. Also if `sell_token_start_amount` > `sell_token_end_amount`.
+///   or `sell_token_end_amount` is zero.
 /// - [`MakeOfferErrorCode::InvalidOfferTime`] if `offer_start_time` is not less than `offer_end_time`.
 /// - [`MakeOfferErrorCode::InvalidPriceFixDuration`] if `price_fix_duration` is zero or if the total
 ///   offer duration is less than `price_fix_duration`.
+/// - `OfferRegistryErrorCode::OfferIdOverflow` if every offer ID was handed out.
 pub fn make_offer_one(
     ctx: Context<MakeOfferOne>,
-    offer_id: u64,
     buy_token_total_amount: u64,
     sell_token_start_amount: u64,
     sell_token_end_amount: u64,
@@ -284,13 +315,14 @@ pub fn make_offer_one(
     price_fix_duration: u64,
 ) -> Result<()> {
     validate_non_zero_token_amounts(&[buy_token_total_amount, sell_token_start_amount, sell_token_end_amount])?;
-    validate_dynamic_price_params(sell_token_start_amount, sell_token_end_amount, offer_start_time, offer_end_time, price_fix_duration)?;
+    validate_dynamic_price_params(offer_start_time, offer_end_time, price_fix_duration)?;
 
     require!(
         ctx.accounts.boss_buy_token_1_account.amount >= buy_token_total_amount,
         MakeOfferErrorCode::InsufficientBalance
     );
 
+    let offer_id = register_offer(&mut ctx.accounts.state, &mut ctx.accounts.offer_registry)?;
     let offer = &mut ctx.accounts.offer;
     offer.offer_id = offer_id;
     offer.sell_token_mint = ctx.accounts.sell_token_mint.key();
@@ -303,6 +335,35 @@ pub fn make_offer_one(
     offer.buy_token_1 = OfferToken { mint: ctx.accounts.buy_token_1_mint.key(), amount: buy_token_total_amount };
     offer.buy_token_2 = OfferToken { mint: Pubkey::default(), amount: 0 };
     offer.authority_bump = ctx.bumps.offer_token_authority;
+    offer.nav_oracle = Pubkey::default();
+    offer.nav_spread_bps = 0;
+    offer.min_kyc_level = 0;
+    offer.max_per_user = 0;
+    offer.max_per_tx = 0;
+    offer.min_per_tx = 0;
+    offer.raise_cap = 0;
+    offer.total_raised = 0;
+    offer.status = OfferStatus::Active;
+    offer.fee_bps = None;
+    offer.referral_fee_bps = 0;
+    offer.boss = ctx.accounts.state.boss;
+    offer.uniform_price = false;
+    offer.clearing_sell_token_amount = 0;
+    offer.auction_settled = false;
+    offer.auction_sell_token_total = 0;
+    offer.auction_buy_token_1_total = 0;
+    offer.subscription = false;
+    offer.total_subscribed = 0;
+    offer.open_subscriptions = 0;
+    offer.min_raise = 0;
+    offer.escrowed_buy_token_1_total = 0;
+    offer.escrowed_buy_token_2_total = 0;
+    offer.open_receipts = 0;
+    offer.vesting_start_time = 0;
+    offer.vesting_cliff_duration = 0;
+    offer.vesting_duration = 0;
+    offer.schedule_start_time = 0;
+    offer.carried_sell_token_amount = 0;
 
     transfer_token(
         &ctx,
@@ -338,11 +399,10 @@ pub fn make_offer_one(
 /// required in exchange varies over time, determined by `sell_token_start_amount`,
 /// `sell_token_end_amount`, `offer_start_time`, `offer_end_time`, and `price_fix_duration`.
 /// Transfers the specified amounts of buy tokens from the boss to the offer's accounts
-/// and emits an `OfferMadeTwo` event.
+/// and emits an `OfferMadeTwo` event. The offer gets the next offer ID and is listed in the offer registry.
 ///
 /// # Arguments
 /// - `ctx`: Context containing the accounts for the offer.
-/// - `offer_id`: Unique identifier for the offer, used in PDA derivation.
 /// - `buy_token_1_total_amount`: Total amount of the first buy token to be offered.
 /// - `buy_token_2_total_amount`: Total amount of the second buy token to be offered.
 /// - `sell_token_start_amount`: The amount of sell token expected in exchange at the beginning of the offer.
@@ -357,13 +417,13 @@ pub fn make_offer_one(
 /// - [`MakeOfferErrorCode::InsufficientBalance`] if the boss lacks sufficient amounts for
 ///   `buy_token_1_total_amount` or `buy_token_2_total_amount`.
 /// - [`MakeOfferErrorCode::InvalidAmount`] if any buy token amount, `sell_token_start_amount`,
-///   or `sell_token_end_amount` is zero. Also if `sell_token_start_amount` > `sell_token_end_amount`.
+///   or `sell_token_end_amount` is zero.
 /// - [`MakeOfferErrorCode::InvalidOfferTime`] if `offer_start_time` is not less than `offer_end_time`.
 /// - [`MakeOfferErrorCode::InvalidPriceFixDuration`] if `price_fix_duration` is zero or if the total
 ///   offer duration is less than `price_fix_duration`.
+/// - `OfferRegistryErrorCode::OfferIdOverflow` if every offer ID was handed out.
 pub fn make_offer_two(
     ctx: Context<MakeOfferTwo>,
-    offer_id: u64,
     buy_token_1_total_amount: u64,
     buy_token_2_total_amount: u64,
     sell_token_start_amount: u64,
@@ -373,7 +433,7 @@ pub fn make_offer_two(
     price_fix_duration: u64,
 ) -> Result<()> {
     validate_non_zero_token_amounts(&[buy_token_1_total_amount, buy_token_2_total_amount, sell_token_start_amount, sell_token_end_amount])?;
-    validate_dynamic_price_params(sell_token_start_amount, sell_token_end_amount, offer_start_time, offer_end_time, price_fix_duration)?;
+    validate_dynamic_price_params(offer_start_time, offer_end_time, price_fix_duration)?;
 
     require!(
         ctx.accounts.boss_buy_token_1_account.amount >= buy_token_1_total_amount,
@@ -384,6 +444,7 @@ pub fn make_offer_two(
         MakeOfferErrorCode::InsufficientBalance
     );
 
+    let offer_id = register_offer(&mut ctx.accounts.state, &mut ctx.accounts.offer_registry)?;
     let offer = &mut ctx.accounts.offer;
     offer.offer_id = offer_id;
     offer.sell_token_mint = ctx.accounts.sell_token_mint.key();
@@ -395,6 +456,35 @@ pub fn make_offer_two(
     offer.offer_start_time = offer_start_time;
     offer.offer_end_time = offer_end_time;
     offer.authority_bump = ctx.bumps.offer_token_authority;
+    offer.nav_oracle = Pubkey::default();
+    offer.nav_spread_bps = 0;
+    offer.min_kyc_level = 0;
+    offer.max_per_user = 0;
+    offer.max_per_tx = 0;
+    offer.min_per_tx = 0;
+    offer.raise_cap = 0;
+    offer.total_raised = 0;
+    offer.status = OfferStatus::Active;
+    offer.fee_bps = None;
+    offer.referral_fee_bps = 0;
+    offer.boss = ctx.accounts.state.boss;
+    offer.uniform_price = false;
+    offer.clearing_sell_token_amount = 0;
+    offer.auction_settled = false;
+    offer.auction_sell_token_total = 0;
+    offer.auction_buy_token_1_total = 0;
+    offer.subscription = false;
+    offer.total_subscribed = 0;
+    offer.open_subscriptions = 0;
+    offer.min_raise = 0;
+    offer.escrowed_buy_token_1_total = 0;
+    offer.escrowed_buy_token_2_total = 0;
+    offer.open_receipts = 0;
+    offer.vesting_start_time = 0;
+    offer.vesting_cliff_duration = 0;
+    offer.vesting_duration = 0;
+    offer.schedule_start_time = 0;
+    offer.carried_sell_token_amount = 0;
 
     transfer_token(
         &ctx,
@@ -464,7 +554,11 @@ fn transfer_token<'info, T: MakeOfferContext<'info> + anchor_lang::Bumps>(
     Ok(())
 }
 
-fn validate_non_zero_token_amounts(token_amounts: &[u64]) -> Result<()> {
+/// Validates that every token amount is non-zero.
+///
+/// # Errors
+/// - [`MakeOfferErrorCode::InvalidAmount`] if any amount is zero.
+pub(crate) fn validate_non_zero_token_amounts(token_amounts: &[u64]) -> Result<()> {
     require!(
         token_amounts.iter().all(|&x| x > 0),
         MakeOfferErrorCode::InvalidAmount
@@ -472,11 +566,16 @@ fn validate_non_zero_token_amounts(token_amounts: &[u64]) -> Result<()> {
     Ok(())
 }
 
-fn validate_dynamic_price_params(sell_token_start_amount: u64, sell_token_end_amount: u64, offer_start_time: u64, offer_end_time: u64, price_fix_duration: u64) -> Result<()> {
-    require!(
-        sell_token_start_amount <= sell_token_end_amount,
-        MakeOfferErrorCode::InvalidAmount
-    );
+/// Validates the timing of the interval price schedule of an offer.
+///
+/// Any non-zero sell token amounts are valid: the schedule ascends if the end amount exceeds the
+/// start amount, and descends otherwise, the price decaying to the end amount floor as in a Dutch auction.
+///
+/// # Errors
+/// - [`MakeOfferErrorCode::InvalidOfferTime`] if the offer doesn't end after it starts or its duration
+///   isn't a multiple of `price_fix_duration`.
+/// - [`MakeOfferErrorCode::InvalidPriceFixDuration`] if `price_fix_duration` is zero or exceeds the offer's duration.
+pub(crate) fn validate_dynamic_price_params(offer_start_time: u64, offer_end_time: u64, price_fix_duration: u64) -> Result<()> {
     require!(
         offer_start_time < offer_end_time,
         MakeOfferErrorCode::InvalidOfferTime
@@ -504,7 +603,7 @@ pub enum MakeOfferErrorCode {
     InsufficientBalance,
 
     /// Triggered when the token transfer amount is zero, or sell token amounts are invalid.
-    #[msg("Token transfer amount must be greater than zero. Sell token start amount must be > 0, end amount must be > 0, and start <= end.")]
+    #[msg("Token transfer amount must be greater than zero. Sell token start amount must be > 0 and end amount must be > 0.")]
     InvalidAmount,
 
     #[msg("Token offer end time must be greater than start time and end time - start time must be divisible by price fix duration")]
@@ -0,0 +1,155 @@
+use crate::state::{Offer, State};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Accounts;
+
+/// Error codes for the migrate_state instruction.
+#[error_code]
+pub enum MigrateStateErrorCode {
+    /// Error when the signer is not the boss recorded in the state.
+    InvalidBoss,
+    /// Error when the migrated account is not an offer.
+    InvalidOffer,
+}
+
+/// Account structure for migrating the program state to the current layout.
+///
+/// The state may be smaller than the current layout and fail to deserialize, so it is taken
+/// as an unchecked account and validated by the handler.
+///
+/// # Preconditions
+/// - The `state` account must be initialized prior to execution, via an `initialize` instruction.
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// The program state account, resized to the current layout.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation and the
+    /// program ownership constraint, and its boss is checked by the handler.
+    #[account(mut, seeds = [b"state"], bump, owner = crate::ID)]
+    pub state: UncheckedAccount<'info>,
+
+    /// The boss, paying for the additional rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// Solana System program for rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates the program state to the current layout.
+///
+/// The state is resized to `8 + State::INIT_SPACE` bytes, with the boss topping up the rent.
+/// Fields added since the state was created are zero initialized by the resize, which is their
+/// default: not paused, no pending boss and no role grants, with offer IDs handed out from `0`.
+/// Offers made before with caller chosen IDs are skipped via `set_next_offer_id`. Calling it on
+/// an up to date state is a no-op.
+///
+/// # Errors
+/// - [`MigrateStateErrorCode::InvalidBoss`] if the signer is not the boss recorded in the state.
+pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    resize_account(
+        &state_info,
+        8 + State::INIT_SPACE,
+        &ctx.accounts.boss,
+        &ctx.accounts.system_program,
+    )?;
+
+    let state = State::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        state.boss,
+        ctx.accounts.boss.key(),
+        MigrateStateErrorCode::InvalidBoss
+    );
+    Ok(())
+}
+
+/// Account structure for migrating an offer to the current layout.
+///
+/// The offer may be smaller than the current layout and fail to deserialize, so it is taken
+/// as an unchecked account and validated by the handler.
+#[derive(Accounts)]
+pub struct MigrateOffer<'info> {
+    /// The offer account, resized to the current layout.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the program ownership constraint
+    /// and its discriminator is checked by the handler.
+    #[account(mut, owner = crate::ID)]
+    pub offer: UncheckedAccount<'info>,
+
+    /// Program state, ensures `boss` is the boss.
+    #[account(seeds = [b"state"], bump, has_one = boss @ MigrateStateErrorCode::InvalidBoss)]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss, paying for the additional rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// Solana System program for rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Migrates an offer made before the current layout.
+///
+/// The offer is resized to `8 + Offer::INIT_SPACE` bytes, with the boss topping up the rent.
+/// Fields added since the offer was made are zero initialized by the resize, which is their
+/// default: interval pricing from `offer_start_time`, no KYC, no limits, the global fee and no
+/// auction, subscription, minimum raise or vesting. The status keeps the former paused flag. An
+/// offer without a recorded boss gets the current boss, who receives its remaining tokens in
+/// `close_expired_offer`. Calling it on an up to date offer is a no-op.
+///
+/// # Errors
+/// - [`MigrateStateErrorCode::InvalidOffer`] if the account is not an offer.
+/// - [`MigrateStateErrorCode::InvalidBoss`] if the signer is not the boss.
+pub fn migrate_offer(ctx: Context<MigrateOffer>) -> Result<()> {
+    let offer_info = ctx.accounts.offer.to_account_info();
+    require!(
+        offer_info.try_borrow_data()?.starts_with(Offer::DISCRIMINATOR),
+        MigrateStateErrorCode::InvalidOffer
+    );
+    resize_account(
+        &offer_info,
+        8 + Offer::INIT_SPACE,
+        &ctx.accounts.boss,
+        &ctx.accounts.system_program,
+    )?;
+
+    let mut offer = Offer::try_deserialize(&mut &offer_info.try_borrow_data()?[..])?;
+    if offer.boss == Pubkey::default() {
+        offer.boss = ctx.accounts.state.boss;
+        offer.try_serialize(&mut &mut offer_info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Grows `account` to `space` bytes, with `payer` topping up the rent. Larger accounts are left as is.
+fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    if current_lamports < required_lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            required_lamports - current_lamports,
+        )?;
+    }
+    #[allow(deprecated)]
+    account.realloc(space, true)?;
+    Ok(())
+}
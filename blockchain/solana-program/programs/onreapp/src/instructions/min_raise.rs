@@ -0,0 +1,397 @@
+use crate::instructions::offer_inventory::verify_buy_token_2_accounts;
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::vesting::check_no_vesting;
+use crate::state::{Offer, Position, Role, State};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Event emitted when the minimum raise of an offer is changed.
+#[event]
+pub struct OfferMinRaiseSet {
+    pub offer_id: u64,
+    pub min_raise: u64,
+}
+
+/// Event emitted when the escrowed takes of a user are settled after a `min_raise` offer ends.
+#[event]
+pub struct ReceiptClaimed {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    /// Whether the offer met its minimum raise, delivering the buy tokens instead of refunding.
+    pub raise_met: bool,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    pub refund_amount: u64,
+}
+
+/// Account structure for changing the minimum raise of an offer.
+#[derive(Accounts)]
+pub struct SetOfferMinRaise<'info> {
+    /// The offer whose minimum raise is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the minimum raise of an offer, `0` for takes to settle instantly.
+///
+/// While the minimum raise is set, takes pay their sell tokens into the offer but the bought
+/// buy tokens stay in the offer, recorded in the user's position. Once the offer ends, receipts
+/// are settled via `claim_receipt`: the buy tokens are delivered if `total_raised` met the
+/// minimum raise, and the sell tokens paid are refunded in full otherwise, as such takes are not
+/// charged protocol or referral fees. Emits an `OfferMinRaiseSet` event.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::OfferAlreadyTaken`] if the offer was already taken.
+/// - [`MinRaiseErrorCode::MinRaiseOffer`] if the offer is NAV priced or in uniform price or subscription mode.
+/// - `VestingErrorCode::VestingOffer` if the offer has a vesting schedule.
+pub fn set_offer_min_raise(ctx: Context<SetOfferMinRaise>, min_raise: u64) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(offer.total_raised == 0, MinRaiseErrorCode::OfferAlreadyTaken);
+    require!(
+        offer.nav_oracle == Pubkey::default() && !offer.uniform_price && !offer.subscription,
+        MinRaiseErrorCode::MinRaiseOffer
+    );
+    check_no_vesting(offer)?;
+    offer.min_raise = min_raise;
+
+    emit!(OfferMinRaiseSet {
+        offer_id: offer.offer_id,
+        min_raise,
+    });
+
+    Ok(())
+}
+
+/// Account structure for settling the escrowed takes of a user.
+#[derive(Accounts)]
+pub struct ClaimReceipt<'info> {
+    /// The ended `min_raise` offer.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The user's position in the offer, holding the escrowed takes.
+    #[account(
+        mut,
+        seeds = [b"position", offer.key().as_ref(), user.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Offer's sell token ATA, sends the refund.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 1 ATA, sends buy token 1 to the user.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's sell token ATA, receives the refund.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's buy token 1 ATA, receives buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = user,
+    )]
+    pub user_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 2 ATA, required only to deliver the buy tokens of offers with two buy tokens.
+    #[account(mut)]
+    pub offer_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// User's buy token 2 ATA, required only to deliver the buy tokens of offers with two buy tokens.
+    #[account(mut)]
+    pub user_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// The owner of the position, receives the buy tokens or the refund.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation of `position`.
+    pub user: AccountInfo<'info>,
+
+    /// The caller settling the receipt, anyone so the boss can settle every receipt.
+    pub payer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Settles the escrowed takes of a user once a `min_raise` offer has ended.
+///
+/// Delivers the escrowed buy tokens if the offer met its minimum raise, and refunds the sell
+/// tokens the user paid into the offer otherwise, leaving the buy tokens to the boss. Anyone can
+/// settle on behalf of the user, the tokens always go to the user's ATAs. Emits a
+/// `ReceiptClaimed` event.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::OfferNotEnded`] if `offer_end_time` has not passed yet.
+/// - [`MinRaiseErrorCode::NothingToClaim`] if the position holds no escrowed takes.
+/// - [`MinRaiseErrorCode::CalculationOverflow`] if the escrowed totals of the offer underflow.
+/// - `OfferInventoryErrorCode` errors if buy tokens of an offer with two buy tokens are delivered
+///   and their accounts are missing or invalid.
+pub fn claim_receipt(ctx: Context<ClaimReceipt>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        current_time >= offer.offer_end_time,
+        MinRaiseErrorCode::OfferNotEnded
+    );
+    let position = &ctx.accounts.position;
+    require!(
+        position.escrowed_sell_token_amount > 0 || position.escrowed_buy_token_1_amount > 0,
+        MinRaiseErrorCode::NothingToClaim
+    );
+
+    let raise_met = offer.total_raised >= offer.min_raise;
+    let (buy_token_1_amount, buy_token_2_amount, refund_amount) = if raise_met {
+        (
+            position.escrowed_buy_token_1_amount,
+            position.escrowed_buy_token_2_amount,
+            0,
+        )
+    } else {
+        (0, 0, position.escrowed_sell_token_amount)
+    };
+
+    let offer_id_bytes = &offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes,
+        &[offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if raise_met {
+        let buy_token_2_accounts = verify_buy_token_2_accounts(
+            offer,
+            ctx.accounts.offer_buy_token_2_account.as_deref(),
+            ctx.accounts.user_buy_token_2_account.as_deref(),
+            &ctx.accounts.offer_token_authority.key(),
+            &ctx.accounts.user.key(),
+        )?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: ctx.accounts.user_buy_token_1_account.to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
+
+        if let Some((offer_buy_token_2_account, user_buy_token_2_account)) = buy_token_2_accounts {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: offer_buy_token_2_account.to_account_info(),
+                        to: user_buy_token_2_account.to_account_info(),
+                        authority: ctx.accounts.offer_token_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                buy_token_2_amount,
+            )?;
+            msg!("Transferring {} buy tokens 2 from offer to user", buy_token_2_amount);
+        }
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_sell_token_account.to_account_info(),
+                    to: ctx.accounts.user_sell_token_account.to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_amount,
+        )?;
+        msg!("Transferring {} sell tokens refund from offer to user", refund_amount);
+    }
+
+    emit!(ReceiptClaimed {
+        offer_id: offer.offer_id,
+        user: ctx.accounts.user.key(),
+        raise_met,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        refund_amount,
+    });
+
+    let position = &mut ctx.accounts.position;
+    let offer = &mut ctx.accounts.offer;
+    offer.escrowed_buy_token_1_total = offer
+        .escrowed_buy_token_1_total
+        .checked_sub(position.escrowed_buy_token_1_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    offer.escrowed_buy_token_2_total = offer
+        .escrowed_buy_token_2_total
+        .checked_sub(position.escrowed_buy_token_2_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    offer.open_receipts = offer
+        .open_receipts
+        .checked_sub(1)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    position.escrowed_sell_token_amount = 0;
+    position.escrowed_buy_token_1_amount = 0;
+    position.escrowed_buy_token_2_amount = 0;
+
+    Ok(())
+}
+
+/// Escrows a take of a `min_raise` offer in the user's position instead of settling it.
+///
+/// Does nothing for offers without a minimum raise.
+///
+/// # Arguments
+/// - `offer`: The taken offer.
+/// - `position`: The user's position in the offer.
+/// - `sell_token_amount`: Sell token amount paid into the offer, the whole take as no fees are charged.
+/// - `buy_token_1_amount`: Buy token 1 amount bought.
+/// - `buy_token_2_amount`: Buy token 2 amount bought, `0` for offers with one buy token.
+///
+/// # Returns
+/// Whether the take was escrowed, in which case the caller keeps the buy tokens in the offer.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::CalculationOverflow`] if a cumulative amount overflows.
+pub fn escrow_take(
+    offer: &mut Offer,
+    position: &mut Position,
+    sell_token_amount: u64,
+    buy_token_1_amount: u64,
+    buy_token_2_amount: u64,
+) -> Result<bool> {
+    if offer.min_raise == 0 {
+        return Ok(false);
+    }
+
+    if position.escrowed_sell_token_amount == 0 && position.escrowed_buy_token_1_amount == 0 {
+        offer.open_receipts = offer
+            .open_receipts
+            .checked_add(1)
+            .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    }
+    offer.escrowed_buy_token_1_total = offer
+        .escrowed_buy_token_1_total
+        .checked_add(buy_token_1_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    offer.escrowed_buy_token_2_total = offer
+        .escrowed_buy_token_2_total
+        .checked_add(buy_token_2_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    position.escrowed_sell_token_amount = position
+        .escrowed_sell_token_amount
+        .checked_add(sell_token_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    position.escrowed_buy_token_1_amount = position
+        .escrowed_buy_token_1_amount
+        .checked_add(buy_token_1_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+    position.escrowed_buy_token_2_amount = position
+        .escrowed_buy_token_2_amount
+        .checked_add(buy_token_2_amount)
+        .ok_or(MinRaiseErrorCode::CalculationOverflow)?;
+
+    Ok(true)
+}
+
+/// Checks that the proceeds of a `min_raise` offer belong to the boss.
+///
+/// That is the case once the offer ended having met its minimum raise, or once every receipt
+/// was settled.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::RaiseNotSettled`] if the proceeds may still be refunded.
+pub fn check_proceeds_unlocked(offer: &Offer, current_time: u64) -> Result<()> {
+    require!(
+        offer.open_receipts == 0
+            || (current_time >= offer.offer_end_time && offer.total_raised >= offer.min_raise),
+        MinRaiseErrorCode::RaiseNotSettled
+    );
+    Ok(())
+}
+
+/// Checks that every receipt of an offer was settled before its tokens are taken back.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::RaiseNotSettled`] if receipts are still open.
+pub fn check_no_open_receipts(offer: &Offer) -> Result<()> {
+    require!(offer.open_receipts == 0, MinRaiseErrorCode::RaiseNotSettled);
+    Ok(())
+}
+
+/// Checks that an offer has no minimum raise, which other settlement modes don't support.
+///
+/// # Errors
+/// - [`MinRaiseErrorCode::MinRaiseOffer`] if the offer has a minimum raise.
+pub fn check_no_min_raise(offer: &Offer) -> Result<()> {
+    require!(offer.min_raise == 0, MinRaiseErrorCode::MinRaiseOffer);
+    Ok(())
+}
+
+/// Error codes for minimum raise operations.
+#[error_code]
+pub enum MinRaiseErrorCode {
+    /// Triggered when changing the minimum raise of an offer that was already taken.
+    #[msg("The offer was already taken")]
+    OfferAlreadyTaken,
+
+    /// Triggered when combining a minimum raise with NAV pricing, uniform pricing, subscriptions or vesting.
+    #[msg("Minimum raise offers cannot use NAV pricing, uniform pricing, subscriptions or vesting")]
+    MinRaiseOffer,
+
+    /// Triggered when settling a receipt before the offer ended.
+    #[msg("The offer has not ended")]
+    OfferNotEnded,
+
+    /// Triggered when settling a position without escrowed takes.
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    /// Triggered when withdrawing from or closing an offer whose escrowed takes may still be refunded.
+    #[msg("The offer has unsettled receipts")]
+    RaiseNotSettled,
+
+    /// Triggered when a cumulative amount overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -2,12 +2,58 @@ pub mod make_offer;
 pub mod close_offer;
 pub mod take_offer;
 pub mod take_offer_one_permissionless;
+pub mod take_offer_two_permissionless;
 pub mod initialize;
 pub mod set_boss;
+pub mod nav_oracle;
+pub mod redemption_offer;
+pub mod redemption_queue;
+pub mod kyc;
+pub mod migrate_state;
+pub mod offer_limits;
+pub mod pause;
+pub mod roles;
+pub mod multisig;
+pub mod protocol_fee;
+pub mod referral;
+pub mod update_offer;
+pub mod offer_inventory;
+pub mod proceeds;
+pub mod dutch_auction;
+pub mod subscription;
+pub mod min_raise;
+pub mod vesting;
+pub mod permissionless_account;
+pub mod pricing;
+pub mod quote_offer;
+pub mod offer_registry;
 
 pub use make_offer::*;
 pub use close_offer::*;
 pub use take_offer::*;
 pub use take_offer_one_permissionless::*;
+pub use take_offer_two_permissionless::*;
 pub use initialize::*;
 pub use set_boss::*;
+pub use nav_oracle::*;
+pub use redemption_offer::*;
+pub use redemption_queue::*;
+pub use kyc::*;
+pub use migrate_state::*;
+pub use offer_limits::*;
+pub use pause::*;
+pub use roles::*;
+pub use multisig::*;
+pub use protocol_fee::*;
+pub use referral::*;
+pub use update_offer::*;
+pub use offer_inventory::*;
+pub use proceeds::*;
+pub use dutch_auction::*;
+pub use subscription::*;
+pub use min_raise::*;
+pub use vesting::*;
+pub use permissionless_account::*;
+pub use pricing::*;
+pub use quote_offer::*;
+pub use offer_registry::*;
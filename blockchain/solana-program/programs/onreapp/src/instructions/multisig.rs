@@ -0,0 +1,572 @@
+use crate::state::{
+    Multisig, Proposal, ProposalAccount, State, MAX_MULTISIG_SIGNERS, MAX_PROPOSAL_ACCOUNTS,
+    MAX_PROPOSAL_DATA_LEN,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Event emitted when the multisig is created.
+#[event]
+pub struct MultisigCreated {
+    pub multisig_authority: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Event emitted when the multisig's signers or threshold are changed.
+#[event]
+pub struct MultisigChanged {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Event emitted when a proposal is created.
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+}
+
+/// Event emitted when a signer approves a proposal.
+#[event]
+pub struct ProposalApproved {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    /// Approvals by current signers after this vote.
+    pub approvals: u8,
+}
+
+/// Event emitted when a signer rejects a proposal.
+#[event]
+pub struct ProposalRejected {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    /// Rejections by current signers after this vote.
+    pub rejections: u8,
+}
+
+/// Event emitted when a proposal is executed.
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+}
+
+/// Event emitted when an executed or rejected proposal is closed.
+#[event]
+pub struct ProposalClosed {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+}
+
+/// Account structure for creating the multisig.
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    /// The multisig to be initialized, with rent paid by `boss`.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [b"multisig"],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// Derived PDA signing executed proposals, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"multisig_authority"], bump)]
+    pub multisig_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `boss` is authorized.
+    #[account(has_one = boss)]
+    pub state: Account<'info, State>,
+
+    /// The boss creating the multisig and paying for its rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the multisig.
+///
+/// The multisig only acts as the boss once its authority PDA accepted the boss handover, by
+/// the boss proposing the authority with `propose_boss` and the multisig executing `accept_boss`.
+/// Emits a `MultisigCreated` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the multisig creation.
+/// - `signers`: Keys allowed to create, approve and reject proposals.
+/// - `threshold`: Number of approvals needed to execute a proposal.
+///
+/// # Errors
+/// - [`MultisigErrorCode::InvalidSigners`] if `signers` is empty, too long, or holds duplicates or the default public key.
+/// - [`MultisigErrorCode::InvalidThreshold`] if `threshold` is zero or greater than the number of signers.
+pub fn create_multisig(ctx: Context<CreateMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    validate_signers(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.authority_bump = ctx.bumps.multisig_authority;
+
+    emit!(MultisigCreated {
+        multisig_authority: ctx.accounts.multisig_authority.key(),
+        signers: multisig.signers.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
+
+/// Account structure for changing the multisig's signers and threshold.
+#[derive(Accounts)]
+pub struct ChangeMultisig<'info> {
+    /// The multisig being changed.
+    #[account(mut, seeds = [b"multisig"], bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The multisig authority, only signing through an executed proposal.
+    #[account(seeds = [b"multisig_authority"], bump = multisig.authority_bump)]
+    pub multisig_authority: Signer<'info>,
+}
+
+/// Changes the multisig's signers and threshold, through an executed proposal.
+///
+/// Votes of removed signers no longer count towards pending proposals. Emits a `MultisigChanged` event.
+///
+/// # Errors
+/// - [`MultisigErrorCode::InvalidSigners`] if `signers` is empty, too long, or holds duplicates or the default public key.
+/// - [`MultisigErrorCode::InvalidThreshold`] if `threshold` is zero or greater than the number of signers.
+pub fn change_multisig(ctx: Context<ChangeMultisig>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    validate_signers(&signers, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.signers = signers;
+    multisig.threshold = threshold;
+
+    emit!(MultisigChanged {
+        signers: multisig.signers.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
+
+/// Account structure for creating a proposal.
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// The multisig the proposal is made to.
+    #[account(
+        mut,
+        seeds = [b"multisig"],
+        bump,
+        constraint = multisig.signers.contains(&proposer.key()) @ MultisigErrorCode::NotASigner
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The proposal to be initialized, with rent paid by `proposer`.
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", multisig.key().as_ref(), multisig.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The multisig signer creating the proposal, counted as its first approval.
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a proposal to execute an onreapp instruction as the multisig authority.
+///
+/// The proposer's approval is recorded. Emits a `ProposalCreated` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the proposal creation.
+/// - `accounts`: Accounts of the instruction, only the multisig authority may be a signer.
+/// - `data`: Serialized instruction, including its discriminator.
+///
+/// # Errors
+/// - [`MultisigErrorCode::NotASigner`] if the proposer is not a signer of the multisig.
+/// - [`MultisigErrorCode::InvalidInstruction`] if the instruction is too large, requires another signer,
+///   executes a proposal, passes the proposal, or passes the multisig to anything but `change_multisig`.
+pub fn create_proposal(ctx: Context<CreateProposal>, accounts: Vec<ProposalAccount>, data: Vec<u8>) -> Result<()> {
+    let multisig_authority = Pubkey::create_program_address(
+        &[b"multisig_authority", &[ctx.accounts.multisig.authority_bump]],
+        ctx.program_id,
+    )
+    .map_err(|_| MultisigErrorCode::InvalidInstruction)?;
+    require!(
+        data.len() >= 8 && data.len() <= MAX_PROPOSAL_DATA_LEN,
+        MultisigErrorCode::InvalidInstruction
+    );
+    require!(
+        accounts.len() <= MAX_PROPOSAL_ACCOUNTS,
+        MultisigErrorCode::InvalidInstruction
+    );
+    require!(
+        accounts
+            .iter()
+            .all(|account| !account.is_signer || account.pubkey == multisig_authority),
+        MultisigErrorCode::InvalidInstruction
+    );
+    check_not_reentrant(
+        &ctx.accounts.multisig.key(),
+        &ctx.accounts.proposal.key(),
+        &accounts,
+        &data,
+    )?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.multisig = multisig.key();
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.approvals = vec![ctx.accounts.proposer.key()];
+    proposal.rejections = Vec::new();
+    proposal.executed = false;
+
+    multisig.proposal_count = multisig
+        .proposal_count
+        .checked_add(1)
+        .ok_or(MultisigErrorCode::CalculationOverflow)?;
+
+    emit!(ProposalCreated {
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+    });
+
+    Ok(())
+}
+
+/// Account structure for approving or rejecting a proposal.
+#[derive(Accounts)]
+pub struct VoteProposal<'info> {
+    /// The multisig the proposal belongs to.
+    #[account(
+        seeds = [b"multisig"],
+        bump,
+        constraint = multisig.signers.contains(&signer.key()) @ MultisigErrorCode::NotASigner
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The proposal voted on.
+    #[account(mut, has_one = multisig)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The multisig signer voting.
+    pub signer: Signer<'info>,
+}
+
+/// Approves a proposal.
+///
+/// Emits a `ProposalApproved` event.
+///
+/// # Errors
+/// - [`MultisigErrorCode::NotASigner`] if the signer is not a signer of the multisig.
+/// - [`MultisigErrorCode::ProposalClosed`] if the proposal was executed or rejected.
+/// - [`MultisigErrorCode::AlreadyVoted`] if the signer already voted on the proposal.
+pub fn approve_proposal(ctx: Context<VoteProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+    check_open(multisig, proposal)?;
+    check_not_voted(proposal, &signer)?;
+
+    proposal.approvals.push(signer);
+
+    emit!(ProposalApproved {
+        proposal_id: proposal.proposal_id,
+        signer,
+        approvals: count_votes(multisig, &proposal.approvals),
+    });
+
+    Ok(())
+}
+
+/// Rejects a proposal.
+///
+/// Once enough signers rejected the proposal for the threshold to be out of reach, it can no
+/// longer be approved or executed. Emits a `ProposalRejected` event.
+///
+/// # Errors
+/// - [`MultisigErrorCode::NotASigner`] if the signer is not a signer of the multisig.
+/// - [`MultisigErrorCode::ProposalClosed`] if the proposal was executed or rejected.
+/// - [`MultisigErrorCode::AlreadyVoted`] if the signer already voted on the proposal.
+pub fn reject_proposal(ctx: Context<VoteProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let signer = ctx.accounts.signer.key();
+    check_open(multisig, proposal)?;
+    check_not_voted(proposal, &signer)?;
+
+    proposal.rejections.push(signer);
+
+    emit!(ProposalRejected {
+        proposal_id: proposal.proposal_id,
+        signer,
+        rejections: count_votes(multisig, &proposal.rejections),
+    });
+
+    Ok(())
+}
+
+/// Account structure for executing a proposal.
+///
+/// The accounts of the proposed instruction are passed as remaining accounts, in the order
+/// stored in the proposal.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// The multisig the proposal belongs to.
+    #[account(seeds = [b"multisig"], bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The proposal to execute.
+    #[account(mut, has_one = multisig)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// Derived PDA signing the proposed instruction, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"multisig_authority"], bump = multisig.authority_bump)]
+    pub multisig_authority: AccountInfo<'info>,
+
+    /// The onreapp program, invoked with the proposed instruction.
+    pub onreapp_program: Program<'info, crate::program::OnreApp>,
+
+    /// Anyone executing the approved proposal.
+    pub executor: Signer<'info>,
+}
+
+/// Executes a proposal approved by the threshold of current signers.
+///
+/// The proposal is marked executed and persisted before the proposed instruction is invoked on
+/// onreapp with the multisig authority as signer. Emits a `ProposalExecuted` event.
+///
+/// # Errors
+/// - [`MultisigErrorCode::ProposalClosed`] if the proposal was executed or rejected.
+/// - [`MultisigErrorCode::ThresholdNotMet`] if fewer current signers than the threshold approved.
+/// - [`MultisigErrorCode::InvalidProposalAccounts`] if the remaining accounts don't match the proposal.
+/// - [`MultisigErrorCode::InvalidInstruction`] if the instruction executes a proposal or passes the
+///   proposal, or passes the multisig to anything but `change_multisig`.
+pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    check_open(multisig, proposal)?;
+    require!(
+        count_votes(multisig, &proposal.approvals) >= multisig.threshold,
+        MultisigErrorCode::ThresholdNotMet
+    );
+    require!(
+        ctx.remaining_accounts.len() == proposal.accounts.len()
+            && ctx
+                .remaining_accounts
+                .iter()
+                .zip(proposal.accounts.iter())
+                .all(|(info, account)| info.key() == account.pubkey),
+        MultisigErrorCode::InvalidProposalAccounts
+    );
+    check_not_reentrant(&multisig.key(), &proposal.key(), &proposal.accounts, &proposal.data)?;
+
+    proposal.executed = true;
+    proposal.exit(&crate::ID)?;
+
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: proposal
+            .accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: proposal.data.clone(),
+    };
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.multisig_authority.to_account_info());
+    account_infos.push(ctx.accounts.onreapp_program.to_account_info());
+
+    let seeds = &[b"multisig_authority".as_ref(), &[multisig.authority_bump]];
+    let signer_seeds = &[&seeds[..]];
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+
+    msg!("Executed proposal {}", proposal.proposal_id);
+    emit!(ProposalExecuted {
+        proposal_id: proposal.proposal_id,
+        executor: ctx.accounts.executor.key(),
+    });
+
+    Ok(())
+}
+
+/// Account structure for closing an executed or rejected proposal.
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    /// The multisig the proposal belongs to.
+    #[account(seeds = [b"multisig"], bump)]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The proposal to close, with its rent refunded to `proposer`.
+    #[account(mut, has_one = multisig, has_one = proposer, close = proposer)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// The signer that created the proposal, receives its rent.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the `has_one` constraint of `proposal`.
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+/// Closes a proposal that was executed or rejected, refunding its rent to the proposer.
+///
+/// Proposal IDs are never reused, so a closed proposal can't be recreated and executed again.
+/// Anyone can close the proposal. Emits a `ProposalClosed` event.
+///
+/// # Errors
+/// - [`MultisigErrorCode::ProposalOpen`] if the proposal can still be approved or executed.
+pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    require!(
+        !is_open(&ctx.accounts.multisig, proposal),
+        MultisigErrorCode::ProposalOpen
+    );
+
+    emit!(ProposalClosed {
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+    });
+
+    Ok(())
+}
+
+fn validate_signers(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+        MultisigErrorCode::InvalidSigners
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(
+            *signer != Pubkey::default() && !signers[..i].contains(signer),
+            MultisigErrorCode::InvalidSigners
+        );
+    }
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        MultisigErrorCode::InvalidThreshold
+    );
+    Ok(())
+}
+
+/// Checks that the proposed instruction can't reenter the multisig.
+///
+/// Proposals can't execute proposals, and can't pass the proposal itself. The multisig may only
+/// be passed to `change_multisig`, whose changes are kept as `execute_proposal` doesn't write the
+/// multisig back.
+fn check_not_reentrant(
+    multisig: &Pubkey,
+    proposal: &Pubkey,
+    accounts: &[ProposalAccount],
+    data: &[u8],
+) -> Result<()> {
+    require!(
+        !data.starts_with(crate::instruction::ExecuteProposal::DISCRIMINATOR),
+        MultisigErrorCode::InvalidInstruction
+    );
+    let changes_multisig = data.starts_with(crate::instruction::ChangeMultisig::DISCRIMINATOR);
+    require!(
+        accounts.iter().all(|account| {
+            account.pubkey != *proposal && (changes_multisig || account.pubkey != *multisig)
+        }),
+        MultisigErrorCode::InvalidInstruction
+    );
+    Ok(())
+}
+
+/// Counts the votes cast by current signers of the multisig.
+fn count_votes(multisig: &Multisig, votes: &[Pubkey]) -> u8 {
+    votes
+        .iter()
+        .filter(|vote| multisig.signers.contains(vote))
+        .count() as u8
+}
+
+/// Returns whether the proposal was neither executed nor rejected by enough signers to be out of reach.
+fn is_open(multisig: &Multisig, proposal: &Proposal) -> bool {
+    let max_rejections = multisig.signers.len() as u8 - multisig.threshold;
+    !proposal.executed && count_votes(multisig, &proposal.rejections) <= max_rejections
+}
+
+fn check_open(multisig: &Multisig, proposal: &Proposal) -> Result<()> {
+    require!(is_open(multisig, proposal), MultisigErrorCode::ProposalClosed);
+    Ok(())
+}
+
+fn check_not_voted(proposal: &Proposal, signer: &Pubkey) -> Result<()> {
+    require!(
+        !proposal.approvals.contains(signer) && !proposal.rejections.contains(signer),
+        MultisigErrorCode::AlreadyVoted
+    );
+    Ok(())
+}
+
+/// Error codes for multisig operations.
+#[error_code]
+pub enum MultisigErrorCode {
+    /// Triggered when the signers are empty, too many, or hold duplicates or the default public key.
+    #[msg("Invalid multisig signers")]
+    InvalidSigners,
+
+    /// Triggered when the threshold is zero or greater than the number of signers.
+    #[msg("Invalid multisig threshold")]
+    InvalidThreshold,
+
+    /// Triggered when the signer is not a signer of the multisig.
+    #[msg("The signer is not a signer of the multisig")]
+    NotASigner,
+
+    /// Triggered when the proposed instruction is too large or requires another signer.
+    #[msg("Invalid proposed instruction")]
+    InvalidInstruction,
+
+    /// Triggered when voting on or executing a proposal that was executed or rejected.
+    #[msg("The proposal was executed or rejected")]
+    ProposalClosed,
+
+    /// Triggered when a signer votes twice on a proposal.
+    #[msg("The signer already voted on this proposal")]
+    AlreadyVoted,
+
+    /// Triggered when executing a proposal without enough approvals.
+    #[msg("The proposal does not have enough approvals")]
+    ThresholdNotMet,
+
+    /// Triggered when the remaining accounts don't match the proposed instruction.
+    #[msg("The accounts do not match the proposal")]
+    InvalidProposalAccounts,
+
+    /// Triggered when the proposal count overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+
+    /// Triggered when closing a proposal that can still be approved or executed.
+    #[msg("The proposal is still open")]
+    ProposalOpen,
+}
@@ -0,0 +1,399 @@
+use crate::instructions::min_raise::check_no_min_raise;
+use crate::instructions::pricing::{mul_div, Rounding};
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{BPS_DENOMINATOR, NAV_PRECISION, NavOracle, Offer, Role, State};
+use anchor_lang::prelude::*;
+use anchor_lang::Accounts;
+
+/// Largest spread, in either direction, that an offer can apply on top of the NAV.
+pub const MAX_NAV_SPREAD_BPS: i16 = 5_000;
+
+/// Event emitted when a NAV oracle is created for a pool.
+#[event]
+pub struct NavOracleInitialized {
+    pub pool_id: u64,
+    pub oracle_authority: Pubkey,
+    pub max_staleness: u64,
+}
+
+/// Event emitted when the oracle authority publishes a new NAV.
+#[event]
+pub struct NavUpdated {
+    pub pool_id: u64,
+    pub nav: u64,
+    pub timestamp: u64,
+}
+
+/// Event emitted when the boss reconfigures a NAV oracle.
+#[event]
+pub struct NavOracleConfigured {
+    pub pool_id: u64,
+    pub oracle_authority: Pubkey,
+    pub max_staleness: u64,
+    pub is_active: bool,
+}
+
+/// Event emitted when an offer is switched to or from NAV based pricing.
+#[event]
+pub struct OfferNavPricingSet {
+    pub offer_id: u64,
+    /// The referenced oracle, `Pubkey::default()` when the offer falls back to its interval schedule.
+    pub nav_oracle: Pubkey,
+    pub nav_spread_bps: i16,
+}
+
+/// Account structure for creating the NAV oracle of a pool.
+///
+/// # Preconditions
+/// - The oracle for `pool_id` must not exist prior to execution.
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct InitializeNavOracle<'info> {
+    /// The NAV oracle account to be initialized, with rent paid by `boss`.
+    ///
+    /// # Note
+    /// - Space is allocated as `8 + NavOracle::INIT_SPACE` bytes, where 8 bytes are for the discriminator.
+    /// - Seeded with `"nav_oracle"` and `pool_id` for PDA derivation.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + NavOracle::INIT_SPACE,
+        seeds = [b"nav_oracle", pool_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub nav_oracle: Account<'info, NavOracle>,
+
+    /// Program state, ensures `boss` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager creating the oracle and paying for its rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the NAV oracle of a pool.
+///
+/// The oracle starts active but without a NAV, so offers referencing it reject trades until
+/// the oracle authority publishes the first value through `update_nav`.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the oracle creation.
+/// - `pool_id`: Identifier of the pool, used in PDA derivation.
+/// - `oracle_authority`: Key allowed to publish NAV updates.
+/// - `max_staleness`: Maximum age in seconds of a NAV that offers still accept.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::InvalidOracleAuthority`] if `oracle_authority` is the default public key.
+/// - [`NavOracleErrorCode::InvalidMaxStaleness`] if `max_staleness` is zero.
+pub fn initialize_nav_oracle(
+    ctx: Context<InitializeNavOracle>,
+    pool_id: u64,
+    oracle_authority: Pubkey,
+    max_staleness: u64,
+) -> Result<()> {
+    require!(
+        oracle_authority != Pubkey::default(),
+        NavOracleErrorCode::InvalidOracleAuthority
+    );
+    require!(max_staleness > 0, NavOracleErrorCode::InvalidMaxStaleness);
+
+    let nav_oracle = &mut ctx.accounts.nav_oracle;
+    nav_oracle.pool_id = pool_id;
+    nav_oracle.oracle_authority = oracle_authority;
+    nav_oracle.nav = 0;
+    nav_oracle.last_update_time = 0;
+    nav_oracle.max_staleness = max_staleness;
+    nav_oracle.is_active = true;
+    nav_oracle.bump = ctx.bumps.nav_oracle;
+
+    emit!(NavOracleInitialized {
+        pool_id,
+        oracle_authority,
+        max_staleness,
+    });
+
+    Ok(())
+}
+
+/// Account structure for publishing a NAV update.
+#[derive(Accounts)]
+pub struct UpdateNav<'info> {
+    /// The NAV oracle being updated, must be controlled by `oracle_authority`.
+    #[account(mut, has_one = oracle_authority @ NavOracleErrorCode::InvalidOracleAuthority)]
+    pub nav_oracle: Account<'info, NavOracle>,
+
+    /// The oracle authority publishing the NAV.
+    pub oracle_authority: Signer<'info>,
+}
+
+/// Publishes a new NAV for a pool.
+///
+/// The update is timestamped with the cluster clock, which restarts the staleness window.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the update.
+/// - `nav`: New NAV, in sell token base units per `NAV_PRECISION` pool token base units.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::InvalidNav`] if `nav` is zero.
+/// - [`NavOracleErrorCode::InvalidOracleAuthority`] if the signer is not the oracle authority.
+pub fn update_nav(ctx: Context<UpdateNav>, nav: u64) -> Result<()> {
+    require!(nav > 0, NavOracleErrorCode::InvalidNav);
+
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let nav_oracle = &mut ctx.accounts.nav_oracle;
+    nav_oracle.nav = nav;
+    nav_oracle.last_update_time = timestamp;
+    msg!("NAV of pool {} updated to {}", nav_oracle.pool_id, nav);
+
+    emit!(NavUpdated {
+        pool_id: nav_oracle.pool_id,
+        nav,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Account structure for reconfiguring a NAV oracle.
+#[derive(Accounts)]
+pub struct ConfigureNavOracle<'info> {
+    /// The NAV oracle being reconfigured.
+    #[account(mut)]
+    pub nav_oracle: Account<'info, NavOracle>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Reconfigures a NAV oracle.
+///
+/// Rotates the oracle authority, changes the staleness window, or (de)activates the pool.
+/// Offers referencing a deactivated pool reject every trade.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the change.
+/// - `oracle_authority`: Key allowed to publish NAV updates.
+/// - `max_staleness`: Maximum age in seconds of a NAV that offers still accept.
+/// - `is_active`: Whether the pool is active.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::InvalidOracleAuthority`] if `oracle_authority` is the default public key.
+/// - [`NavOracleErrorCode::InvalidMaxStaleness`] if `max_staleness` is zero.
+pub fn configure_nav_oracle(
+    ctx: Context<ConfigureNavOracle>,
+    oracle_authority: Pubkey,
+    max_staleness: u64,
+    is_active: bool,
+) -> Result<()> {
+    require!(
+        oracle_authority != Pubkey::default(),
+        NavOracleErrorCode::InvalidOracleAuthority
+    );
+    require!(max_staleness > 0, NavOracleErrorCode::InvalidMaxStaleness);
+
+    let nav_oracle = &mut ctx.accounts.nav_oracle;
+    nav_oracle.oracle_authority = oracle_authority;
+    nav_oracle.max_staleness = max_staleness;
+    nav_oracle.is_active = is_active;
+
+    emit!(NavOracleConfigured {
+        pool_id: nav_oracle.pool_id,
+        oracle_authority,
+        max_staleness,
+        is_active,
+    });
+
+    Ok(())
+}
+
+/// Account structure for switching an offer to or from NAV based pricing.
+#[derive(Accounts)]
+pub struct SetOfferNavPricing<'info> {
+    /// The offer whose pricing is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The NAV oracle to price the offer against. Omit to return to the interval schedule.
+    pub nav_oracle: Option<Account<'info, NavOracle>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Switches an offer to or from NAV based pricing.
+///
+/// While an oracle is referenced, takers pay `nav * (10_000 + nav_spread_bps) / 10_000` sell token
+/// base units per `NAV_PRECISION` buy token 1 base units, and the interval schedule is ignored.
+/// For offers with two buy tokens, buy token 2 is paid out in proportion to buy token 1.
+/// Subscription, uniform price and minimum raise offers settle at their schedule's prices and
+/// cannot reference an oracle.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the change.
+/// - `nav_spread_bps`: Spread applied on top of the NAV, positive for a premium and negative for a discount.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::InvalidSpread`] if `nav_spread_bps` exceeds `MAX_NAV_SPREAD_BPS` in either direction.
+/// - [`NavOracleErrorCode::UnsupportedOffer`] if an oracle is referenced by a subscription or uniform price offer.
+/// - `MinRaiseErrorCode::MinRaiseOffer` if an oracle is referenced by an offer with a minimum raise.
+pub fn set_offer_nav_pricing(ctx: Context<SetOfferNavPricing>, nav_spread_bps: i16) -> Result<()> {
+    require!(
+        (-MAX_NAV_SPREAD_BPS..=MAX_NAV_SPREAD_BPS).contains(&nav_spread_bps),
+        NavOracleErrorCode::InvalidSpread
+    );
+
+    let nav_oracle = ctx
+        .accounts
+        .nav_oracle
+        .as_ref()
+        .map_or(Pubkey::default(), |nav_oracle| nav_oracle.key());
+
+    let offer = &mut ctx.accounts.offer;
+    if nav_oracle != Pubkey::default() {
+        require!(
+            !offer.subscription && !offer.uniform_price,
+            NavOracleErrorCode::UnsupportedOffer
+        );
+        check_no_min_raise(offer)?;
+    }
+    offer.nav_oracle = nav_oracle;
+    offer.nav_spread_bps = if nav_oracle == Pubkey::default() { 0 } else { nav_spread_bps };
+
+    emit!(OfferNavPricingSet {
+        offer_id: offer.offer_id,
+        nav_oracle,
+        nav_spread_bps: offer.nav_spread_bps,
+    });
+
+    Ok(())
+}
+
+/// Returns the NAV of an oracle after checking that it can be used for pricing.
+///
+/// # Arguments
+/// - `nav_oracle`: The oracle account passed to the instruction.
+/// - `expected_nav_oracle`: The oracle referenced by the offer being priced.
+/// - `current_time`: Current unix timestamp.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::NavOracleMismatch`] if `nav_oracle` is not `expected_nav_oracle`.
+/// - [`NavOracleErrorCode::PoolDeactivated`] if the pool has been deactivated.
+/// - [`NavOracleErrorCode::StaleNav`] if no NAV was published yet or it is older than `max_staleness`.
+pub fn current_nav(
+    nav_oracle: &Account<NavOracle>,
+    expected_nav_oracle: Pubkey,
+    current_time: u64,
+) -> Result<u64> {
+    require!(
+        nav_oracle.key() == expected_nav_oracle,
+        NavOracleErrorCode::NavOracleMismatch
+    );
+    require!(nav_oracle.is_active, NavOracleErrorCode::PoolDeactivated);
+    require!(
+        nav_oracle.nav > 0
+            && current_time.saturating_sub(nav_oracle.last_update_time) <= nav_oracle.max_staleness,
+        NavOracleErrorCode::StaleNav
+    );
+    Ok(nav_oracle.nav)
+}
+
+/// Applies a signed basis point spread to a NAV.
+///
+/// The result is rounded in the given direction, so that callers can round in favour of the offer.
+///
+/// # Errors
+/// - `PricingErrorCode::CalculationOverflow` if the result does not fit in a `u64`.
+pub fn apply_nav_spread(nav: u64, nav_spread_bps: i16, rounding: Rounding) -> Result<u64> {
+    // Spreads are bounded by `MAX_NAV_SPREAD_BPS`, so the factor is positive.
+    let spread_factor = (BPS_DENOMINATOR as i64 + nav_spread_bps as i64) as u64;
+    mul_div(nav, spread_factor, BPS_DENOMINATOR, rounding)
+}
+
+/// Calculates the sell token amount matching the offer's total buy token 1 amount at the current NAV.
+///
+/// The result plays the same role as the interval price of scheduled offers, so it can be fed
+/// directly into `pricing::calculate_buy_amount`. The NAV plus spread and the amount are both
+/// rounded up, in favour of the offer.
+///
+/// # Arguments
+/// - `offer`: The offer being taken, referencing `nav_oracle`.
+/// - `nav_oracle`: The oracle account passed to the take instruction.
+/// - `current_time`: Current unix timestamp.
+///
+/// # Errors
+/// - Any error of [`current_nav`].
+/// - `PricingErrorCode::CalculationOverflow` if the amount does not fit in a `u64`.
+pub fn calculate_nav_sell_amount(
+    offer: &Offer,
+    nav_oracle: &Account<NavOracle>,
+    current_time: u64,
+) -> Result<u64> {
+    let nav = current_nav(nav_oracle, offer.nav_oracle, current_time)?;
+    let price = apply_nav_spread(nav, offer.nav_spread_bps, Rounding::Up)?;
+    mul_div(offer.buy_token_1.amount, price, NAV_PRECISION, Rounding::Up)
+}
+
+/// Error codes for NAV oracle operations.
+#[error_code]
+pub enum NavOracleErrorCode {
+    /// Triggered when the oracle authority is the default key or does not sign the update.
+    #[msg("Invalid oracle authority")]
+    InvalidOracleAuthority,
+
+    /// Triggered when the staleness window is zero.
+    #[msg("Max staleness must be greater than zero")]
+    InvalidMaxStaleness,
+
+    /// Triggered when a zero NAV is published.
+    #[msg("NAV must be greater than zero")]
+    InvalidNav,
+
+    /// Triggered when the spread is out of bounds.
+    #[msg("NAV spread is out of bounds")]
+    InvalidSpread,
+
+    /// Triggered when a NAV priced offer is taken without its oracle account.
+    #[msg("The NAV oracle referenced by the offer is missing")]
+    MissingNavOracle,
+
+    /// Triggered when the oracle account passed does not match the one referenced by the offer.
+    #[msg("The NAV oracle does not match the offer")]
+    NavOracleMismatch,
+
+    /// Triggered when the pool of the referenced oracle is deactivated.
+    #[msg("The pool is deactivated")]
+    PoolDeactivated,
+
+    /// Triggered when the NAV is missing or older than the allowed staleness window.
+    #[msg("The NAV is stale")]
+    StaleNav,
+
+    /// Triggered when referencing an oracle from a subscription or uniform price offer.
+    #[msg("Subscription and uniform price offers cannot use NAV pricing")]
+    UnsupportedOffer,
+}
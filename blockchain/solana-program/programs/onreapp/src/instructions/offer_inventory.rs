@@ -0,0 +1,419 @@
+use crate::instructions::min_raise::check_no_open_receipts;
+use crate::instructions::pricing::{mul_div, Rounding};
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::subscription::check_no_open_subscriptions;
+use crate::state::{Offer, Role, State};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Event emitted when buy tokens are added to an offer.
+#[event]
+pub struct OfferToppedUp {
+    pub offer_id: u64,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    pub buy_token_1_total_amount: u64,
+    pub buy_token_2_total_amount: u64,
+    pub sell_token_start_amount: u64,
+    pub sell_token_end_amount: u64,
+}
+
+/// Event emitted when unsold buy tokens are withdrawn from an offer.
+#[event]
+pub struct UnsoldWithdrawn {
+    pub offer_id: u64,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    pub buy_token_1_total_amount: u64,
+    pub buy_token_2_total_amount: u64,
+    pub sell_token_start_amount: u64,
+    pub sell_token_end_amount: u64,
+}
+
+/// Account structure for adding buy tokens to an offer.
+///
+/// The buy token 2 accounts are required only for offers with two buy tokens.
+#[derive(Accounts)]
+pub struct TopUpOffer<'info> {
+    /// The offer receiving the buy tokens.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Offer's buy token 1 ATA, receives buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's buy token 1 ATA, sends buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 2 ATA, receives buy token 2.
+    #[account(mut)]
+    pub offer_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Boss's buy token 2 ATA, sends buy token 2.
+    #[account(mut)]
+    pub boss_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `boss` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The signer funding and authorizing the top-up, the boss or an offer manager.
+    pub boss: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Adds buy tokens to an offer without changing its price.
+///
+/// `OfferToken.amount` is the quantity of buy tokens priced by the sell token amounts, so the
+/// offer is scaled as a whole: `buy_token_1.amount` grows by `buy_token_1_amount` and the sell
+/// token amounts and `buy_token_2.amount` grow by the same factor. Sell token amounts are rounded
+/// up and `buy_token_2.amount` down, in the offer's favour. For offers with two buy tokens the
+/// resulting increase of `buy_token_2.amount` is transferred as well. Emits an `OfferToppedUp` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the top-up.
+/// - `buy_token_1_amount`: Amount of buy token 1 added to the offer.
+///
+/// # Errors
+/// - [`OfferInventoryErrorCode::InvalidAmount`] if `buy_token_1_amount` is zero.
+/// - [`OfferInventoryErrorCode::MissingBuyToken2Accounts`] if the offer has two buy tokens and their accounts are missing.
+/// - [`OfferInventoryErrorCode::InvalidBuyToken2Account`] if a buy token 2 account is not the expected ATA.
+/// - [`OfferInventoryErrorCode::CalculationOverflow`] if the buy token 1 amount overflows.
+/// - `PricingErrorCode::CalculationOverflow` if the scaled amounts overflow.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if receipts of the offer were not settled.
+pub fn top_up_offer(ctx: Context<TopUpOffer>, buy_token_1_amount: u64) -> Result<()> {
+    require!(buy_token_1_amount > 0, OfferInventoryErrorCode::InvalidAmount);
+
+    let offer = &mut ctx.accounts.offer;
+    check_no_open_subscriptions(offer)?;
+    check_no_open_receipts(offer)?;
+    let buy_token_2_accounts = verify_buy_token_2_accounts(
+        offer,
+        ctx.accounts.offer_buy_token_2_account.as_deref(),
+        ctx.accounts.boss_buy_token_2_account.as_deref(),
+        &ctx.accounts.offer_token_authority.key(),
+        &ctx.accounts.boss.key(),
+    )?;
+
+    let buy_token_1_total_amount = offer
+        .buy_token_1
+        .amount
+        .checked_add(buy_token_1_amount)
+        .ok_or(OfferInventoryErrorCode::CalculationOverflow)?;
+    let old_buy_token_2_total_amount = offer.buy_token_2.amount;
+    rescale_offer(offer, buy_token_1_total_amount)?;
+    let buy_token_2_amount = offer.buy_token_2.amount - old_buy_token_2_total_amount;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.boss_buy_token_1_account.to_account_info(),
+                to: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                authority: ctx.accounts.boss.to_account_info(),
+            },
+        ),
+        buy_token_1_amount,
+    )?;
+    msg!("Transferring {} buy tokens 1 from boss to offer", buy_token_1_amount);
+
+    if let Some((offer_buy_token_2_account, boss_buy_token_2_account)) = buy_token_2_accounts {
+        if buy_token_2_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: boss_buy_token_2_account.to_account_info(),
+                        to: offer_buy_token_2_account.to_account_info(),
+                        authority: ctx.accounts.boss.to_account_info(),
+                    },
+                ),
+                buy_token_2_amount,
+            )?;
+            msg!("Transferring {} buy tokens 2 from boss to offer", buy_token_2_amount);
+        }
+    }
+
+    emit!(OfferToppedUp {
+        offer_id: offer.offer_id,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        buy_token_1_total_amount: offer.buy_token_1.amount,
+        buy_token_2_total_amount: offer.buy_token_2.amount,
+        sell_token_start_amount: offer.sell_token_start_amount,
+        sell_token_end_amount: offer.sell_token_end_amount,
+    });
+
+    Ok(())
+}
+
+/// Account structure for withdrawing unsold buy tokens from an offer.
+///
+/// The buy token 2 accounts are required only for offers with two buy tokens.
+#[derive(Accounts)]
+pub struct WithdrawUnsold<'info> {
+    /// The offer the buy tokens are withdrawn from.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Offer's buy token 1 ATA, sends buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's buy token 1 ATA, receives buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 2 ATA, sends buy token 2.
+    #[account(mut)]
+    pub offer_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Boss's buy token 2 ATA, receives buy token 2.
+    #[account(mut)]
+    pub boss_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `boss` is the boss and `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        has_one = boss,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss, receiving the withdrawn buy tokens.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the `has_one = boss` constraint of `state`.
+    pub boss: AccountInfo<'info>,
+
+    /// The boss or an offer manager authorizing the withdrawal.
+    pub authority: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraws unsold buy tokens from an offer without changing its price.
+///
+/// The inverse of [`top_up_offer`]: `buy_token_1.amount` shrinks by `buy_token_1_amount` and
+/// the sell token amounts and `buy_token_2.amount` shrink by the same factor, with the same
+/// rounding. For offers with two buy tokens the resulting decrease of `buy_token_2.amount` is
+/// withdrawn as well. Emits an `UnsoldWithdrawn` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the withdrawal.
+/// - `buy_token_1_amount`: Amount of buy token 1 withdrawn from the offer.
+///
+/// # Errors
+/// - [`OfferInventoryErrorCode::InvalidAmount`] if `buy_token_1_amount` is zero or not below `buy_token_1.amount`.
+/// - [`OfferInventoryErrorCode::InsufficientInventory`] if the offer holds fewer unsold buy tokens than withdrawn.
+/// - [`OfferInventoryErrorCode::MissingBuyToken2Accounts`] if the offer has two buy tokens and their accounts are missing.
+/// - [`OfferInventoryErrorCode::InvalidBuyToken2Account`] if a buy token 2 account is not the expected ATA.
+/// - `PricingErrorCode::CalculationOverflow` if the scaled amounts overflow.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if receipts of the offer were not settled.
+pub fn withdraw_unsold(ctx: Context<WithdrawUnsold>, buy_token_1_amount: u64) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    check_no_open_subscriptions(offer)?;
+    check_no_open_receipts(offer)?;
+    require!(
+        buy_token_1_amount > 0 && buy_token_1_amount < offer.buy_token_1.amount,
+        OfferInventoryErrorCode::InvalidAmount
+    );
+    require!(
+        buy_token_1_amount <= ctx.accounts.offer_buy_token_1_account.amount,
+        OfferInventoryErrorCode::InsufficientInventory
+    );
+    let buy_token_2_accounts = verify_buy_token_2_accounts(
+        offer,
+        ctx.accounts.offer_buy_token_2_account.as_deref(),
+        ctx.accounts.boss_buy_token_2_account.as_deref(),
+        &ctx.accounts.offer_token_authority.key(),
+        &ctx.accounts.boss.key(),
+    )?;
+
+    let buy_token_1_total_amount = offer.buy_token_1.amount - buy_token_1_amount;
+    let old_buy_token_2_total_amount = offer.buy_token_2.amount;
+    rescale_offer(offer, buy_token_1_total_amount)?;
+    let buy_token_2_amount = old_buy_token_2_total_amount - offer.buy_token_2.amount;
+
+    let offer_id_bytes = &offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes,
+        &[offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                to: ctx.accounts.boss_buy_token_1_account.to_account_info(),
+                authority: ctx.accounts.offer_token_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        buy_token_1_amount,
+    )?;
+    msg!("Transferring {} buy tokens 1 from offer to boss", buy_token_1_amount);
+
+    if let Some((offer_buy_token_2_account, boss_buy_token_2_account)) = buy_token_2_accounts {
+        require!(
+            buy_token_2_amount <= offer_buy_token_2_account.amount,
+            OfferInventoryErrorCode::InsufficientInventory
+        );
+        if buy_token_2_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: offer_buy_token_2_account.to_account_info(),
+                        to: boss_buy_token_2_account.to_account_info(),
+                        authority: ctx.accounts.offer_token_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                buy_token_2_amount,
+            )?;
+            msg!("Transferring {} buy tokens 2 from offer to boss", buy_token_2_amount);
+        }
+    }
+
+    emit!(UnsoldWithdrawn {
+        offer_id: offer.offer_id,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        buy_token_1_total_amount: offer.buy_token_1.amount,
+        buy_token_2_total_amount: offer.buy_token_2.amount,
+        sell_token_start_amount: offer.sell_token_start_amount,
+        sell_token_end_amount: offer.sell_token_end_amount,
+    });
+
+    Ok(())
+}
+
+/// Checks the buy token 2 accounts passed for an offer.
+///
+/// # Returns
+/// The offer's and the boss's buy token 2 accounts, `None` for offers with one buy token.
+///
+/// # Errors
+/// - [`OfferInventoryErrorCode::MissingBuyToken2Accounts`] if the offer has two buy tokens and either account is missing.
+/// - [`OfferInventoryErrorCode::InvalidBuyToken2Account`] if either account is not the expected ATA.
+pub(crate) fn verify_buy_token_2_accounts<'a, 'info>(
+    offer: &Offer,
+    offer_buy_token_2_account: Option<&'a Account<'info, TokenAccount>>,
+    boss_buy_token_2_account: Option<&'a Account<'info, TokenAccount>>,
+    offer_token_authority: &Pubkey,
+    boss: &Pubkey,
+) -> Result<Option<(&'a Account<'info, TokenAccount>, &'a Account<'info, TokenAccount>)>> {
+    let mint = offer.buy_token_2.mint;
+    if mint == Pubkey::default() {
+        return Ok(None);
+    }
+
+    let (Some(offer_buy_token_2_account), Some(boss_buy_token_2_account)) =
+        (offer_buy_token_2_account, boss_buy_token_2_account)
+    else {
+        return err!(OfferInventoryErrorCode::MissingBuyToken2Accounts);
+    };
+    require!(
+        offer_buy_token_2_account.key() == get_associated_token_address(offer_token_authority, &mint)
+            && offer_buy_token_2_account.mint == mint,
+        OfferInventoryErrorCode::InvalidBuyToken2Account
+    );
+    require!(
+        boss_buy_token_2_account.key() == get_associated_token_address(boss, &mint)
+            && boss_buy_token_2_account.mint == mint,
+        OfferInventoryErrorCode::InvalidBuyToken2Account
+    );
+
+    Ok(Some((offer_buy_token_2_account, boss_buy_token_2_account)))
+}
+
+/// Scales the priced quantities of an offer so that `buy_token_1.amount` becomes `buy_token_1_amount`.
+///
+/// The sell token amounts, including the clearing price of a uniform price offer, are rounded up
+/// and `buy_token_2.amount` down, so the unit price never moves against the offer.
+///
+/// # Errors
+/// - `PricingErrorCode::CalculationOverflow` if a scaled amount overflows.
+fn rescale_offer(offer: &mut Offer, buy_token_1_amount: u64) -> Result<()> {
+    let old_amount = offer.buy_token_1.amount;
+    let scale =
+        |amount: u64, rounding: Rounding| mul_div(amount, buy_token_1_amount, old_amount, rounding);
+
+    offer.sell_token_start_amount = scale(offer.sell_token_start_amount, Rounding::Up)?;
+    offer.sell_token_end_amount = scale(offer.sell_token_end_amount, Rounding::Up)?;
+    offer.clearing_sell_token_amount = scale(offer.clearing_sell_token_amount, Rounding::Up)?;
+    offer.buy_token_2.amount = scale(offer.buy_token_2.amount, Rounding::Down)?;
+    offer.buy_token_1.amount = buy_token_1_amount;
+
+    Ok(())
+}
+
+/// Error codes for offer inventory operations.
+#[error_code]
+pub enum OfferInventoryErrorCode {
+    /// Triggered when the amount is zero, or a withdrawal would empty the offer.
+    #[msg("Invalid amount")]
+    InvalidAmount,
+
+    /// Triggered when the offer holds fewer unsold buy tokens than withdrawn.
+    #[msg("Insufficient unsold buy tokens in the offer")]
+    InsufficientInventory,
+
+    /// Triggered when an offer with two buy tokens is changed without its buy token 2 accounts.
+    #[msg("Missing buy token 2 accounts")]
+    MissingBuyToken2Accounts,
+
+    /// Triggered when a buy token 2 account is not the expected ATA.
+    #[msg("Invalid buy token 2 account")]
+    InvalidBuyToken2Account,
+
+    /// Triggered when a scaled amount overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -0,0 +1,191 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::subscription::check_not_subscription;
+use crate::state::{Offer, Position, Role, State};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the purchase limits of an offer are changed.
+#[event]
+pub struct OfferLimitsSet {
+    pub offer_id: u64,
+    pub max_per_user: u64,
+    pub max_per_tx: u64,
+    pub min_per_tx: u64,
+    pub raise_cap: u64,
+}
+
+/// Account structure for changing the purchase limits of an offer.
+#[derive(Accounts)]
+pub struct SetOfferLimits<'info> {
+    /// The offer whose limits are changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the purchase limits of an offer.
+///
+/// All limits are expressed in sell token amounts, the amount the user pays, and `0` disables
+/// a limit. Lowering a limit below what has already been purchased only blocks further takes.
+/// Emits an `OfferLimitsSet` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the change.
+/// - `max_per_user`: Maximum cumulative amount a single user can pay.
+/// - `max_per_tx`: Maximum amount per take.
+/// - `min_per_tx`: Minimum amount per take.
+/// - `raise_cap`: Maximum cumulative amount the offer accepts from all users.
+///
+/// # Errors
+/// - [`OfferLimitsErrorCode::InvalidLimits`] if `min_per_tx` exceeds `max_per_tx`, `max_per_user` or `raise_cap`.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+pub fn set_offer_limits(
+    ctx: Context<SetOfferLimits>,
+    max_per_user: u64,
+    max_per_tx: u64,
+    min_per_tx: u64,
+    raise_cap: u64,
+) -> Result<()> {
+    check_not_subscription(&ctx.accounts.offer)?;
+    for max in [max_per_tx, max_per_user, raise_cap] {
+        require!(
+            max == 0 || min_per_tx <= max,
+            OfferLimitsErrorCode::InvalidLimits
+        );
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.max_per_user = max_per_user;
+    offer.max_per_tx = max_per_tx;
+    offer.min_per_tx = min_per_tx;
+    offer.raise_cap = raise_cap;
+
+    emit!(OfferLimitsSet {
+        offer_id: offer.offer_id,
+        max_per_user,
+        max_per_tx,
+        min_per_tx,
+        raise_cap,
+    });
+
+    Ok(())
+}
+
+/// Records a purchase against the offer's limits and the user's position.
+///
+/// Initializes the position on the user's first take, then checks the per-take limits, the
+/// user's cumulative limit and the offer's raise cap before updating both totals.
+///
+/// # Arguments
+/// - `offer`: The offer being taken.
+/// - `position`: The user's position in the offer, possibly just created.
+/// - `user`: The user taking the offer.
+/// - `position_bump`: Bump seed of the position PDA.
+/// - `sell_token_amount`: Sell token amount paid by the take.
+///
+/// # Errors
+/// - [`OfferLimitsErrorCode::BelowMinPerTx`] if the amount is below `min_per_tx`.
+/// - [`OfferLimitsErrorCode::AboveMaxPerTx`] if the amount exceeds `max_per_tx`.
+/// - [`OfferLimitsErrorCode::UserCapExceeded`] if the user's cumulative amount would exceed `max_per_user`.
+/// - [`OfferLimitsErrorCode::RaiseCapExceeded`] if the offer's cumulative amount would exceed `raise_cap`.
+pub fn record_purchase(
+    offer: &mut Account<Offer>,
+    position: &mut Account<Position>,
+    user: Pubkey,
+    position_bump: u8,
+    sell_token_amount: u64,
+) -> Result<()> {
+    if position.offer == Pubkey::default() {
+        position.offer = offer.key();
+        position.user = user;
+        position.bump = position_bump;
+    }
+
+    require!(
+        sell_token_amount >= offer.min_per_tx,
+        OfferLimitsErrorCode::BelowMinPerTx
+    );
+    require!(
+        offer.max_per_tx == 0 || sell_token_amount <= offer.max_per_tx,
+        OfferLimitsErrorCode::AboveMaxPerTx
+    );
+
+    let user_total = position
+        .sell_token_amount
+        .checked_add(sell_token_amount)
+        .ok_or(OfferLimitsErrorCode::CalculationOverflow)?;
+    require!(
+        offer.max_per_user == 0 || user_total <= offer.max_per_user,
+        OfferLimitsErrorCode::UserCapExceeded
+    );
+
+    let total_raised = offer
+        .total_raised
+        .checked_add(sell_token_amount)
+        .ok_or(OfferLimitsErrorCode::CalculationOverflow)?;
+    require!(
+        offer.raise_cap == 0 || total_raised <= offer.raise_cap,
+        OfferLimitsErrorCode::RaiseCapExceeded
+    );
+
+    position.sell_token_amount = user_total;
+    offer.total_raised = total_raised;
+
+    Ok(())
+}
+
+/// Checks that an offer has no purchase limits, which subscriptions don't enforce.
+///
+/// # Errors
+/// - [`OfferLimitsErrorCode::LimitedOffer`] if one of the offer's limits is set.
+pub fn check_no_limits(offer: &Offer) -> Result<()> {
+    require!(
+        offer.max_per_user == 0
+            && offer.max_per_tx == 0
+            && offer.min_per_tx == 0
+            && offer.raise_cap == 0,
+        OfferLimitsErrorCode::LimitedOffer
+    );
+    Ok(())
+}
+
+/// Error codes for offer purchase limits.
+#[error_code]
+pub enum OfferLimitsErrorCode {
+    /// Triggered when the minimum per take exceeds one of the maximums.
+    #[msg("Minimum per transaction must not exceed the maximums")]
+    InvalidLimits,
+
+    /// Triggered when a take is below the offer's minimum per transaction.
+    #[msg("Amount is below the minimum per transaction")]
+    BelowMinPerTx,
+
+    /// Triggered when a take exceeds the offer's maximum per transaction.
+    #[msg("Amount exceeds the maximum per transaction")]
+    AboveMaxPerTx,
+
+    /// Triggered when a take would exceed the user's cumulative cap.
+    #[msg("Purchase would exceed the per user cap")]
+    UserCapExceeded,
+
+    /// Triggered when a take would exceed the offer's raise cap.
+    #[msg("Purchase would exceed the offer's raise cap")]
+    RaiseCapExceeded,
+
+    /// Triggered when cumulative amounts overflow.
+    #[msg("Calculation overflowed or invalid.")]
+    CalculationOverflow,
+
+    /// Triggered when enabling subscriptions on an offer with purchase limits.
+    #[msg("The offer has purchase limits")]
+    LimitedOffer,
+}
@@ -0,0 +1,124 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{OfferRegistry, Role, State};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the ID of the next offer is changed.
+#[event]
+pub struct NextOfferIdSet {
+    pub old_next_offer_id: u64,
+    pub new_next_offer_id: u64,
+}
+
+/// Account structure for creating the offer registry of a state made before it existed.
+///
+/// `initialize` creates the registry of new states.
+#[derive(Accounts)]
+pub struct InitializeOfferRegistry<'info> {
+    /// The registry to be created, with rent paid by `authority`.
+    #[account(
+        init,
+        payer = authority,
+        space = OfferRegistry::space(0),
+        seeds = [b"offer_registry"],
+        bump
+    )]
+    pub offer_registry: Account<'info, OfferRegistry>,
+
+    /// Program state, ensures `authority` holds the admin role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin, authorizing and paying for the registry creation.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an empty offer registry.
+///
+/// Offers made before the registry existed are not listed in it.
+///
+/// # Errors
+/// - `RoleErrorCode::MissingRole` if the signer doesn't hold the admin role.
+/// - Fails if the registry already exists.
+pub fn initialize_offer_registry(_ctx: Context<InitializeOfferRegistry>) -> Result<()> {
+    Ok(())
+}
+
+/// Account structure for setting the ID of the next offer.
+#[derive(Accounts)]
+pub struct SetNextOfferId<'info> {
+    /// Program state handing out the offer IDs, ensures `authority` holds the admin role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Moves the ID given to the next offer forward.
+///
+/// Offer IDs only ever increase, so offers made with caller chosen IDs before the state was
+/// migrated can be skipped but no ID is handed out twice. Emits a `NextOfferIdSet` event.
+///
+/// # Errors
+/// - [`OfferRegistryErrorCode::InvalidNextOfferId`] if `next_offer_id` is not greater than the current one.
+pub fn set_next_offer_id(ctx: Context<SetNextOfferId>, next_offer_id: u64) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(
+        next_offer_id > state.next_offer_id,
+        OfferRegistryErrorCode::InvalidNextOfferId
+    );
+    let old_next_offer_id = state.next_offer_id;
+    state.next_offer_id = next_offer_id;
+
+    emit!(NextOfferIdSet {
+        old_next_offer_id,
+        new_next_offer_id: next_offer_id,
+    });
+
+    Ok(())
+}
+
+/// Hands out the ID of a new offer and lists it in the registry.
+///
+/// The registry must have been grown by one ID beforehand.
+///
+/// # Errors
+/// - [`OfferRegistryErrorCode::OfferIdOverflow`] if every offer ID was handed out.
+pub(crate) fn register_offer(state: &mut State, offer_registry: &mut OfferRegistry) -> Result<u64> {
+    let offer_id = state.next_offer_id;
+    state.next_offer_id = offer_id
+        .checked_add(1)
+        .ok_or(OfferRegistryErrorCode::OfferIdOverflow)?;
+    offer_registry.offer_ids.push(offer_id);
+    Ok(offer_id)
+}
+
+/// Removes a closed offer from the registry, offers that are not listed are ignored.
+pub(crate) fn deregister_offer(offer_registry: &mut OfferRegistry, offer_id: u64) {
+    offer_registry.offer_ids.retain(|listed_offer_id| *listed_offer_id != offer_id);
+}
+
+/// Error codes for the offer registry.
+#[error_code]
+pub enum OfferRegistryErrorCode {
+    /// Triggered when the offer IDs are exhausted.
+    #[msg("No offer ID left to hand out")]
+    OfferIdOverflow,
+
+    /// Triggered when the next offer ID would not increase.
+    #[msg("The next offer ID must be greater than the current one")]
+    InvalidNextOfferId,
+}
@@ -0,0 +1,123 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{Offer, OfferStatus, Role, State};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the program is paused or unpaused.
+#[event]
+pub struct PausedSet {
+    pub paused: bool,
+    /// The boss or a pauser that changed the flag.
+    pub authority: Pubkey,
+}
+
+/// Event emitted when an offer is paused or unpaused.
+#[event]
+pub struct OfferPausedSet {
+    pub offer_id: u64,
+    pub paused: bool,
+    /// The boss or a pauser that changed the flag.
+    pub authority: Pubkey,
+}
+
+/// Account structure for pausing or unpausing the program.
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    /// Program state holding the global flag.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Pauser, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or a pauser changing the flag.
+    pub authority: Signer<'info>,
+}
+
+/// Pauses or unpauses taking all offers.
+///
+/// Emits a `PausedSet` event.
+///
+/// # Errors
+/// - `RoleErrorCode::MissingRole` if the signer doesn't hold the pauser role.
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.state.paused = paused;
+
+    emit!(PausedSet {
+        paused,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Account structure for pausing or unpausing an offer.
+#[derive(Accounts)]
+pub struct SetOfferPaused<'info> {
+    /// The offer whose flag is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures the signer holds the pauser role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Pauser, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or a pauser changing the flag.
+    pub authority: Signer<'info>,
+}
+
+/// Pauses or unpauses taking a single offer.
+///
+/// Sets the status of the offer. Emits an `OfferPausedSet` event.
+///
+/// # Errors
+/// - `RoleErrorCode::MissingRole` if the signer doesn't hold the pauser role.
+pub fn set_offer_paused(ctx: Context<SetOfferPaused>, paused: bool) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    offer.status = if paused { OfferStatus::Paused } else { OfferStatus::Active };
+
+    emit!(OfferPausedSet {
+        offer_id: offer.offer_id,
+        paused,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Checks that neither the program nor the offer is paused.
+///
+/// # Errors
+/// - [`PauseErrorCode::ProgramPaused`] if the program is paused.
+/// - [`PauseErrorCode::OfferPaused`] if the offer is paused.
+pub fn check_not_paused(state: &State, offer: &Offer) -> Result<()> {
+    check_program_not_paused(state)?;
+    require!(offer.status == OfferStatus::Active, PauseErrorCode::OfferPaused);
+    Ok(())
+}
+
+/// Checks that the program is not paused, for instructions that don't take an `Offer`.
+///
+/// # Errors
+/// - [`PauseErrorCode::ProgramPaused`] if the program is paused.
+pub fn check_program_not_paused(state: &State) -> Result<()> {
+    require!(!state.paused, PauseErrorCode::ProgramPaused);
+    Ok(())
+}
+
+/// Error codes for pause operations.
+#[error_code]
+pub enum PauseErrorCode {
+    /// Triggered when taking an offer while the program is paused.
+    #[msg("The program is paused")]
+    ProgramPaused,
+
+    /// Triggered when taking an offer that is paused.
+    #[msg("The offer is paused")]
+    OfferPaused,
+}
@@ -0,0 +1,252 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{PermissionlessAccount, Role, State};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when a permissionless account is enabled or disabled.
+#[event]
+pub struct PermissionlessAccountEnabledSet {
+    pub permissionless_account: Pubkey,
+    pub index: u64,
+    pub enabled: bool,
+}
+
+/// Event emitted when the balance of an intermediary token account is swept to the boss.
+#[event]
+pub struct IntermediarySwept {
+    pub permissionless_account: Pubkey,
+    pub index: u64,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when a permissionless account and its intermediary token accounts are closed.
+#[event]
+pub struct IntermediaryClosed {
+    pub permissionless_account: Pubkey,
+    pub index: u64,
+    pub closed_token_accounts: u8,
+}
+
+/// Account structure for enabling or disabling a permissionless account.
+#[derive(Accounts)]
+pub struct SetPermissionlessAccountEnabled<'info> {
+    /// The permissionless account to enable or disable.
+    #[account(
+        mut,
+        seeds = [b"permissionless", permissionless_account.index.to_le_bytes().as_ref()],
+        bump = permissionless_account.bump
+    )]
+    pub permissionless_account: Account<'info, PermissionlessAccount>,
+
+    /// Program state, ensures `authority` holds the admin role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Enables or disables routing permissionless takes through a permissionless account.
+///
+/// Disabling a routing channel leaves its intermediary token accounts untouched.
+/// Emits a `PermissionlessAccountEnabledSet` event.
+pub fn set_permissionless_account_enabled(
+    ctx: Context<SetPermissionlessAccountEnabled>,
+    enabled: bool,
+) -> Result<()> {
+    let permissionless_account = &mut ctx.accounts.permissionless_account;
+    permissionless_account.enabled = enabled;
+
+    emit!(PermissionlessAccountEnabledSet {
+        permissionless_account: permissionless_account.key(),
+        index: permissionless_account.index,
+        enabled,
+    });
+
+    Ok(())
+}
+
+/// Account structure for sweeping an intermediary token account to the boss.
+#[derive(Accounts)]
+pub struct SweepIntermediary<'info> {
+    /// The permissionless account controlling the intermediary token account.
+    #[account(
+        seeds = [b"permissionless", permissionless_account.index.to_le_bytes().as_ref()],
+        bump = permissionless_account.bump
+    )]
+    pub permissionless_account: Account<'info, PermissionlessAccount>,
+
+    /// Mint of the swept token.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Intermediary ATA of `mint`, sends its whole balance.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = permissionless_account,
+    )]
+    pub intermediary_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's ATA of `mint`, receives the swept tokens.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Program state, ensures `boss` is the boss.
+    #[account(seeds = [b"state"], bump, has_one = boss)]
+    pub state: Account<'info, State>,
+
+    /// The boss authorizing the sweep.
+    pub boss: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Transfers the whole balance of an intermediary token account to the boss.
+///
+/// Recovers tokens sent directly to an intermediary account, which the take flow never moves.
+/// Emits an `IntermediarySwept` event.
+pub fn sweep_intermediary(ctx: Context<SweepIntermediary>) -> Result<()> {
+    let permissionless_account = &ctx.accounts.permissionless_account;
+    let index_bytes = &permissionless_account.index.to_le_bytes();
+    let seeds = &[
+        b"permissionless".as_ref(),
+        index_bytes,
+        &[permissionless_account.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let amount = ctx.accounts.intermediary_token_account.amount;
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.intermediary_token_account.to_account_info(),
+                    to: ctx.accounts.boss_token_account.to_account_info(),
+                    authority: permissionless_account.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+        msg!("Transferring {} tokens from intermediary to boss", amount);
+    }
+
+    emit!(IntermediarySwept {
+        permissionless_account: permissionless_account.key(),
+        index: permissionless_account.index,
+        token_account: ctx.accounts.intermediary_token_account.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Account structure for closing a permissionless account and its intermediary token accounts.
+///
+/// The intermediary token accounts to close are passed as writable remaining accounts.
+#[derive(Accounts)]
+pub struct CloseIntermediary<'info> {
+    /// The permissionless account to close, its rent is refunded to the boss.
+    #[account(
+        mut,
+        close = boss,
+        seeds = [b"permissionless", permissionless_account.index.to_le_bytes().as_ref()],
+        bump = permissionless_account.bump
+    )]
+    pub permissionless_account: Account<'info, PermissionlessAccount>,
+
+    /// Program state, ensures `boss` is the boss.
+    #[account(seeds = [b"state"], bump, has_one = boss)]
+    pub state: Account<'info, State>,
+
+    /// The boss authorizing the closure and receiving the rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Closes a permissionless account together with its intermediary token accounts.
+///
+/// Each remaining account must be an empty token account owned by the permissionless account,
+/// so stray balances have to be swept first. The rent of all closed accounts is refunded to the
+/// boss. Emits an `IntermediaryClosed` event.
+///
+/// # Errors
+/// - [`PermissionlessAccountErrorCode::InvalidIntermediaryAccount`] if a remaining account is not a token account of the permissionless account.
+/// - [`PermissionlessAccountErrorCode::IntermediaryNotEmpty`] if an intermediary token account still holds tokens.
+pub fn close_intermediary<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseIntermediary<'info>>,
+) -> Result<()> {
+    let permissionless_account = &ctx.accounts.permissionless_account;
+    let index_bytes = &permissionless_account.index.to_le_bytes();
+    let seeds = &[
+        b"permissionless".as_ref(),
+        index_bytes,
+        &[permissionless_account.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    for token_account_info in ctx.remaining_accounts.iter() {
+        let token_account = Account::<TokenAccount>::try_from(token_account_info)
+            .map_err(|_| PermissionlessAccountErrorCode::InvalidIntermediaryAccount)?;
+        require_keys_eq!(
+            token_account.owner,
+            permissionless_account.key(),
+            PermissionlessAccountErrorCode::InvalidIntermediaryAccount
+        );
+        require!(
+            token_account.amount == 0,
+            PermissionlessAccountErrorCode::IntermediaryNotEmpty
+        );
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: token_account_info.clone(),
+                destination: ctx.accounts.boss.to_account_info(),
+                authority: permissionless_account.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        msg!("Closed intermediary token account {}", token_account_info.key());
+    }
+
+    emit!(IntermediaryClosed {
+        permissionless_account: permissionless_account.key(),
+        index: permissionless_account.index,
+        closed_token_accounts: ctx.remaining_accounts.len() as u8,
+    });
+
+    Ok(())
+}
+
+/// Error codes for permissionless account operations.
+#[error_code]
+pub enum PermissionlessAccountErrorCode {
+    /// Triggered when routing a take through a disabled permissionless account.
+    #[msg("The permissionless account is disabled")]
+    PermissionlessAccountDisabled,
+
+    /// Triggered when closing an account that is not a token account of the permissionless account.
+    #[msg("The account is not an intermediary token account of the permissionless account")]
+    InvalidIntermediaryAccount,
+
+    /// Triggered when closing an intermediary token account that still holds tokens.
+    #[msg("The intermediary token account must be swept before closing")]
+    IntermediaryNotEmpty,
+}
@@ -0,0 +1,375 @@
+use crate::instructions::nav_oracle::{calculate_nav_sell_amount, NavOracleErrorCode};
+use crate::state::{NavOracle, Offer};
+use anchor_lang::prelude::*;
+
+/// Rounding direction of a fixed-point calculation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Computes `value * numerator / denominator` with `u128` intermediate precision.
+///
+/// # Errors
+/// - [`PricingErrorCode::CalculationOverflow`] if `denominator` is zero or the result does not fit in a `u64`.
+pub fn mul_div(value: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    require!(denominator > 0, PricingErrorCode::CalculationOverflow);
+    let product = (value as u128) * (numerator as u128);
+    let result = match rounding {
+        Rounding::Down => product / denominator as u128,
+        Rounding::Up => product.div_ceil(denominator as u128),
+    };
+    u64::try_from(result).map_err(|_| error!(PricingErrorCode::CalculationOverflow))
+}
+
+/// Returns the pricing interval `current_time` falls in and the offer's number of intervals.
+///
+/// Intervals last `price_fix_duration` seconds and are counted from `offer_start_time`, the
+/// first interval having index `0`.
+///
+/// # Errors
+/// - [`PricingErrorCode::InvalidCurrentTime`] if `current_time` is outside `[offer_start_time, offer_end_time)`.
+/// - [`PricingErrorCode::InvalidPriceSchedule`] if the offer doesn't span at least one interval.
+pub fn current_interval(
+    offer_start_time: u64,
+    offer_end_time: u64,
+    price_fix_duration: u64,
+    current_time: u64,
+) -> Result<(u64, u64)> {
+    require!(
+        current_time >= offer_start_time && current_time < offer_end_time,
+        PricingErrorCode::InvalidCurrentTime
+    );
+    let number_of_intervals = (offer_end_time - offer_start_time)
+        .checked_div(price_fix_duration)
+        .ok_or(PricingErrorCode::InvalidPriceSchedule)?;
+    require!(number_of_intervals > 0, PricingErrorCode::InvalidPriceSchedule);
+
+    let interval = ((current_time - offer_start_time) / price_fix_duration).min(number_of_intervals - 1);
+    Ok((interval, number_of_intervals))
+}
+
+/// Interpolates the sell token amount of an interval between the start and end amounts.
+///
+/// The first interval is priced at `sell_token_start_amount` and the last one at
+/// `sell_token_end_amount`, the intervals in between moving linearly from one to the other.
+/// Offers with a single interval are priced at `sell_token_start_amount`. The amount is rounded
+/// up, in favour of the offer, whether the schedule ascends or descends.
+///
+/// # Errors
+/// - [`PricingErrorCode::InvalidPriceSchedule`] if `interval` is not below `number_of_intervals`.
+pub fn interpolate_sell_amount(
+    sell_token_start_amount: u64,
+    sell_token_end_amount: u64,
+    interval: u64,
+    number_of_intervals: u64,
+) -> Result<u64> {
+    require!(interval < number_of_intervals, PricingErrorCode::InvalidPriceSchedule);
+    if number_of_intervals == 1 {
+        return Ok(sell_token_start_amount);
+    }
+
+    let delta = sell_token_end_amount.abs_diff(sell_token_start_amount);
+    // Both branches stay between the start and end amounts, so they cannot overflow.
+    if sell_token_end_amount >= sell_token_start_amount {
+        let change = mul_div(delta, interval, number_of_intervals - 1, Rounding::Up)?;
+        Ok(sell_token_start_amount + change)
+    } else {
+        let change = mul_div(delta, interval, number_of_intervals - 1, Rounding::Down)?;
+        Ok(sell_token_start_amount - change)
+    }
+}
+
+/// Returns the time the price intervals of an offer are counted from.
+///
+/// This is `offer_start_time` unless `update_offer` anchored a new schedule after it.
+pub fn schedule_start_time(offer: &Offer) -> u64 {
+    offer.offer_start_time.max(offer.schedule_start_time)
+}
+
+/// Returns the interval of the offer's schedule `current_time` falls in and its number of intervals.
+///
+/// Returns `None` while the sell token amount carried over by `update_offer` applies, before
+/// the schedule starts.
+///
+/// # Errors
+/// - [`PricingErrorCode::InvalidCurrentTime`] if `current_time` is outside `[offer_start_time, offer_end_time)`.
+/// - [`PricingErrorCode::InvalidPriceSchedule`] if the schedule doesn't span at least one interval.
+pub fn offer_interval(offer: &Offer, current_time: u64) -> Result<Option<(u64, u64)>> {
+    require!(
+        current_time >= offer.offer_start_time && current_time < offer.offer_end_time,
+        PricingErrorCode::InvalidCurrentTime
+    );
+    if current_time < offer.schedule_start_time {
+        return Ok(None);
+    }
+    current_interval(
+        schedule_start_time(offer),
+        offer.offer_end_time,
+        offer.price_fix_duration,
+        current_time,
+    )
+    .map(Some)
+}
+
+/// Calculates the sell token amount of the offer's interval schedule at `current_time`.
+///
+/// # Errors
+/// - [`PricingErrorCode::InvalidCurrentTime`] if `current_time` is outside the offer's active period.
+/// - [`PricingErrorCode::InvalidPriceSchedule`] if the schedule doesn't span at least one interval.
+pub fn scheduled_sell_amount(offer: &Offer, current_time: u64) -> Result<u64> {
+    match offer_interval(offer, current_time)? {
+        Some((interval, number_of_intervals)) => interpolate_sell_amount(
+            offer.sell_token_start_amount,
+            offer.sell_token_end_amount,
+            interval,
+            number_of_intervals,
+        ),
+        None => Ok(offer.carried_sell_token_amount),
+    }
+}
+
+/// Returns the start time of the interval following `interval`, `None` if the price won't change again.
+///
+/// The price of an offer stays fixed after its last interval, and throughout offers whose start
+/// and end amounts are equal.
+pub fn next_price_change_time(offer: &Offer, interval: u64, number_of_intervals: u64) -> Option<u64> {
+    if offer.sell_token_start_amount == offer.sell_token_end_amount
+        || interval + 1 >= number_of_intervals
+    {
+        return None;
+    }
+    Some(schedule_start_time(offer) + (interval + 1) * offer.price_fix_duration)
+}
+
+/// Calculates the current sell token amount based on the offer's dynamic pricing model.
+///
+/// The price of the sell token (how much is required per buy token) changes linearly over the
+/// offer's duration, see [`scheduled_sell_amount`]. Offers referencing a NAV oracle are priced at
+/// the current NAV plus their spread instead, see [`calculate_nav_sell_amount`].
+///
+/// # Returns
+/// The amount of sell tokens that corresponds to the total `buy_token_X_amount` defined in the
+/// offer for the current interval.
+///
+/// # Errors
+/// - [`PricingErrorCode::InvalidCurrentTime`] if the current time is outside the offer's active period.
+/// - [`PricingErrorCode::InvalidPriceSchedule`] if the offer doesn't span at least one interval.
+/// - `NavOracleErrorCode::MissingNavOracle` if the offer is NAV priced and no oracle account was passed.
+pub fn calculate_current_sell_amount(
+    offer: &Offer,
+    nav_oracle: Option<&Account<NavOracle>>,
+    current_time: u64,
+) -> Result<u64> {
+    let scheduled_sell_amount = scheduled_sell_amount(offer, current_time)?;
+
+    if offer.nav_oracle != Pubkey::default() {
+        let nav_oracle = nav_oracle.ok_or(NavOracleErrorCode::MissingNavOracle)?;
+        return calculate_nav_sell_amount(offer, nav_oracle, current_time);
+    }
+
+    Ok(scheduled_sell_amount)
+}
+
+/// Calculates the proportional amount of a buy token a user receives for their sell tokens.
+///
+/// Essentially `user_sell_token_amount * offer_buy_token_amount / offer_sell_token_amount`,
+/// rounded down in favour of the offer.
+///
+/// # Arguments
+/// - `user_sell_token_amount`: Amount of sell tokens paying for the buy tokens.
+/// - `offer_buy_token_amount`: Total amount of the buy token set in the offer (e.g., `offer.buy_token_1.amount`).
+/// - `offer_sell_token_amount`: Current sell token amount of the offer, see [`calculate_current_sell_amount`].
+///
+/// # Errors
+/// - [`PricingErrorCode::ZeroSellTokenAmount`] if `offer_sell_token_amount` is zero.
+/// - [`PricingErrorCode::CalculationOverflow`] if the result does not fit in a `u64`.
+/// - [`PricingErrorCode::ZeroBuyTokenAmount`] if the calculated buy token amount is zero.
+pub fn calculate_buy_amount(
+    user_sell_token_amount: u64,
+    offer_buy_token_amount: u64,
+    offer_sell_token_amount: u64,
+) -> Result<u64> {
+    require!(offer_sell_token_amount > 0, PricingErrorCode::ZeroSellTokenAmount);
+    let buy_token_amount = mul_div(
+        user_sell_token_amount,
+        offer_buy_token_amount,
+        offer_sell_token_amount,
+        Rounding::Down,
+    )?;
+    require!(buy_token_amount > 0, PricingErrorCode::ZeroBuyTokenAmount);
+    Ok(buy_token_amount)
+}
+
+/// Error codes for offer pricing.
+#[error_code]
+pub enum PricingErrorCode {
+    /// Triggered when the current time is outside the offer's time range.
+    #[msg("Current time must be within the offer's start and end time range.")]
+    InvalidCurrentTime,
+
+    /// Triggered when the offer's times don't span at least one price interval.
+    #[msg("The offer must span at least one price interval.")]
+    InvalidPriceSchedule,
+
+    /// Triggered when the offer's current sell token amount is zero.
+    #[msg("The offer's sell token amount is zero.")]
+    ZeroSellTokenAmount,
+
+    /// Triggered when a pricing calculation overflows.
+    #[msg("Calculation overflowed or invalid.")]
+    CalculationOverflow,
+
+    /// Triggered when the calculated buy token amount is zero.
+    #[msg("Zero buy token amount.")]
+    ZeroBuyTokenAmount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn first_interval_is_priced_at_start_amount() {
+        assert_eq!(interpolate_sell_amount(100, 200, 0, 5).unwrap(), 100);
+        assert_eq!(interpolate_sell_amount(200, 100, 0, 5).unwrap(), 200);
+    }
+
+    #[test]
+    fn last_interval_is_priced_at_end_amount() {
+        assert_eq!(interpolate_sell_amount(100, 200, 4, 5).unwrap(), 200);
+        assert_eq!(interpolate_sell_amount(200, 100, 4, 5).unwrap(), 100);
+        // (end - start) is not a multiple of the number of steps
+        assert_eq!(interpolate_sell_amount(100, 201, 2, 3).unwrap(), 201);
+    }
+
+    #[test]
+    fn intermediate_intervals_round_in_favour_of_the_offer() {
+        // 100 + 101 * 1 / 2 = 150.5
+        assert_eq!(interpolate_sell_amount(100, 201, 1, 3).unwrap(), 151);
+        // 201 - 101 * 1 / 2 = 150.5
+        assert_eq!(interpolate_sell_amount(201, 100, 1, 3).unwrap(), 151);
+    }
+
+    #[test]
+    fn single_interval_is_priced_at_start_amount() {
+        assert_eq!(interpolate_sell_amount(100, 200, 0, 1).unwrap(), 100);
+    }
+
+    #[test]
+    fn current_interval_is_counted_from_start_time() {
+        assert_eq!(current_interval(1000, 1600, 100, 1000).unwrap(), (0, 6));
+        assert_eq!(current_interval(1000, 1600, 100, 1099).unwrap(), (0, 6));
+        assert_eq!(current_interval(1000, 1600, 100, 1100).unwrap(), (1, 6));
+        assert_eq!(current_interval(1000, 1600, 100, 1599).unwrap(), (5, 6));
+    }
+
+    #[test]
+    fn current_interval_covers_a_trailing_partial_interval() {
+        assert_eq!(current_interval(1000, 1650, 100, 1620).unwrap(), (5, 6));
+    }
+
+    #[test]
+    fn current_interval_rejects_times_outside_the_offer() {
+        assert!(current_interval(1000, 1600, 100, 999).is_err());
+        assert!(current_interval(1000, 1600, 100, 1600).is_err());
+    }
+
+    #[test]
+    fn current_interval_rejects_invalid_schedules() {
+        assert!(current_interval(1000, 1600, 0, 1000).is_err());
+        assert!(current_interval(1000, 1050, 100, 1000).is_err());
+    }
+
+    #[test]
+    fn buy_amount_is_rounded_down() {
+        assert_eq!(calculate_buy_amount(10, 100, 30).unwrap(), 33);
+        assert_eq!(calculate_buy_amount(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn buy_amount_errors_instead_of_panicking() {
+        assert!(calculate_buy_amount(10, 100, 0).is_err());
+        assert!(calculate_buy_amount(1, 1, 2).is_err());
+        assert!(calculate_buy_amount(u64::MAX, 2, 1).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn sell_amount_stays_between_start_and_end(
+            start in any::<u64>(),
+            end in any::<u64>(),
+            number_of_intervals in 1u64..10_000,
+            interval_seed in any::<u64>(),
+        ) {
+            let interval = interval_seed % number_of_intervals;
+            let amount = interpolate_sell_amount(start, end, interval, number_of_intervals).unwrap();
+            prop_assert!(amount >= start.min(end) && amount <= start.max(end));
+        }
+
+        #[test]
+        fn sell_amount_moves_monotonically_towards_end(
+            start in any::<u64>(),
+            end in any::<u64>(),
+            number_of_intervals in 2u64..10_000,
+            interval_seed in any::<u64>(),
+        ) {
+            let interval = interval_seed % (number_of_intervals - 1);
+            let amount = interpolate_sell_amount(start, end, interval, number_of_intervals).unwrap();
+            let next_amount = interpolate_sell_amount(start, end, interval + 1, number_of_intervals).unwrap();
+            if end >= start {
+                prop_assert!(next_amount >= amount);
+            } else {
+                prop_assert!(next_amount <= amount);
+            }
+        }
+
+        #[test]
+        fn sell_amount_is_never_below_the_exact_price(
+            start in any::<u64>(),
+            end in any::<u64>(),
+            number_of_intervals in 2u64..10_000,
+            interval_seed in any::<u64>(),
+        ) {
+            let interval = interval_seed % number_of_intervals;
+            let amount = interpolate_sell_amount(start, end, interval, number_of_intervals).unwrap();
+            // amount >= start + (end - start) * interval / (number_of_intervals - 1), scaled to integers
+            let steps = (number_of_intervals - 1) as i128;
+            let exact_scaled = start as i128 * steps + (end as i128 - start as i128) * interval as i128;
+            prop_assert!(amount as i128 * steps >= exact_scaled);
+            prop_assert!((amount as i128 - 1) * steps < exact_scaled);
+        }
+
+        #[test]
+        fn buy_amount_never_exceeds_the_exact_share(
+            user_sell_token_amount in any::<u64>(),
+            offer_buy_token_amount in any::<u64>(),
+            offer_sell_token_amount in 1u64..,
+        ) {
+            if let Ok(buy_amount) =
+                calculate_buy_amount(user_sell_token_amount, offer_buy_token_amount, offer_sell_token_amount)
+            {
+                let product = user_sell_token_amount as u128 * offer_buy_token_amount as u128;
+                prop_assert!(buy_amount as u128 * offer_sell_token_amount as u128 <= product);
+                prop_assert!((buy_amount as u128 + 1) * offer_sell_token_amount as u128 > product);
+            }
+        }
+
+        #[test]
+        fn current_interval_is_within_the_offer(
+            offer_start_time in 0u64..u32::MAX as u64,
+            duration in 1u64..1_000_000,
+            price_fix_duration in 1u64..10_000,
+            elapsed_seed in any::<u64>(),
+        ) {
+            prop_assume!(duration >= price_fix_duration);
+            let current_time = offer_start_time + elapsed_seed % duration;
+            let (interval, number_of_intervals) =
+                current_interval(offer_start_time, offer_start_time + duration, price_fix_duration, current_time).unwrap();
+            prop_assert_eq!(number_of_intervals, duration / price_fix_duration);
+            prop_assert!(interval < number_of_intervals);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::instructions::dutch_auction::check_auction_settled;
+use crate::instructions::min_raise::check_proceeds_unlocked;
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::subscription::check_no_open_subscriptions;
+use crate::state::{Offer, Role, State};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Event emitted when the treasury is changed.
+#[event]
+pub struct TreasurySet {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+}
+
+/// Event emitted when sale proceeds are withdrawn from an offer.
+#[event]
+pub struct ProceedsWithdrawn {
+    pub offer_id: u64,
+    pub amount: u64,
+    pub treasury_token_account: Pubkey,
+    pub treasurer: Pubkey,
+}
+
+/// Account structure for changing the treasury.
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    /// Program state holding the treasury, ensures `authority` holds the admin role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the owner of the token accounts receiving withdrawn proceeds.
+///
+/// Emits a `TreasurySet` event.
+///
+/// # Errors
+/// - [`ProceedsErrorCode::InvalidTreasury`] if `treasury` is the default public key.
+pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+    require!(
+        treasury != Pubkey::default(),
+        ProceedsErrorCode::InvalidTreasury
+    );
+
+    let state = &mut ctx.accounts.state;
+    let old_treasury = state.treasury;
+    state.treasury = treasury;
+
+    emit!(TreasurySet {
+        old_treasury,
+        new_treasury: treasury,
+    });
+
+    Ok(())
+}
+
+/// Account structure for withdrawing sale proceeds from an offer.
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    /// The offer whose proceeds are withdrawn.
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Offer's sell token ATA, sends the proceeds.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Sell token account of the treasury, receives the proceeds.
+    #[account(mut, token::mint = offer.sell_token_mint)]
+    pub treasury_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state holding the treasury, ensures `treasurer` holds the treasurer role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Treasurer, &treasurer.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss or a treasurer withdrawing the proceeds.
+    pub treasurer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraws sale proceeds from an offer to the treasury.
+///
+/// Transfers sell tokens accumulated by takes from the offer's sell token account, signed by
+/// the offer's token authority. The buy token inventory is left untouched, so the offer stays
+/// open. Emits a `ProceedsWithdrawn` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the withdrawal.
+/// - `amount`: Amount of sell tokens to withdraw.
+///
+/// # Errors
+/// - [`ProceedsErrorCode::TreasuryNotSet`] if no treasury was set.
+/// - [`ProceedsErrorCode::InvalidTreasuryAccount`] if the receiving token account is not owned by the treasury.
+/// - [`ProceedsErrorCode::InvalidAmount`] if `amount` is zero or exceeds the offer's sell token balance.
+/// - `DutchAuctionErrorCode::AuctionNotSettled` if the offer is in uniform price mode and not settled.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `MinRaiseErrorCode::RaiseNotSettled` if the offer has a minimum raise and its proceeds may still be refunded.
+pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.state.treasury != Pubkey::default(),
+        ProceedsErrorCode::TreasuryNotSet
+    );
+    require_keys_eq!(
+        ctx.accounts.treasury_sell_token_account.owner,
+        ctx.accounts.state.treasury,
+        ProceedsErrorCode::InvalidTreasuryAccount
+    );
+    require!(
+        amount > 0 && amount <= ctx.accounts.offer_sell_token_account.amount,
+        ProceedsErrorCode::InvalidAmount
+    );
+    check_auction_settled(&ctx.accounts.offer)?;
+    check_no_open_subscriptions(&ctx.accounts.offer)?;
+    check_proceeds_unlocked(&ctx.accounts.offer, Clock::get()?.unix_timestamp as u64)?;
+
+    let offer = &ctx.accounts.offer;
+    let offer_id_bytes = &offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes,
+        &[offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.offer_sell_token_account.to_account_info(),
+                to: ctx.accounts.treasury_sell_token_account.to_account_info(),
+                authority: ctx.accounts.offer_token_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    msg!("Transferring {} sell tokens from offer to treasury", amount);
+
+    emit!(ProceedsWithdrawn {
+        offer_id: offer.offer_id,
+        amount,
+        treasury_token_account: ctx.accounts.treasury_sell_token_account.key(),
+        treasurer: ctx.accounts.treasurer.key(),
+    });
+
+    Ok(())
+}
+
+/// Error codes for proceeds operations.
+#[error_code]
+pub enum ProceedsErrorCode {
+    /// Triggered when setting the default public key as treasury.
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+
+    /// Triggered when withdrawing before a treasury was set.
+    #[msg("Treasury not set")]
+    TreasuryNotSet,
+
+    /// Triggered when the receiving token account is not owned by the treasury.
+    #[msg("The token account is not owned by the treasury")]
+    InvalidTreasuryAccount,
+
+    /// Triggered when withdrawing zero or more than the offer holds.
+    #[msg("Invalid amount")]
+    InvalidAmount,
+}
@@ -0,0 +1,254 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{Offer, Role, State, BPS_DENOMINATOR, MAX_FEE_BPS};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when the protocol fee is changed.
+#[event]
+pub struct ProtocolFeeSet {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+/// Event emitted when the protocol fee override of an offer is changed.
+#[event]
+pub struct OfferFeeSet {
+    pub offer_id: u64,
+    /// The override, `None` if the offer uses the protocol fee.
+    pub fee_bps: Option<u16>,
+}
+
+/// Event emitted when accumulated fees are withdrawn from a fee vault.
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub treasurer: Pubkey,
+}
+
+/// Account structure for changing the protocol fee.
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+    /// Program state holding the fee, ensures `authority` holds the admin role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the protocol fee deducted from the sell tokens of every take.
+///
+/// Emits a `ProtocolFeeSet` event.
+///
+/// # Errors
+/// - [`ProtocolFeeErrorCode::InvalidFeeBps`] if `fee_bps` exceeds `MAX_FEE_BPS`.
+pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_FEE_BPS, ProtocolFeeErrorCode::InvalidFeeBps);
+
+    let state = &mut ctx.accounts.state;
+    let old_fee_bps = state.fee_bps;
+    state.fee_bps = fee_bps;
+
+    emit!(ProtocolFeeSet {
+        old_fee_bps,
+        new_fee_bps: fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Account structure for changing the protocol fee override of an offer.
+#[derive(Accounts)]
+pub struct SetOfferFee<'info> {
+    /// The offer whose fee is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Overrides the protocol fee of an offer, `None` to return to the protocol fee.
+///
+/// Emits an `OfferFeeSet` event.
+///
+/// # Errors
+/// - [`ProtocolFeeErrorCode::InvalidFeeBps`] if `fee_bps` exceeds `MAX_FEE_BPS`.
+pub fn set_offer_fee(ctx: Context<SetOfferFee>, fee_bps: Option<u16>) -> Result<()> {
+    require!(
+        fee_bps.is_none_or(|fee_bps| fee_bps <= MAX_FEE_BPS),
+        ProtocolFeeErrorCode::InvalidFeeBps
+    );
+
+    let offer = &mut ctx.accounts.offer;
+    offer.fee_bps = fee_bps;
+
+    emit!(OfferFeeSet {
+        offer_id: offer.offer_id,
+        fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Account structure for withdrawing accumulated fees.
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    /// Fee vault of `mint`, sends the fees.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = fee_vault_authority,
+    )]
+    pub fee_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Token account receiving the fees.
+    #[account(mut, token::mint = mint)]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the withdrawn fees.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA owning the fee vaults, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"fee_vault_authority"], bump)]
+    pub fee_vault_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `treasurer` holds the treasurer role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Treasurer, &treasurer.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss or a treasurer withdrawing the fees.
+    pub treasurer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraws accumulated fees from a fee vault.
+///
+/// Emits a `FeesWithdrawn` event.
+///
+/// # Errors
+/// - [`ProtocolFeeErrorCode::InvalidAmount`] if `amount` is zero or exceeds the vault balance.
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require!(
+        amount > 0 && amount <= ctx.accounts.fee_vault_token_account.amount,
+        ProtocolFeeErrorCode::InvalidAmount
+    );
+
+    let seeds = &[
+        b"fee_vault_authority".as_ref(),
+        &[ctx.bumps.fee_vault_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault_token_account.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.fee_vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    msg!("Transferring {} fee tokens from fee vault to treasury", amount);
+
+    emit!(FeesWithdrawn {
+        mint: ctx.accounts.mint.key(),
+        amount,
+        destination: ctx.accounts.destination_token_account.key(),
+        treasurer: ctx.accounts.treasurer.key(),
+    });
+
+    Ok(())
+}
+
+/// Calculates the protocol fee deducted from the sell tokens of a take.
+///
+/// Uses the offer's override if set, the protocol fee otherwise, rounding down. Takes of offers
+/// with a minimum raise are not charged, so a missed raise refunds everything the taker paid.
+///
+/// # Errors
+/// - [`ProtocolFeeErrorCode::CalculationOverflow`] if the calculation overflows.
+pub fn calculate_fee(state: &State, offer: &Offer, sell_token_amount: u64) -> Result<u64> {
+    if offer.min_raise > 0 {
+        return Ok(0);
+    }
+    let fee_bps = offer.fee_bps.unwrap_or(state.fee_bps);
+    let fee = (sell_token_amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ProtocolFeeErrorCode::CalculationOverflow)?
+        / BPS_DENOMINATOR as u128;
+    Ok(fee as u64)
+}
+
+/// Checks that a fee vault was passed when a fee is due, and that it is the fee vault of `mint`.
+///
+/// Fee vaults are the associated token accounts of the `["fee_vault_authority"]` PDA.
+///
+/// # Errors
+/// - [`ProtocolFeeErrorCode::MissingFeeVault`] if `fee` is non-zero and no fee vault was passed.
+/// - [`ProtocolFeeErrorCode::InvalidFeeVault`] if the passed account is not the fee vault of `mint`.
+pub fn verify_fee_vault(fee_vault: Option<Pubkey>, mint: &Pubkey, fee: u64) -> Result<()> {
+    let Some(fee_vault) = fee_vault else {
+        require!(fee == 0, ProtocolFeeErrorCode::MissingFeeVault);
+        return Ok(());
+    };
+    let (fee_vault_authority, _) =
+        Pubkey::find_program_address(&[b"fee_vault_authority"], &crate::ID);
+    require_keys_eq!(
+        fee_vault,
+        get_associated_token_address(&fee_vault_authority, mint),
+        ProtocolFeeErrorCode::InvalidFeeVault
+    );
+    Ok(())
+}
+
+/// Error codes for protocol fee operations.
+#[error_code]
+pub enum ProtocolFeeErrorCode {
+    /// Triggered when a fee exceeds `MAX_FEE_BPS`.
+    #[msg("Invalid fee basis points")]
+    InvalidFeeBps,
+
+    /// Triggered when withdrawing zero or more than the vault holds.
+    #[msg("Invalid amount")]
+    InvalidAmount,
+
+    /// Triggered when a fee is due and no fee vault was passed.
+    #[msg("Missing fee vault")]
+    MissingFeeVault,
+
+    /// Triggered when the passed account is not the fee vault of the sell token.
+    #[msg("Invalid fee vault")]
+    InvalidFeeVault,
+
+    /// Triggered when the fee calculation overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -0,0 +1,168 @@
+use crate::instructions::pricing::{
+    calculate_buy_amount, calculate_current_sell_amount, mul_div, next_price_change_time,
+    offer_interval, schedule_start_time, Rounding,
+};
+use crate::instructions::protocol_fee::calculate_fee;
+use crate::state::{NavOracle, Offer, State, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+/// Amount a quote is requested for.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuoteAmount {
+    /// Quotes the buy tokens received for exactly `sell_token_amount` sell tokens.
+    ExactSell { sell_token_amount: u64 },
+    /// Quotes the sell tokens needed to receive at least `buy_token_1_amount` of buy token 1.
+    ExactBuy { buy_token_1_amount: u64 },
+}
+
+/// Quote of an offer at the current time, written as return data by `quote_offer`.
+///
+/// - `interval`: Index of the current price interval, starting at `0`, also `0` while the price kept by `update_offer` applies.
+/// - `number_of_intervals`: Number of price intervals of the offer's schedule.
+/// - `current_sell_token_amount`: Sell token amount matching the offer's total buy token amounts at the current price.
+/// - `sell_token_amount`: Sell tokens the taker pays, including the protocol fee.
+/// - `fee_amount`: Part of `sell_token_amount` going to the fee vault.
+/// - `buy_token_1_amount`: Buy token 1 amount the taker receives.
+/// - `buy_token_2_amount`: Buy token 2 amount the taker receives, `0` for offers with one buy token.
+/// - `next_price_change_time`: Unix timestamp the price changes next, `0` if it won't change again or the offer is NAV priced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OfferQuote {
+    pub offer_id: u64,
+    pub interval: u64,
+    pub number_of_intervals: u64,
+    pub current_sell_token_amount: u64,
+    pub sell_token_amount: u64,
+    pub fee_amount: u64,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    pub next_price_change_time: u64,
+}
+
+/// Account structure for quoting an offer.
+///
+/// No account is written, so the instruction can be simulated to read the quote.
+#[derive(Accounts)]
+pub struct QuoteOffer<'info> {
+    /// The offer to quote.
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, provides the protocol fee.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// NAV oracle referenced by the offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+}
+
+/// Quotes an offer at the current price and writes the [`OfferQuote`] as return data.
+///
+/// Uses the same pricing as the take instructions, so simulating it yields the amounts a take in
+/// the same slot would transfer, before any referral fee. Exact buy quotes return the smallest
+/// sell token amount receiving at least the requested buy token 1 amount.
+///
+/// # Errors
+/// - [`QuoteOfferErrorCode::InvalidQuoteAmount`] if the requested amount is zero.
+/// - `PricingErrorCode` errors if the offer is not active or the amounts can't be calculated.
+/// - `NavOracleErrorCode` errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+pub fn quote_offer(ctx: Context<QuoteOffer>, amount: QuoteAmount) -> Result<OfferQuote> {
+    let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    let schedule_interval = offer_interval(offer, current_time)?;
+    let current_sell_token_amount =
+        calculate_current_sell_amount(offer, ctx.accounts.nav_oracle.as_deref(), current_time)?;
+
+    let sell_token_amount = match amount {
+        QuoteAmount::ExactSell { sell_token_amount } => sell_token_amount,
+        QuoteAmount::ExactBuy { buy_token_1_amount } => {
+            require!(buy_token_1_amount > 0, QuoteOfferErrorCode::InvalidQuoteAmount);
+            calculate_sell_amount_for(
+                &ctx.accounts.state,
+                offer,
+                buy_token_1_amount,
+                current_sell_token_amount,
+            )?
+        }
+    };
+    require!(sell_token_amount > 0, QuoteOfferErrorCode::InvalidQuoteAmount);
+
+    let fee_amount = calculate_fee(&ctx.accounts.state, offer, sell_token_amount)?;
+    let net_sell_token_amount = sell_token_amount - fee_amount;
+    let buy_token_1_amount = calculate_buy_amount(
+        net_sell_token_amount,
+        offer.buy_token_1.amount,
+        current_sell_token_amount,
+    )?;
+    let buy_token_2_amount = if offer.buy_token_2.amount > 0 {
+        calculate_buy_amount(
+            net_sell_token_amount,
+            offer.buy_token_2.amount,
+            current_sell_token_amount,
+        )?
+    } else {
+        0
+    };
+
+    let (interval, number_of_intervals, next_price_change_time) = match schedule_interval {
+        Some((interval, number_of_intervals)) => (
+            interval,
+            number_of_intervals,
+            next_price_change_time(offer, interval, number_of_intervals).unwrap_or(0),
+        ),
+        None => (
+            0,
+            (offer.offer_end_time - offer.schedule_start_time) / offer.price_fix_duration,
+            schedule_start_time(offer),
+        ),
+    };
+    let next_price_change_time = if offer.nav_oracle == Pubkey::default() {
+        next_price_change_time
+    } else {
+        0
+    };
+
+    Ok(OfferQuote {
+        offer_id: offer.offer_id,
+        interval,
+        number_of_intervals,
+        current_sell_token_amount,
+        sell_token_amount,
+        fee_amount,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        next_price_change_time,
+    })
+}
+
+/// Calculates the smallest sell token amount buying at least `buy_token_1_amount` after the protocol fee.
+///
+/// The net amount is rounded up, and grossed up by the fee rounded up as well, so the forward
+/// calculation of the take never falls short of the requested amount.
+fn calculate_sell_amount_for(
+    state: &State,
+    offer: &Offer,
+    buy_token_1_amount: u64,
+    current_sell_token_amount: u64,
+) -> Result<u64> {
+    let net_sell_token_amount = mul_div(
+        buy_token_1_amount,
+        current_sell_token_amount,
+        offer.buy_token_1.amount,
+        Rounding::Up,
+    )?;
+    let fee_bps = offer.fee_bps.unwrap_or(state.fee_bps) as u64;
+    mul_div(
+        net_sell_token_amount,
+        BPS_DENOMINATOR,
+        BPS_DENOMINATOR - fee_bps,
+        Rounding::Up,
+    )
+}
+
+/// Error codes for offer quotes.
+#[error_code]
+pub enum QuoteOfferErrorCode {
+    /// Triggered when quoting a zero amount.
+    #[msg("The quoted amount must be greater than zero")]
+    InvalidQuoteAmount,
+}
@@ -0,0 +1,708 @@
+use crate::instructions::nav_oracle::{apply_nav_spread, current_nav, NavOracleErrorCode, MAX_NAV_SPREAD_BPS};
+use crate::instructions::pause::check_program_not_paused;
+use crate::instructions::pricing::{mul_div, Rounding};
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{NAV_PRECISION, NavOracle, RedemptionOffer, Role, State};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when a redemption offer is created.
+#[event]
+pub struct RedemptionOfferMade {
+    pub offer_id: u64,
+    pub boss: Pubkey,
+    pub payout_token_total_amount: u64,
+    pub price_start: u64,
+    pub price_end: u64,
+    pub offer_start_time: u64,
+    pub offer_end_time: u64,
+    pub price_fix_duration: u64,
+    pub nav_oracle: Pubkey,
+    pub nav_spread_bps: i16,
+    pub epoch_duration: u64,
+    pub epoch_cap: u64,
+}
+
+/// Event emitted when a user redeems through a redemption offer.
+#[event]
+pub struct RedemptionOfferTaken {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    pub redeem_token_amount: u64,
+    pub payout_token_amount: u64,
+    /// Whether the tendered tokens were burned (`true`) or escrowed for the boss (`false`).
+    pub burned: bool,
+    pub epoch: u64,
+    pub epoch_redeemed: u64,
+    pub remaining_payout_token_amount: u64,
+}
+
+/// Event emitted when a redemption offer is closed.
+#[event]
+pub struct RedemptionOfferClosed {
+    pub offer_id: u64,
+    pub boss: Pubkey,
+    pub payout_token_returned: u64,
+    pub redeem_token_returned: u64,
+}
+
+/// Parameters of a new redemption offer.
+///
+/// - `payout_token_total_amount`: Amount of payout tokens escrowed as redemption liquidity.
+/// - `price_start`: Payout token base units per `NAV_PRECISION` redeem token base units in the first interval.
+/// - `price_end`: Payout token base units per `NAV_PRECISION` redeem token base units in the last interval.
+/// - `offer_start_time`: Unix timestamp for when the redemption offer becomes active.
+/// - `offer_end_time`: Unix timestamp for when the redemption offer expires.
+/// - `price_fix_duration`: Duration in seconds for each price interval. The price moves linearly from
+///   `price_start` in the first interval to `price_end` in the last one, in either direction.
+/// - `nav_spread_bps`: Spread applied on top of the NAV, ignored when no oracle is passed.
+/// - `epoch_duration`: Duration in seconds of a redemption epoch.
+/// - `epoch_cap`: Maximum amount of redeem tokens accepted per epoch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MakeRedemptionOfferArgs {
+    pub payout_token_total_amount: u64,
+    pub price_start: u64,
+    pub price_end: u64,
+    pub offer_start_time: u64,
+    pub offer_end_time: u64,
+    pub price_fix_duration: u64,
+    pub nav_spread_bps: i16,
+    pub epoch_duration: u64,
+    pub epoch_cap: u64,
+}
+
+/// Account structure for creating a redemption offer.
+///
+/// The boss escrows payout tokens that users can claim by tendering redeem tokens.
+///
+/// # Preconditions
+/// - All Associated Token Accounts (ATAs) must be initialized prior to execution.
+///   This includes `offer_payout_token_account`, `offer_redeem_token_account` and `boss_payout_token_account`.
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct MakeRedemptionOffer<'info> {
+    /// The redemption offer account to be initialized, with rent paid by `boss`.
+    ///
+    /// # Note
+    /// - Space is allocated as `8 + RedemptionOffer::INIT_SPACE` bytes, where 8 bytes are for the discriminator.
+    /// - Seeded with `"redemption_offer"` and `offer_id` for PDA derivation.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + RedemptionOffer::INIT_SPACE,
+        seeds = [b"redemption_offer", offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_offer: Box<Account<'info, RedemptionOffer>>,
+
+    /// Offer's payout token ATA, must exist prior to execution, controlled by `offer_token_authority`.
+    #[account(
+        mut,
+        associated_token::mint = payout_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's redeem token ATA, must exist prior to execution, controlled by `offer_token_authority`.
+    ///
+    /// # Note
+    /// Included to escrow tendered tokens when they are not burned.
+    #[account(
+        associated_token::mint = redeem_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_offer_authority", offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Boss's payout token ATA, must exist prior to execution, owned by `boss`.
+    #[account(
+        mut,
+        associated_token::mint = payout_token_mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the token users tender.
+    pub redeem_token_mint: Box<Account<'info, Mint>>,
+
+    /// Mint of the token users receive.
+    pub payout_token_mint: Box<Account<'info, Mint>>,
+
+    /// NAV oracle to price the payout against. Omit to use the interval schedule.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// Program state, ensures `boss` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The signer funding and authorizing the offer creation, the boss or an offer manager.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a redemption offer.
+///
+/// Transfers `payout_token_total_amount` from the boss into the offer's payout token account.
+/// The payout per redeem token either follows the interval schedule between `price_start` and
+/// `price_end`, or, if `nav_oracle` is passed, the pool's NAV plus `nav_spread_bps`.
+/// Emits a `RedemptionOfferMade` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the redemption offer.
+/// - `offer_id`: Unique identifier for the redemption offer, used in PDA derivation.
+/// - `args`: Amounts, price schedule and epochs of the redemption offer, see [`MakeRedemptionOfferArgs`].
+///
+/// # Errors
+/// - [`RedemptionOfferErrorCode::InsufficientBalance`] if the boss lacks `payout_token_total_amount`.
+/// - [`RedemptionOfferErrorCode::InvalidAmount`] if an amount, price or the epoch cap is zero.
+/// - [`RedemptionOfferErrorCode::InvalidOfferTime`] if the offer times are inconsistent.
+/// - [`RedemptionOfferErrorCode::InvalidPriceFixDuration`] if `price_fix_duration` is invalid.
+/// - [`RedemptionOfferErrorCode::InvalidEpochDuration`] if `epoch_duration` is zero.
+/// - [`NavOracleErrorCode::InvalidSpread`] if `nav_spread_bps` is out of bounds.
+pub fn make_redemption_offer(
+    ctx: Context<MakeRedemptionOffer>,
+    offer_id: u64,
+    args: MakeRedemptionOfferArgs,
+) -> Result<()> {
+    let MakeRedemptionOfferArgs {
+        payout_token_total_amount,
+        price_start,
+        price_end,
+        offer_start_time,
+        offer_end_time,
+        price_fix_duration,
+        nav_spread_bps,
+        epoch_duration,
+        epoch_cap,
+    } = args;
+    require!(
+        payout_token_total_amount > 0 && price_start > 0 && price_end > 0 && epoch_cap > 0,
+        RedemptionOfferErrorCode::InvalidAmount
+    );
+    require!(
+        offer_start_time < offer_end_time,
+        RedemptionOfferErrorCode::InvalidOfferTime
+    );
+    require!(
+        price_fix_duration > 0 && (offer_end_time - offer_start_time) >= price_fix_duration,
+        RedemptionOfferErrorCode::InvalidPriceFixDuration
+    );
+    require!(
+        (offer_end_time - offer_start_time).is_multiple_of(price_fix_duration),
+        RedemptionOfferErrorCode::InvalidOfferTime
+    );
+    require!(epoch_duration > 0, RedemptionOfferErrorCode::InvalidEpochDuration);
+    require!(
+        (-MAX_NAV_SPREAD_BPS..=MAX_NAV_SPREAD_BPS).contains(&nav_spread_bps),
+        NavOracleErrorCode::InvalidSpread
+    );
+    require!(
+        ctx.accounts.boss_payout_token_account.amount >= payout_token_total_amount,
+        RedemptionOfferErrorCode::InsufficientBalance
+    );
+
+    let nav_oracle = ctx
+        .accounts
+        .nav_oracle
+        .as_ref()
+        .map_or(Pubkey::default(), |nav_oracle| nav_oracle.key());
+
+    let redemption_offer = &mut ctx.accounts.redemption_offer;
+    redemption_offer.offer_id = offer_id;
+    redemption_offer.redeem_token_mint = ctx.accounts.redeem_token_mint.key();
+    redemption_offer.payout_token_mint = ctx.accounts.payout_token_mint.key();
+    redemption_offer.price_start = price_start;
+    redemption_offer.price_end = price_end;
+    redemption_offer.price_fix_duration = price_fix_duration;
+    redemption_offer.offer_start_time = offer_start_time;
+    redemption_offer.offer_end_time = offer_end_time;
+    redemption_offer.nav_oracle = nav_oracle;
+    redemption_offer.nav_spread_bps = if nav_oracle == Pubkey::default() { 0 } else { nav_spread_bps };
+    redemption_offer.epoch_duration = epoch_duration;
+    redemption_offer.epoch_cap = epoch_cap;
+    redemption_offer.current_epoch = 0;
+    redemption_offer.epoch_redeemed = 0;
+    redemption_offer.authority_bump = ctx.bumps.offer_token_authority;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.boss_payout_token_account.to_account_info(),
+                to: ctx.accounts.offer_payout_token_account.to_account_info(),
+                authority: ctx.accounts.boss.to_account_info(),
+            },
+        ),
+        payout_token_total_amount,
+    )?;
+    msg!("Transferring {} payout tokens from boss to redemption offer", payout_token_total_amount);
+
+    emit!(RedemptionOfferMade {
+        offer_id,
+        boss: ctx.accounts.boss.key(),
+        payout_token_total_amount,
+        price_start,
+        price_end,
+        offer_start_time,
+        offer_end_time,
+        price_fix_duration,
+        nav_oracle,
+        nav_spread_bps: redemption_offer.nav_spread_bps,
+        epoch_duration,
+        epoch_cap,
+    });
+
+    Ok(())
+}
+
+/// Account structure for redeeming through a redemption offer.
+///
+/// # Preconditions
+/// - All Associated Token Accounts (ATAs) must be initialized prior to execution.
+///   This includes `offer_payout_token_account`, `offer_redeem_token_account`,
+///   `user_redeem_token_account` and `user_payout_token_account`.
+#[derive(Accounts)]
+pub struct TakeRedemptionOffer<'info> {
+    /// The redemption offer being taken.
+    #[account(mut)]
+    pub redemption_offer: Box<Account<'info, RedemptionOffer>>,
+
+    /// Offer's payout token ATA, sends payout tokens to the user.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.payout_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's redeem token ATA, escrows tendered tokens for the boss when they are not burned.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.redeem_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's redeem token ATA, sends the tendered tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.redeem_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's payout token ATA, receives payout tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.payout_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the redeem token, mutable so tendered tokens can be burned.
+    #[account(
+        mut,
+        constraint = redeem_token_mint.key() == redemption_offer.redeem_token_mint @ RedemptionOfferErrorCode::InvalidMint
+    )]
+    pub redeem_token_mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_offer_authority", redemption_offer.offer_id.to_le_bytes().as_ref()],
+        bump = redemption_offer.authority_bump
+    )]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// NAV oracle referenced by the redemption offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// The user redeeming, signs the transaction.
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Redeems tokens through a redemption offer.
+///
+/// The user tenders `redeem_token_amount` and receives the payout at the current price, rounded
+/// down in favour of the offer. If the program's `"mint_authority"` PDA is the mint authority of
+/// the redeem token, the tendered tokens are burned; otherwise they are escrowed in the offer's
+/// redeem token account until the boss closes the offer. Emits a `RedemptionOfferTaken` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the redemption.
+/// - `redeem_token_amount`: Amount of redeem tokens the user tenders.
+///
+/// # Errors
+/// - [`RedemptionOfferErrorCode::InvalidCurrentTime`] if the redemption offer is not active.
+/// - [`RedemptionOfferErrorCode::EpochCapExceeded`] if the amount exceeds what is left of the epoch cap.
+/// - [`RedemptionOfferErrorCode::InsufficientLiquidity`] if the offer lacks payout tokens.
+/// - [`RedemptionOfferErrorCode::ZeroPayoutAmount`] if the payout rounds down to zero.
+/// - [`NavOracleErrorCode`] errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+/// - `PauseErrorCode::ProgramPaused` if the program is paused.
+pub fn take_redemption_offer(ctx: Context<TakeRedemptionOffer>, redeem_token_amount: u64) -> Result<()> {
+    check_program_not_paused(&ctx.accounts.state)?;
+    require!(redeem_token_amount > 0, RedemptionOfferErrorCode::InvalidAmount);
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let redemption_offer = &ctx.accounts.redemption_offer;
+    require!(
+        current_time >= redemption_offer.offer_start_time && current_time < redemption_offer.offer_end_time,
+        RedemptionOfferErrorCode::InvalidCurrentTime
+    );
+
+    let price = calculate_current_redemption_price(
+        redemption_offer,
+        ctx.accounts.nav_oracle.as_deref(),
+        current_time,
+    )?;
+    msg!("Calculated current redemption price: {}", price);
+
+    let payout_token_amount = u64::try_from(
+        (redeem_token_amount as u128) * (price as u128) / (NAV_PRECISION as u128),
+    )
+    .map_err(|_| error!(RedemptionOfferErrorCode::CalculationOverflow))?;
+    require!(payout_token_amount > 0, RedemptionOfferErrorCode::ZeroPayoutAmount);
+    require!(
+        ctx.accounts.offer_payout_token_account.amount >= payout_token_amount,
+        RedemptionOfferErrorCode::InsufficientLiquidity
+    );
+
+    let epoch = (current_time - redemption_offer.offer_start_time) / redemption_offer.epoch_duration;
+    let epoch_redeemed = if epoch == redemption_offer.current_epoch {
+        redemption_offer.epoch_redeemed
+    } else {
+        0
+    }
+    .checked_add(redeem_token_amount)
+    .ok_or(RedemptionOfferErrorCode::CalculationOverflow)?;
+    require!(
+        epoch_redeemed <= redemption_offer.epoch_cap,
+        RedemptionOfferErrorCode::EpochCapExceeded
+    );
+
+    let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority"], &crate::ID);
+    let burned = ctx.accounts.redeem_token_mint.mint_authority == COption::Some(mint_authority);
+    if burned {
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.redeem_token_mint.to_account_info(),
+                    from: ctx.accounts.user_redeem_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            redeem_token_amount,
+        )?;
+        msg!("Burning {} redeem tokens from user", redeem_token_amount);
+    } else {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_redeem_token_account.to_account_info(),
+                    to: ctx.accounts.offer_redeem_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            redeem_token_amount,
+        )?;
+        msg!("Transferring {} redeem tokens from user to redemption offer", redeem_token_amount);
+    }
+
+    let offer_id_bytes = &redemption_offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"redemption_offer_authority".as_ref(),
+        offer_id_bytes,
+        &[redemption_offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.offer_payout_token_account.to_account_info(),
+                to: ctx.accounts.user_payout_token_account.to_account_info(),
+                authority: ctx.accounts.offer_token_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        payout_token_amount,
+    )?;
+    msg!("Transferring {} payout tokens from redemption offer to user", payout_token_amount);
+
+    let redemption_offer = &mut ctx.accounts.redemption_offer;
+    redemption_offer.current_epoch = epoch;
+    redemption_offer.epoch_redeemed = epoch_redeemed;
+
+    emit!(RedemptionOfferTaken {
+        offer_id: redemption_offer.offer_id,
+        user: ctx.accounts.user.key(),
+        redeem_token_amount,
+        payout_token_amount,
+        burned,
+        epoch,
+        epoch_redeemed,
+        remaining_payout_token_amount: ctx.accounts.offer_payout_token_account.amount - payout_token_amount,
+    });
+
+    Ok(())
+}
+
+/// Calculates the payout token base units paid per `NAV_PRECISION` redeem token base units.
+///
+/// NAV priced offers use the NAV plus their spread, rounded down. Scheduled offers move linearly
+/// from `price_start` in the first interval to `price_end` in the last interval, rounded down in
+/// favour of the offer whether the schedule ascends or descends.
+///
+/// # Errors
+/// - [`NavOracleErrorCode::MissingNavOracle`] if the offer is NAV priced and no oracle account was passed.
+/// - Any error of [`current_nav`] for NAV priced offers.
+pub fn calculate_current_redemption_price(
+    redemption_offer: &RedemptionOffer,
+    nav_oracle: Option<&Account<NavOracle>>,
+    current_time: u64,
+) -> Result<u64> {
+    if redemption_offer.nav_oracle != Pubkey::default() {
+        let nav_oracle = nav_oracle.ok_or(NavOracleErrorCode::MissingNavOracle)?;
+        let nav = current_nav(nav_oracle, redemption_offer.nav_oracle, current_time)?;
+        return apply_nav_spread(nav, redemption_offer.nav_spread_bps, Rounding::Down);
+    }
+
+    let number_of_intervals = (redemption_offer.offer_end_time - redemption_offer.offer_start_time)
+        / redemption_offer.price_fix_duration;
+    if number_of_intervals <= 1 {
+        return Ok(redemption_offer.price_start);
+    }
+    let current_interval = (current_time.saturating_sub(redemption_offer.offer_start_time)
+        / redemption_offer.price_fix_duration)
+        .min(number_of_intervals - 1);
+
+    let price_start = redemption_offer.price_start;
+    let price_end = redemption_offer.price_end;
+    let delta = price_end.abs_diff(price_start);
+    // Both branches stay between the start and end prices, so they cannot overflow.
+    if price_end >= price_start {
+        let change = mul_div(delta, current_interval, number_of_intervals - 1, Rounding::Down)?;
+        Ok(price_start + change)
+    } else {
+        let change = mul_div(delta, current_interval, number_of_intervals - 1, Rounding::Up)?;
+        Ok(price_start - change)
+    }
+}
+
+/// Account structure for closing a redemption offer.
+///
+/// # Preconditions
+/// - All Associated Token Accounts (ATAs) must be initialized prior to execution.
+///   This includes `boss_payout_token_account` and `boss_redeem_token_account`.
+#[derive(Accounts)]
+pub struct CloseRedemptionOffer<'info> {
+    /// The redemption offer to be closed, with rent refunded to `boss`.
+    #[account(mut, close = boss)]
+    pub redemption_offer: Box<Account<'info, RedemptionOffer>>,
+
+    /// Offer's payout token ATA, swept and closed.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.payout_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's redeem token ATA, swept and closed.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.redeem_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's payout token ATA, receives the unused liquidity.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.payout_token_mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's redeem token ATA, receives the escrowed tendered tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_offer.redeem_token_mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_offer_authority", redemption_offer.offer_id.to_le_bytes().as_ref()],
+        bump = redemption_offer.authority_bump
+    )]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `boss` is the boss and `authority` holds the offer manager role.
+    #[account(
+        has_one = boss,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss, receiving the remaining tokens and the rent of the closed accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the `has_one = boss` constraint of `state`.
+    #[account(mut)]
+    pub boss: AccountInfo<'info>,
+
+    /// The boss or an offer manager authorizing the closure.
+    pub authority: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Closes a redemption offer.
+///
+/// Returns the unused payout liquidity and the escrowed tendered tokens to the boss, closes the
+/// offer's token accounts and refunds the rent to the boss. Emits a `RedemptionOfferClosed` event.
+pub fn close_redemption_offer(ctx: Context<CloseRedemptionOffer>) -> Result<()> {
+    let offer_id_bytes = &ctx.accounts.redemption_offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"redemption_offer_authority".as_ref(),
+        offer_id_bytes,
+        &[ctx.accounts.redemption_offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let payout_token_returned = ctx.accounts.offer_payout_token_account.amount;
+    let redeem_token_returned = ctx.accounts.offer_redeem_token_account.amount;
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.offer_payout_token_account,
+            &ctx.accounts.boss_payout_token_account,
+            payout_token_returned,
+        ),
+        (
+            &ctx.accounts.offer_redeem_token_account,
+            &ctx.accounts.boss_redeem_token_account,
+            redeem_token_returned,
+        ),
+    ] {
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: from.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.offer_token_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+            msg!("Transferred {} tokens from {} to {}", amount, from.key(), to.key());
+        }
+
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: from.to_account_info(),
+                destination: ctx.accounts.boss.to_account_info(),
+                authority: ctx.accounts.offer_token_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(RedemptionOfferClosed {
+        offer_id: ctx.accounts.redemption_offer.offer_id,
+        boss: ctx.accounts.boss.key(),
+        payout_token_returned,
+        redeem_token_returned,
+    });
+
+    Ok(())
+}
+
+/// Error codes for redemption offer operations.
+#[error_code]
+pub enum RedemptionOfferErrorCode {
+    /// Triggered when the boss's payout token account doesn't have sufficient balance.
+    #[msg("Insufficient token balance in boss account")]
+    InsufficientBalance,
+
+    /// Triggered when an amount, price or the epoch cap is zero.
+    #[msg("Amounts, prices and the epoch cap must be greater than zero")]
+    InvalidAmount,
+
+    /// Triggered when the offer times are inconsistent.
+    #[msg("Offer end time must be greater than start time and end time - start time must be divisible by price fix duration")]
+    InvalidOfferTime,
+
+    /// Triggered when the price fix duration is invalid.
+    #[msg("Price fix duration must be greater than zero and less than or equal to the total offer duration")]
+    InvalidPriceFixDuration,
+
+    /// Triggered when the epoch duration is zero.
+    #[msg("Epoch duration must be greater than zero")]
+    InvalidEpochDuration,
+
+    /// Triggered when the redeem token mint doesn't match the redemption offer.
+    #[msg("The redeem token mint does not match the offer.")]
+    InvalidMint,
+
+    /// Triggered when the current time is outside the offer's time range.
+    #[msg("Current time must be within the offer's start and end time range.")]
+    InvalidCurrentTime,
+
+    /// Triggered when the redemption exceeds what is left of the epoch cap.
+    #[msg("Redemption exceeds the epoch cap.")]
+    EpochCapExceeded,
+
+    /// Triggered when the offer lacks payout tokens for the redemption.
+    #[msg("Insufficient payout liquidity remaining in the offer.")]
+    InsufficientLiquidity,
+
+    /// Triggered when the payout rounds down to zero.
+    #[msg("Zero payout token amount.")]
+    ZeroPayoutAmount,
+
+    /// Triggered when amount calculations overflow.
+    #[msg("Calculation overflowed or invalid.")]
+    CalculationOverflow,
+}
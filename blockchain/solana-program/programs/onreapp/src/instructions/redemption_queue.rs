@@ -0,0 +1,777 @@
+use crate::instructions::pause::check_program_not_paused;
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{NAV_PRECISION, RedemptionEpoch, RedemptionQueue, RedemptionRequest, Role, State};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when a redemption queue is created.
+#[event]
+pub struct RedemptionQueueInitialized {
+    pub queue_id: u64,
+    pub redeem_token_mint: Pubkey,
+    pub payout_token_mint: Pubkey,
+}
+
+/// Event emitted when a user locks redeem tokens into a redemption queue.
+#[event]
+pub struct RedemptionRequested {
+    pub queue_id: u64,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    /// Total amount locked by the user's request after this call.
+    pub request_amount: u64,
+    /// Total amount locked by all requests of the epoch after this call.
+    pub epoch_total_requested: u64,
+}
+
+/// Event emitted when a user cancels a pending redemption request.
+#[event]
+pub struct RedemptionCancelled {
+    pub queue_id: u64,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub epoch_total_requested: u64,
+}
+
+/// Event emitted when the boss settles a redemption epoch.
+#[event]
+pub struct RedemptionEpochSettled {
+    pub queue_id: u64,
+    pub epoch: u64,
+    pub total_requested: u64,
+    pub filled_amount: u64,
+    pub price: u64,
+    pub payout_token_amount: u64,
+}
+
+/// Event emitted when a user claims a settled redemption request.
+#[event]
+pub struct RedemptionClaimed {
+    pub queue_id: u64,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub filled_amount: u64,
+    pub payout_token_amount: u64,
+    pub returned_amount: u64,
+}
+
+/// Account structure for creating a redemption queue.
+///
+/// # Preconditions
+/// - The queue's token accounts, `queue_redeem_token_account` and `queue_payout_token_account`,
+///   must be initialized prior to execution.
+#[derive(Accounts)]
+#[instruction(queue_id: u64)]
+pub struct InitializeRedemptionQueue<'info> {
+    /// The redemption queue to be initialized, with rent paid by `boss`.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + RedemptionQueue::INIT_SPACE,
+        seeds = [b"redemption_queue", queue_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The first epoch of the queue, with rent paid by `boss`.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + RedemptionEpoch::INIT_SPACE,
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), 0u64.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// Queue's redeem token ATA, holds the locked tokens.
+    #[account(
+        associated_token::mint = redeem_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Queue's payout token ATA, holds the settlement funds until claimed.
+    #[account(
+        associated_token::mint = payout_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_queue_authority", queue_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub queue_authority: AccountInfo<'info>,
+
+    /// Mint of the token users lock.
+    pub redeem_token_mint: Box<Account<'info, Mint>>,
+
+    /// Mint of the token settlements are paid in.
+    pub payout_token_mint: Box<Account<'info, Mint>>,
+
+    /// Program state, ensures `boss` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss or an offer manager creating the queue and paying for its rent.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a redemption queue and opens its first epoch.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the queue creation.
+/// - `queue_id`: Unique identifier for the queue, used in PDA derivation.
+pub fn initialize_redemption_queue(ctx: Context<InitializeRedemptionQueue>, queue_id: u64) -> Result<()> {
+    let redemption_queue = &mut ctx.accounts.redemption_queue;
+    redemption_queue.queue_id = queue_id;
+    redemption_queue.redeem_token_mint = ctx.accounts.redeem_token_mint.key();
+    redemption_queue.payout_token_mint = ctx.accounts.payout_token_mint.key();
+    redemption_queue.current_epoch = 0;
+    redemption_queue.authority_bump = ctx.bumps.queue_authority;
+
+    let redemption_epoch = &mut ctx.accounts.redemption_epoch;
+    redemption_epoch.queue = redemption_queue.key();
+    redemption_epoch.epoch = 0;
+
+    emit!(RedemptionQueueInitialized {
+        queue_id,
+        redeem_token_mint: redemption_queue.redeem_token_mint,
+        payout_token_mint: redemption_queue.payout_token_mint,
+    });
+
+    Ok(())
+}
+
+/// Account structure for locking redeem tokens into a redemption queue.
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+    /// The redemption queue the request is made to.
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The epoch currently accepting requests.
+    #[account(
+        mut,
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), redemption_queue.current_epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// The user's request, created on first use with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + RedemptionRequest::INIT_SPACE,
+        seeds = [b"redemption_request", redemption_queue.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redemption_request: Box<Account<'info, RedemptionRequest>>,
+
+    /// Queue's redeem token ATA, receives the locked tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's redeem token ATA, sends the locked tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_queue_authority", redemption_queue.queue_id.to_le_bytes().as_ref()],
+        bump = redemption_queue.authority_bump
+    )]
+    pub queue_authority: AccountInfo<'info>,
+
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// The user locking tokens, pays for the request account.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Locks redeem tokens into the current epoch of a redemption queue.
+///
+/// Repeated requests within the same epoch add to the user's request. A request from an
+/// earlier epoch must be claimed before a new one can be made. Emits a `RedemptionRequested` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the request.
+/// - `amount`: Amount of redeem tokens to lock.
+///
+/// # Errors
+/// - [`RedemptionQueueErrorCode::InvalidAmount`] if `amount` is zero.
+/// - [`RedemptionQueueErrorCode::UnclaimedRequest`] if the user has an unclaimed request from an earlier epoch.
+/// - `PauseErrorCode::ProgramPaused` if the program is paused.
+pub fn request_redemption(ctx: Context<RequestRedemption>, amount: u64) -> Result<()> {
+    check_program_not_paused(&ctx.accounts.state)?;
+    require!(amount > 0, RedemptionQueueErrorCode::InvalidAmount);
+
+    let epoch = ctx.accounts.redemption_queue.current_epoch;
+    let redemption_request = &mut ctx.accounts.redemption_request;
+    require!(
+        redemption_request.amount == 0 || redemption_request.epoch == epoch,
+        RedemptionQueueErrorCode::UnclaimedRequest
+    );
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_redeem_token_account.to_account_info(),
+                to: ctx.accounts.queue_redeem_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    msg!("Locking {} redeem tokens from user into the redemption queue", amount);
+
+    redemption_request.queue = ctx.accounts.redemption_queue.key();
+    redemption_request.user = ctx.accounts.user.key();
+    redemption_request.epoch = epoch;
+    redemption_request.amount = redemption_request
+        .amount
+        .checked_add(amount)
+        .ok_or(RedemptionQueueErrorCode::CalculationOverflow)?;
+
+    let redemption_epoch = &mut ctx.accounts.redemption_epoch;
+    redemption_epoch.total_requested = redemption_epoch
+        .total_requested
+        .checked_add(amount)
+        .ok_or(RedemptionQueueErrorCode::CalculationOverflow)?;
+
+    emit!(RedemptionRequested {
+        queue_id: ctx.accounts.redemption_queue.queue_id,
+        user: ctx.accounts.user.key(),
+        epoch,
+        amount,
+        request_amount: redemption_request.amount,
+        epoch_total_requested: redemption_epoch.total_requested,
+    });
+
+    Ok(())
+}
+
+/// Account structure for cancelling a pending redemption request.
+#[derive(Accounts)]
+pub struct CancelRedemption<'info> {
+    /// The redemption queue the request belongs to.
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The epoch of the request, must not be settled yet.
+    #[account(
+        mut,
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), redemption_request.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// The user's request, closed with rent refunded to `user`.
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"redemption_request", redemption_queue.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redemption_request: Box<Account<'info, RedemptionRequest>>,
+
+    /// Queue's redeem token ATA, returns the locked tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's redeem token ATA, receives the locked tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_queue_authority", redemption_queue.queue_id.to_le_bytes().as_ref()],
+        bump = redemption_queue.authority_bump
+    )]
+    pub queue_authority: AccountInfo<'info>,
+
+    /// The user cancelling the request.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Cancels a pending redemption request and returns the locked tokens.
+///
+/// Only requests of an epoch that has not been settled yet can be cancelled. Emits a
+/// `RedemptionCancelled` event.
+///
+/// # Errors
+/// - [`RedemptionQueueErrorCode::EpochAlreadySettled`] if the request's epoch has been settled.
+/// - [`RedemptionQueueErrorCode::CalculationOverflow`] if the epoch total would underflow.
+pub fn cancel_redemption(ctx: Context<CancelRedemption>) -> Result<()> {
+    require!(
+        !ctx.accounts.redemption_epoch.is_settled,
+        RedemptionQueueErrorCode::EpochAlreadySettled
+    );
+
+    let amount = ctx.accounts.redemption_request.amount;
+    if amount > 0 {
+        transfer_from_queue(
+            &ctx.accounts.redemption_queue,
+            &ctx.accounts.queue_redeem_token_account,
+            &ctx.accounts.user_redeem_token_account,
+            &ctx.accounts.queue_authority,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+        msg!("Returning {} redeem tokens from the redemption queue to user", amount);
+    }
+
+    let redemption_epoch = &mut ctx.accounts.redemption_epoch;
+    redemption_epoch.total_requested = redemption_epoch
+        .total_requested
+        .checked_sub(amount)
+        .ok_or(RedemptionQueueErrorCode::CalculationOverflow)?;
+
+    emit!(RedemptionCancelled {
+        queue_id: ctx.accounts.redemption_queue.queue_id,
+        user: ctx.accounts.user.key(),
+        epoch: redemption_epoch.epoch,
+        amount,
+        epoch_total_requested: redemption_epoch.total_requested,
+    });
+
+    Ok(())
+}
+
+/// Account structure for settling the current epoch of a redemption queue.
+///
+/// # Preconditions
+/// - `boss_payout_token_account` must be initialized prior to execution.
+#[derive(Accounts)]
+pub struct SettleRedemptionEpoch<'info> {
+    /// The redemption queue being settled.
+    #[account(mut)]
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The epoch being settled, the queue's current epoch.
+    #[account(
+        mut,
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), redemption_queue.current_epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// The epoch opened by the settlement, with rent paid by `boss`.
+    #[account(
+        init,
+        payer = boss,
+        space = 8 + RedemptionEpoch::INIT_SPACE,
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), (redemption_queue.current_epoch + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// Queue's payout token ATA, receives the settlement funds.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.payout_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's payout token ATA, funds the settlement.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.payout_token_mint,
+        associated_token::authority = boss,
+    )]
+    pub boss_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_queue_authority", redemption_queue.queue_id.to_le_bytes().as_ref()],
+        bump = redemption_queue.authority_bump
+    )]
+    pub queue_authority: AccountInfo<'info>,
+
+    /// Program state, ensures `boss` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &boss.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// The boss or an offer manager funding the settlement.
+    #[account(mut)]
+    pub boss: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Solana System program for account creation and rent payment.
+    pub system_program: Program<'info, System>,
+}
+
+/// Settles the current epoch of a redemption queue and opens the next one.
+///
+/// The boss makes up to `payout_token_amount` available at `price`. The epoch is filled up to
+/// what that amount buys, and every request of the epoch is filled in the same proportion.
+/// Only the payout actually needed for the fill is transferred from the boss.
+/// Emits a `RedemptionEpochSettled` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the settlement.
+/// - `payout_token_amount`: Maximum amount of payout tokens the boss funds.
+/// - `price`: Payout token base units per `NAV_PRECISION` redeem token base units.
+///
+/// # Errors
+/// - [`RedemptionQueueErrorCode::InvalidAmount`] if `price` is zero.
+/// - [`RedemptionQueueErrorCode::CalculationOverflow`] if amount calculations overflow.
+pub fn settle_redemption_epoch(
+    ctx: Context<SettleRedemptionEpoch>,
+    payout_token_amount: u64,
+    price: u64,
+) -> Result<()> {
+    require!(price > 0, RedemptionQueueErrorCode::InvalidAmount);
+
+    let total_requested = ctx.accounts.redemption_epoch.total_requested;
+    let affordable = (payout_token_amount as u128) * (NAV_PRECISION as u128) / (price as u128);
+    let filled_amount = (total_requested as u128).min(affordable) as u64;
+    let required_payout = u64::try_from(
+        ((filled_amount as u128) * (price as u128)).div_ceil(NAV_PRECISION as u128),
+    )
+    .map_err(|_| error!(RedemptionQueueErrorCode::CalculationOverflow))?;
+
+    if required_payout > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.boss_payout_token_account.to_account_info(),
+                    to: ctx.accounts.queue_payout_token_account.to_account_info(),
+                    authority: ctx.accounts.boss.to_account_info(),
+                },
+            ),
+            required_payout,
+        )?;
+        msg!("Transferring {} payout tokens from boss to the redemption queue", required_payout);
+    }
+
+    let redemption_epoch = &mut ctx.accounts.redemption_epoch;
+    redemption_epoch.filled_amount = filled_amount;
+    redemption_epoch.price = price;
+    redemption_epoch.is_settled = true;
+    let epoch = redemption_epoch.epoch;
+
+    let redemption_queue = &mut ctx.accounts.redemption_queue;
+    redemption_queue.current_epoch = epoch + 1;
+
+    let next_redemption_epoch = &mut ctx.accounts.next_redemption_epoch;
+    next_redemption_epoch.queue = redemption_queue.key();
+    next_redemption_epoch.epoch = epoch + 1;
+
+    emit!(RedemptionEpochSettled {
+        queue_id: redemption_queue.queue_id,
+        epoch,
+        total_requested,
+        filled_amount,
+        price,
+        payout_token_amount: required_payout,
+    });
+
+    Ok(())
+}
+
+/// Account structure for claiming a settled redemption request.
+///
+/// # Preconditions
+/// - `user_payout_token_account` and `boss_redeem_token_account` must be initialized prior to execution.
+#[derive(Accounts)]
+pub struct ClaimRedemption<'info> {
+    /// The redemption queue the request belongs to.
+    pub redemption_queue: Box<Account<'info, RedemptionQueue>>,
+
+    /// The settled epoch of the request.
+    #[account(
+        seeds = [b"redemption_epoch", redemption_queue.key().as_ref(), redemption_request.epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub redemption_epoch: Box<Account<'info, RedemptionEpoch>>,
+
+    /// The user's request, closed with rent refunded to `user`.
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"redemption_request", redemption_queue.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub redemption_request: Box<Account<'info, RedemptionRequest>>,
+
+    /// Queue's redeem token ATA, releases the filled and unfilled tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Queue's payout token ATA, pays the user.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.payout_token_mint,
+        associated_token::authority = queue_authority,
+    )]
+    pub queue_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's redeem token ATA, receives the unfilled tokens.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's payout token ATA, receives the payout.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.payout_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_payout_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Boss's redeem token ATA, receives the filled tokens unless they are burned.
+    #[account(
+        mut,
+        associated_token::mint = redemption_queue.redeem_token_mint,
+        associated_token::authority = state.boss,
+    )]
+    pub boss_redeem_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the redeem token, mutable so filled tokens can be burned.
+    #[account(
+        mut,
+        constraint = redeem_token_mint.key() == redemption_queue.redeem_token_mint @ RedemptionQueueErrorCode::InvalidMint
+    )]
+    pub redeem_token_mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA for token authority, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(
+        seeds = [b"redemption_queue_authority", redemption_queue.queue_id.to_le_bytes().as_ref()],
+        bump = redemption_queue.authority_bump
+    )]
+    pub queue_authority: AccountInfo<'info>,
+
+    /// Program state, provides the boss receiving the filled tokens.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// The user claiming the request.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claims a settled redemption request.
+///
+/// The user's fill is `amount * filled_amount / total_requested` of the epoch, rounded down.
+/// The user receives the payout for the fill, rounded down, and gets the unfilled tokens back.
+/// The filled tokens are burned if the program's `"mint_authority"` PDA is the mint authority of
+/// the redeem token, and sent to the boss otherwise. Emits a `RedemptionClaimed` event.
+///
+/// # Errors
+/// - [`RedemptionQueueErrorCode::EpochNotSettled`] if the request's epoch has not been settled yet.
+pub fn claim_redemption(ctx: Context<ClaimRedemption>) -> Result<()> {
+    let redemption_epoch = &ctx.accounts.redemption_epoch;
+    require!(redemption_epoch.is_settled, RedemptionQueueErrorCode::EpochNotSettled);
+
+    let amount = ctx.accounts.redemption_request.amount;
+    let filled_amount = if redemption_epoch.total_requested == 0 {
+        0
+    } else {
+        ((amount as u128) * (redemption_epoch.filled_amount as u128)
+            / (redemption_epoch.total_requested as u128)) as u64
+    };
+    let payout_token_amount =
+        ((filled_amount as u128) * (redemption_epoch.price as u128) / (NAV_PRECISION as u128)) as u64;
+    let returned_amount = amount - filled_amount;
+
+    if payout_token_amount > 0 {
+        transfer_from_queue(
+            &ctx.accounts.redemption_queue,
+            &ctx.accounts.queue_payout_token_account,
+            &ctx.accounts.user_payout_token_account,
+            &ctx.accounts.queue_authority,
+            &ctx.accounts.token_program,
+            payout_token_amount,
+        )?;
+        msg!("Transferring {} payout tokens from the redemption queue to user", payout_token_amount);
+    }
+
+    if returned_amount > 0 {
+        transfer_from_queue(
+            &ctx.accounts.redemption_queue,
+            &ctx.accounts.queue_redeem_token_account,
+            &ctx.accounts.user_redeem_token_account,
+            &ctx.accounts.queue_authority,
+            &ctx.accounts.token_program,
+            returned_amount,
+        )?;
+        msg!("Returning {} unfilled redeem tokens from the redemption queue to user", returned_amount);
+    }
+
+    if filled_amount > 0 {
+        let (mint_authority, _) = Pubkey::find_program_address(&[b"mint_authority"], &crate::ID);
+        if ctx.accounts.redeem_token_mint.mint_authority == COption::Some(mint_authority) {
+            let queue_id_bytes = ctx.accounts.redemption_queue.queue_id.to_le_bytes();
+            let seeds = &[
+                b"redemption_queue_authority".as_ref(),
+                queue_id_bytes.as_ref(),
+                &[ctx.accounts.redemption_queue.authority_bump],
+            ];
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.redeem_token_mint.to_account_info(),
+                        from: ctx.accounts.queue_redeem_token_account.to_account_info(),
+                        authority: ctx.accounts.queue_authority.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                filled_amount,
+            )?;
+            msg!("Burning {} filled redeem tokens", filled_amount);
+        } else {
+            transfer_from_queue(
+                &ctx.accounts.redemption_queue,
+                &ctx.accounts.queue_redeem_token_account,
+                &ctx.accounts.boss_redeem_token_account,
+                &ctx.accounts.queue_authority,
+                &ctx.accounts.token_program,
+                filled_amount,
+            )?;
+            msg!("Transferring {} filled redeem tokens to boss", filled_amount);
+        }
+    }
+
+    emit!(RedemptionClaimed {
+        queue_id: ctx.accounts.redemption_queue.queue_id,
+        user: ctx.accounts.user.key(),
+        epoch: redemption_epoch.epoch,
+        filled_amount,
+        payout_token_amount,
+        returned_amount,
+    });
+
+    Ok(())
+}
+
+/// Transfers tokens out of one of the queue's token accounts, signed by the queue authority.
+fn transfer_from_queue<'info>(
+    redemption_queue: &Account<'info, RedemptionQueue>,
+    from: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    queue_authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let queue_id_bytes = redemption_queue.queue_id.to_le_bytes();
+    let seeds = &[
+        b"redemption_queue_authority".as_ref(),
+        queue_id_bytes.as_ref(),
+        &[redemption_queue.authority_bump],
+    ];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: queue_authority.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+    )
+}
+
+/// Error codes for redemption queue operations.
+#[error_code]
+pub enum RedemptionQueueErrorCode {
+    /// Triggered when the amount or price is zero.
+    #[msg("Amount and price must be greater than zero")]
+    InvalidAmount,
+
+    /// Triggered when the user still has a request from an earlier epoch.
+    #[msg("Claim the pending redemption request of an earlier epoch first")]
+    UnclaimedRequest,
+
+    /// Triggered when cancelling a request of an epoch that has been settled.
+    #[msg("The redemption epoch has already been settled")]
+    EpochAlreadySettled,
+
+    /// Triggered when claiming a request of an epoch that has not been settled.
+    #[msg("The redemption epoch has not been settled yet")]
+    EpochNotSettled,
+
+    /// Triggered when the redeem token mint doesn't match the queue.
+    #[msg("The redeem token mint does not match the queue.")]
+    InvalidMint,
+
+    /// Triggered when amount calculations overflow.
+    #[msg("Calculation overflowed or invalid.")]
+    CalculationOverflow,
+}
@@ -0,0 +1,312 @@
+use crate::instructions::roles::RoleErrorCode;
+use crate::state::{Offer, ReferrerAccount, Role, State, BPS_DENOMINATOR, MAX_FEE_BPS};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+/// Event emitted when a referrer is registered for a sell token.
+#[event]
+pub struct ReferrerRegistered {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    /// The boss or an admin that registered the referrer.
+    pub authority: Pubkey,
+}
+
+/// Event emitted when the referral fee of an offer is changed.
+#[event]
+pub struct OfferReferralFeeSet {
+    pub offer_id: u64,
+    pub referral_fee_bps: u16,
+}
+
+/// Event emitted when a referrer claims accrued referral fees.
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Account structure for registering a distribution partner as referrer for a sell token.
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    /// The referrer account, created with rent paid by `authority`.
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ReferrerAccount::INIT_SPACE,
+        seeds = [b"referrer", referrer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    /// Sell token mint the referral fees are accrued in.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The distribution partner registered as referrer, claims the fees with its signature.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as only its key is recorded.
+    pub referrer: AccountInfo<'info>,
+
+    /// Program state, ensures `authority` holds the admin role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin, authorizing and paying for the referrer account.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Solana System program for account creation.
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers a distribution partner as referrer for a sell token.
+///
+/// Only registered referrers earn referral fees, so takers cannot refer themselves through a
+/// second wallet. Emits a `ReferrerRegistered` event.
+///
+/// # Errors
+/// - `RoleErrorCode::MissingRole` if the signer doesn't hold the admin role.
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer_account = &mut ctx.accounts.referrer_account;
+    referrer_account.referrer = ctx.accounts.referrer.key();
+    referrer_account.mint = ctx.accounts.mint.key();
+    referrer_account.balance = 0;
+    referrer_account.total_earned = 0;
+    referrer_account.bump = ctx.bumps.referrer_account;
+
+    emit!(ReferrerRegistered {
+        referrer: referrer_account.referrer,
+        mint: referrer_account.mint,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Account structure for changing the referral fee of an offer.
+#[derive(Accounts)]
+pub struct SetOfferReferralFee<'info> {
+    /// The offer whose referral fee is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the referral fee credited to the referrer of a take, `0` to disable referrals.
+///
+/// Emits an `OfferReferralFeeSet` event.
+///
+/// # Errors
+/// - [`ReferralErrorCode::InvalidReferralFeeBps`] if `referral_fee_bps` exceeds `MAX_FEE_BPS`.
+pub fn set_offer_referral_fee(
+    ctx: Context<SetOfferReferralFee>,
+    referral_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        referral_fee_bps <= MAX_FEE_BPS,
+        ReferralErrorCode::InvalidReferralFeeBps
+    );
+
+    let offer = &mut ctx.accounts.offer;
+    offer.referral_fee_bps = referral_fee_bps;
+
+    emit!(OfferReferralFeeSet {
+        offer_id: offer.offer_id,
+        referral_fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Account structure for claiming accrued referral fees.
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    /// The referrer account holding the accrued balance.
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.key().as_ref(), mint.key().as_ref()],
+        bump = referrer_account.bump
+    )]
+    pub referrer_account: Account<'info, ReferrerAccount>,
+
+    /// Referral vault of `mint`, sends the fees.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = referral_vault_authority,
+    )]
+    pub referral_vault_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Token account receiving the fees.
+    #[account(mut, token::mint = mint)]
+    pub referrer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the claimed fees.
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA owning the referral vaults, does not store data.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"referral_vault_authority"], bump)]
+    pub referral_vault_authority: AccountInfo<'info>,
+
+    /// The referrer claiming the fees.
+    pub referrer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claims the whole accrued balance of a referrer account.
+///
+/// Emits a `ReferralFeesClaimed` event.
+///
+/// # Errors
+/// - [`ReferralErrorCode::NothingToClaim`] if the accrued balance is zero.
+pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referrer_account.balance;
+    require!(amount > 0, ReferralErrorCode::NothingToClaim);
+    ctx.accounts.referrer_account.balance = 0;
+
+    let seeds = &[
+        b"referral_vault_authority".as_ref(),
+        &[ctx.bumps.referral_vault_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.referral_vault_token_account.to_account_info(),
+                to: ctx.accounts.referrer_token_account.to_account_info(),
+                authority: ctx.accounts.referral_vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    msg!("Transferring {} referral fee tokens from referral vault to referrer", amount);
+
+    emit!(ReferralFeesClaimed {
+        referrer: ctx.accounts.referrer.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Calculates the referral fee of a take and credits it to the referrer account, if one was passed.
+///
+/// The fee is a share of the sell tokens of the take paid out of the offer's proceeds, not by the
+/// taker. The caller transfers it to the referral vault, the `mint` associated token account of
+/// the `["referral_vault_authority"]` PDA.
+///
+/// # Returns
+/// The referral fee, `0` if no referrer account was passed or the offer has a minimum raise,
+/// whose takes have no proceeds until the raise is met.
+///
+/// # Errors
+/// - [`ReferralErrorCode::SelfReferral`] if the referrer is the user taking the offer.
+/// - [`ReferralErrorCode::InvalidReferrerAccount`] if the referrer account is for another sell token.
+/// - [`ReferralErrorCode::MissingReferralVault`] if a fee is due and no referral vault was passed.
+/// - [`ReferralErrorCode::InvalidReferralVault`] if the passed account is not the referral vault of the sell token.
+/// - [`ReferralErrorCode::CalculationOverflow`] if the calculation overflows.
+pub fn accrue_referral_fee(
+    offer: &Offer,
+    referrer_account: Option<&mut Account<ReferrerAccount>>,
+    referral_vault: Option<Pubkey>,
+    user: &Pubkey,
+    sell_token_amount: u64,
+) -> Result<u64> {
+    let Some(referrer_account) = referrer_account else {
+        return Ok(0);
+    };
+    if offer.min_raise > 0 {
+        return Ok(0);
+    }
+    require_keys_neq!(referrer_account.referrer, *user, ReferralErrorCode::SelfReferral);
+    require_keys_eq!(
+        referrer_account.mint,
+        offer.sell_token_mint,
+        ReferralErrorCode::InvalidReferrerAccount
+    );
+
+    let referral_fee = ((sell_token_amount as u128)
+        .checked_mul(offer.referral_fee_bps as u128)
+        .ok_or(ReferralErrorCode::CalculationOverflow)?
+        / BPS_DENOMINATOR as u128) as u64;
+    if referral_fee == 0 {
+        return Ok(0);
+    }
+
+    let referral_vault = referral_vault.ok_or(ReferralErrorCode::MissingReferralVault)?;
+    let (referral_vault_authority, _) =
+        Pubkey::find_program_address(&[b"referral_vault_authority"], &crate::ID);
+    require_keys_eq!(
+        referral_vault,
+        get_associated_token_address(&referral_vault_authority, &offer.sell_token_mint),
+        ReferralErrorCode::InvalidReferralVault
+    );
+
+    referrer_account.balance = referrer_account
+        .balance
+        .checked_add(referral_fee)
+        .ok_or(ReferralErrorCode::CalculationOverflow)?;
+    referrer_account.total_earned = referrer_account
+        .total_earned
+        .checked_add(referral_fee)
+        .ok_or(ReferralErrorCode::CalculationOverflow)?;
+
+    Ok(referral_fee)
+}
+
+/// Error codes for referral operations.
+#[error_code]
+pub enum ReferralErrorCode {
+    /// Triggered when a referral fee exceeds `MAX_FEE_BPS`.
+    #[msg("Invalid referral fee basis points")]
+    InvalidReferralFeeBps,
+
+    /// Triggered when the user taking the offer passes their own referrer account.
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+
+    /// Triggered when the referrer account is for another sell token than the offer's.
+    #[msg("Invalid referrer account")]
+    InvalidReferrerAccount,
+
+    /// Triggered when a referral fee is due and no referral vault was passed.
+    #[msg("Missing referral vault")]
+    MissingReferralVault,
+
+    /// Triggered when the passed account is not the referral vault of the sell token.
+    #[msg("Invalid referral vault")]
+    InvalidReferralVault,
+
+    /// Triggered when claiming with no accrued balance.
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    /// Triggered when the referral fee calculation overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
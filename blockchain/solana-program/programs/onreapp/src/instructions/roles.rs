@@ -0,0 +1,119 @@
+use crate::state::{Role, RoleGrant, State, MAX_ROLE_GRANTS};
+use anchor_lang::prelude::*;
+
+/// Event emitted when a role is granted.
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub holder: Pubkey,
+    /// The boss or admin that granted the role.
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a role is revoked.
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub holder: Pubkey,
+    /// The boss or admin that revoked the role.
+    pub authority: Pubkey,
+}
+
+/// Account structure for granting or revoking a role.
+#[derive(Accounts)]
+pub struct UpdateRole<'info> {
+    /// Program state holding the role grants, ensures `authority` holds the admin role.
+    #[account(
+        mut,
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::Admin, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an admin.
+    pub authority: Signer<'info>,
+}
+
+/// Grants a role to a key.
+///
+/// Emits a `RoleGranted` event.
+///
+/// # Errors
+/// - [`RoleErrorCode::InvalidHolder`] if `holder` is the default public key or the boss.
+/// - [`RoleErrorCode::RoleAlreadyGranted`] if `holder` already holds `role`.
+/// - [`RoleErrorCode::TooManyRoleGrants`] if `MAX_ROLE_GRANTS` roles are already granted.
+pub fn grant_role(ctx: Context<UpdateRole>, role: Role, holder: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(
+        holder != Pubkey::default() && holder != state.boss,
+        RoleErrorCode::InvalidHolder
+    );
+
+    let grant = RoleGrant { role, holder };
+    require!(
+        !state.roles.contains(&grant),
+        RoleErrorCode::RoleAlreadyGranted
+    );
+    require!(
+        state.roles.len() < MAX_ROLE_GRANTS,
+        RoleErrorCode::TooManyRoleGrants
+    );
+    state.roles.push(grant);
+
+    emit!(RoleGranted {
+        role,
+        holder,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Revokes a role from a key.
+///
+/// Emits a `RoleRevoked` event.
+///
+/// # Errors
+/// - [`RoleErrorCode::RoleNotGranted`] if `holder` doesn't hold `role`.
+pub fn revoke_role(ctx: Context<UpdateRole>, role: Role, holder: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    let index = state
+        .roles
+        .iter()
+        .position(|grant| grant.role == role && grant.holder == holder)
+        .ok_or(RoleErrorCode::RoleNotGranted)?;
+    state.roles.swap_remove(index);
+
+    emit!(RoleRevoked {
+        role,
+        holder,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Error codes for role based access control.
+#[error_code]
+pub enum RoleErrorCode {
+    /// Triggered when the signer doesn't hold the role required by the instruction.
+    #[msg("The signer does not hold the required role")]
+    MissingRole,
+
+    /// Triggered when granting a role to the default public key or the boss.
+    #[msg("Invalid role holder")]
+    InvalidHolder,
+
+    /// Triggered when granting a role the holder already holds.
+    #[msg("The role is already granted to this holder")]
+    RoleAlreadyGranted,
+
+    /// Triggered when revoking a role the holder doesn't hold.
+    #[msg("The role is not granted to this holder")]
+    RoleNotGranted,
+
+    /// Triggered when the maximum number of role grants is reached.
+    #[msg("Too many role grants")]
+    TooManyRoleGrants,
+}
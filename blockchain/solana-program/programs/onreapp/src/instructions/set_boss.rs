@@ -2,69 +2,157 @@ use crate::state::State;
 use anchor_lang::prelude::*; // Includes `emit!` and `#[event]`
 use anchor_lang::Accounts;
 
-/// Error codes for the set_boss instruction.
+/// Error codes for the boss handover instructions.
 #[error_code]
 pub enum SetBossErrorCode {
-    /// Error when attempting to set the boss to the system program address.
+    /// Error when attempting to propose the system program address or the current boss as boss.
     InvalidBossAddress,
+    /// Error when accepting or cancelling while no boss proposal is pending.
+    NoPendingBoss,
+    /// Error when the signer accepting the proposal is not the proposed boss.
+    NotPendingBoss,
 }
 
-/// Event emitted when the boss is updated in the program state.
+/// Stage of a boss handover reported by a `BossUpdated` event.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BossUpdateKind {
+    /// The current boss proposed `new_boss`; `boss` is unchanged until the proposal is accepted.
+    Proposed,
+    /// `new_boss` accepted the proposal and replaced `old_boss`.
+    Accepted,
+    /// The current boss withdrew the proposal of `new_boss`.
+    Cancelled,
+}
+
+/// Event emitted at every stage of a boss handover.
 #[event]
 pub struct BossUpdated {
-    /// The previous boss’s public key.
+    /// The stage of the handover.
+    pub kind: BossUpdateKind,
+    /// The boss’s public key before the handover.
     pub old_boss: Pubkey,
-    /// The new boss’s public key.
+    /// The proposed boss’s public key.
     pub new_boss: Pubkey,
 }
 
-/// Account structure for updating the program’s boss.
-///
-/// This struct defines the accounts required to change the `boss` field in the program state.
+/// Account structure for proposing a new boss.
 ///
 /// # Preconditions
 /// - The `state` account must be initialized prior to execution, via an `initialize` instruction.
-/// - The current `boss` must sign the transaction to authorize the change.
+/// - The current `boss` must sign the transaction to authorize the proposal.
 #[derive(Accounts)]
-pub struct SetBoss<'info> {
-    /// The program state account, containing the current boss to be updated.
+pub struct ProposeBoss<'info> {
+    /// The program state account, storing the pending boss.
     ///
     /// # Constraints
-    /// - Must be mutable to allow updating the `boss` field.
-    /// - The `has_one = boss` constraint ensures only the current boss can modify it.
+    /// - Must be mutable to allow updating the `pending_boss` field.
+    /// - The `has_one = boss` constraint ensures only the current boss can propose.
     #[account(mut, has_one = boss)]
     pub state: Account<'info, State>,
 
-    /// The current boss, signing the transaction to authorize the update.
+    /// The current boss, signing the transaction to authorize the proposal.
     pub boss: Signer<'info>,
-
-    /// Solana System program, included for potential rent accounting.
-    pub system_program: Program<'info, System>,
 }
 
-/// Updates the boss in the program state.
+/// Proposes a new boss, the first step of a boss handover.
 ///
-/// Sets the `boss` field in the `state` account to a new public key, emitting a `BossUpdated` event
-/// for traceability. Only the current boss can call this instruction, enforced by the `has_one = boss` constraint.
+/// The proposed boss only takes over once it signs `accept_boss`, so a mistyped key cannot lock
+/// the program. A new proposal replaces a pending one. Emits a `BossUpdated` event of kind
+/// `Proposed`.
 ///
 /// # Arguments
-/// - `ctx`: Context containing the accounts for the state update.
-/// - `new_boss`: The new public key to set as the boss.
+/// - `ctx`: Context containing the accounts for the proposal.
+/// - `new_boss`: The public key proposed as boss.
 ///
 /// # Errors
-/// - [`SetBossErrorCode::InvalidBossAddress`] if the new boss is the system program address.
-///
-/// # Returns
-/// A `Result` indicating success or failure.
-pub fn set_boss(ctx: Context<SetBoss>, new_boss: Pubkey) -> Result<()> {
+/// - [`SetBossErrorCode::InvalidBossAddress`] if the new boss is the system program address or the current boss.
+pub fn propose_boss(ctx: Context<ProposeBoss>, new_boss: Pubkey) -> Result<()> {
+    let state = &mut ctx.accounts.state;
     require!(
-        new_boss != Pubkey::default(),
+        new_boss != Pubkey::default() && new_boss != state.boss,
         SetBossErrorCode::InvalidBossAddress
     );
 
+    state.pending_boss = new_boss;
+    emit!(BossUpdated {
+        kind: BossUpdateKind::Proposed,
+        old_boss: state.boss,
+        new_boss,
+    });
+    Ok(())
+}
+
+/// Account structure for accepting a boss proposal.
+#[derive(Accounts)]
+pub struct AcceptBoss<'info> {
+    /// The program state account, containing the boss to be replaced.
+    #[account(mut, seeds = [b"state"], bump)]
+    pub state: Account<'info, State>,
+
+    /// The proposed boss, signing the transaction to prove control of the key.
+    pub new_boss: Signer<'info>,
+}
+
+/// Accepts a pending boss proposal, completing the handover.
+///
+/// Emits a `BossUpdated` event of kind `Accepted`.
+///
+/// # Errors
+/// - [`SetBossErrorCode::NoPendingBoss`] if no proposal is pending.
+/// - [`SetBossErrorCode::NotPendingBoss`] if the signer is not the proposed boss.
+pub fn accept_boss(ctx: Context<AcceptBoss>) -> Result<()> {
+    let state = &mut ctx.accounts.state;
+    require!(
+        state.pending_boss != Pubkey::default(),
+        SetBossErrorCode::NoPendingBoss
+    );
+    require_keys_eq!(
+        state.pending_boss,
+        ctx.accounts.new_boss.key(),
+        SetBossErrorCode::NotPendingBoss
+    );
+
+    let old_boss = state.boss;
+    state.boss = state.pending_boss;
+    state.pending_boss = Pubkey::default();
+    emit!(BossUpdated {
+        kind: BossUpdateKind::Accepted,
+        old_boss,
+        new_boss: state.boss,
+    });
+    Ok(())
+}
+
+/// Account structure for cancelling a boss proposal.
+#[derive(Accounts)]
+pub struct CancelBossProposal<'info> {
+    /// The program state account, storing the pending boss.
+    #[account(mut, has_one = boss)]
+    pub state: Account<'info, State>,
+
+    /// The current boss, signing the transaction to authorize the cancellation.
+    pub boss: Signer<'info>,
+}
+
+/// Withdraws a pending boss proposal.
+///
+/// Emits a `BossUpdated` event of kind `Cancelled`.
+///
+/// # Errors
+/// - [`SetBossErrorCode::NoPendingBoss`] if no proposal is pending.
+pub fn cancel_boss_proposal(ctx: Context<CancelBossProposal>) -> Result<()> {
     let state = &mut ctx.accounts.state;
-    let old_boss = ctx.accounts.boss.key(); // Capture old boss before update
-    state.boss = new_boss;
-    emit!(BossUpdated { old_boss, new_boss });
+    require!(
+        state.pending_boss != Pubkey::default(),
+        SetBossErrorCode::NoPendingBoss
+    );
+
+    let new_boss = state.pending_boss;
+    state.pending_boss = Pubkey::default();
+    emit!(BossUpdated {
+        kind: BossUpdateKind::Cancelled,
+        old_boss: state.boss,
+        new_boss,
+    });
     Ok(())
 }
@@ -0,0 +1,502 @@
+use crate::instructions::kyc::verify_kyc;
+use crate::instructions::min_raise::check_no_min_raise;
+use crate::instructions::offer_inventory::verify_buy_token_2_accounts;
+use crate::instructions::offer_limits::check_no_limits;
+use crate::instructions::pause::check_not_paused;
+use crate::instructions::pricing::{mul_div, Rounding};
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::vesting::check_no_vesting;
+use crate::state::{KycRecord, Offer, Role, State, Subscription};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Event emitted when the subscription mode of an offer is changed.
+#[event]
+pub struct OfferSubscriptionSet {
+    pub offer_id: u64,
+    pub subscription: bool,
+}
+
+/// Event emitted when a user deposits sell tokens into a subscription.
+#[event]
+pub struct Subscribed {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    pub sell_token_amount: u64,
+    pub total_subscribed: u64,
+}
+
+/// Event emitted when the fill of a subscription is claimed.
+#[event]
+pub struct SubscriptionClaimed {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    /// Part of the deposit paying for the fill.
+    pub filled_sell_token_amount: u64,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    /// Part of the deposit refunded to the user.
+    pub refund_amount: u64,
+}
+
+/// Account structure for changing the subscription mode of an offer.
+#[derive(Accounts)]
+pub struct SetOfferSubscription<'info> {
+    /// The offer whose subscription mode is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets whether an offer collects subscriptions instead of being taken.
+///
+/// Subscription offers cannot be taken and don't enforce purchase limits, so offers with limits
+/// cannot be switched to subscriptions. Users deposit sell tokens via `subscribe` while the
+/// offer is active, and once it ends claim their fill at the end price of the schedule,
+/// allocated pro-rata if the offer is oversubscribed, plus a refund of the unfilled deposit.
+/// Emits an `OfferSubscriptionSet` event.
+///
+/// # Errors
+/// - [`SubscriptionErrorCode::OfferAlreadyTaken`] if the offer was already taken or subscribed.
+/// - [`SubscriptionErrorCode::UnsupportedPricing`] if the offer is NAV priced or in uniform price mode.
+/// - `MinRaiseErrorCode::MinRaiseOffer` if the offer has a minimum raise.
+/// - `VestingErrorCode::VestingOffer` if the offer has a vesting schedule.
+/// - `OfferLimitsErrorCode::LimitedOffer` if subscriptions are enabled on an offer with purchase limits.
+pub fn set_offer_subscription(ctx: Context<SetOfferSubscription>, subscription: bool) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(
+        offer.total_raised == 0 && offer.total_subscribed == 0,
+        SubscriptionErrorCode::OfferAlreadyTaken
+    );
+    require!(
+        offer.nav_oracle == Pubkey::default() && !offer.uniform_price,
+        SubscriptionErrorCode::UnsupportedPricing
+    );
+    check_no_min_raise(offer)?;
+    check_no_vesting(offer)?;
+    if subscription {
+        check_no_limits(offer)?;
+    }
+    offer.subscription = subscription;
+
+    emit!(OfferSubscriptionSet {
+        offer_id: offer.offer_id,
+        subscription,
+    });
+
+    Ok(())
+}
+
+/// Account structure for subscribing to an offer.
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    /// The subscription offer.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The user's subscription, created on the first deposit with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", offer.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    /// Offer's sell token ATA, escrows the deposit.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's sell token ATA, sends the deposit.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// KYC record of `user`, required only for offers with a minimum KYC level.
+    pub kyc_record: Option<Box<Account<'info, KycRecord>>>,
+
+    /// The subscribing user, signs the transaction and pays for the subscription account.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Solana System program for the subscription account creation.
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits sell tokens into the user's subscription to an offer.
+///
+/// The deposit is escrowed in the offer's sell token account until the fill is claimed via
+/// `claim_subscription` after the offer ends. Emits a `Subscribed` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the deposit.
+/// - `sell_token_amount`: Amount of sell tokens to deposit.
+///
+/// # Errors
+/// - [`SubscriptionErrorCode::NotSubscriptionOffer`] if the offer is not in subscription mode.
+/// - [`SubscriptionErrorCode::InvalidCurrentTime`] if the offer is not active.
+/// - [`SubscriptionErrorCode::InvalidAmount`] if `sell_token_amount` is zero.
+/// - `PauseErrorCode` errors if the program or the offer is paused.
+/// - `KycErrorCode` errors if the offer requires KYC and the user's record is missing, insufficient or expired.
+/// - [`SubscriptionErrorCode::CalculationOverflow`] if a cumulative amount overflows.
+pub fn subscribe(ctx: Context<Subscribe>, sell_token_amount: u64) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(offer.subscription, SubscriptionErrorCode::NotSubscriptionOffer);
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        current_time >= offer.offer_start_time && current_time < offer.offer_end_time,
+        SubscriptionErrorCode::InvalidCurrentTime
+    );
+    require!(sell_token_amount > 0, SubscriptionErrorCode::InvalidAmount);
+    check_not_paused(&ctx.accounts.state, offer)?;
+    verify_kyc(
+        offer.min_kyc_level,
+        ctx.accounts.kyc_record.as_deref(),
+        ctx.accounts.user.key(),
+        current_time,
+    )?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_sell_token_account.to_account_info(),
+                to: ctx.accounts.offer_sell_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        sell_token_amount,
+    )?;
+    msg!("Transferring {} sell tokens from user to offer", sell_token_amount);
+
+    let offer = &mut ctx.accounts.offer;
+    let subscription = &mut ctx.accounts.subscription;
+    if subscription.offer == Pubkey::default() {
+        subscription.offer = offer.key();
+        subscription.user = ctx.accounts.user.key();
+        subscription.bump = ctx.bumps.subscription;
+        offer.open_subscriptions = offer
+            .open_subscriptions
+            .checked_add(1)
+            .ok_or(SubscriptionErrorCode::CalculationOverflow)?;
+    }
+    subscription.sell_token_amount = subscription
+        .sell_token_amount
+        .checked_add(sell_token_amount)
+        .ok_or(SubscriptionErrorCode::CalculationOverflow)?;
+    offer.total_subscribed = offer
+        .total_subscribed
+        .checked_add(sell_token_amount)
+        .ok_or(SubscriptionErrorCode::CalculationOverflow)?;
+
+    emit!(Subscribed {
+        offer_id: offer.offer_id,
+        user: subscription.user,
+        sell_token_amount,
+        total_subscribed: offer.total_subscribed,
+    });
+
+    Ok(())
+}
+
+/// Account structure for claiming the fill of a subscription.
+#[derive(Accounts)]
+pub struct ClaimSubscription<'info> {
+    /// The ended subscription offer.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The claimed subscription, closed with its rent refunded to `user`.
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"subscription", offer.key().as_ref(), user.key().as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    /// Offer's sell token ATA, sends the refund.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 1 ATA, sends buy token 1 to the user.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's sell token ATA, receives the refund.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_sell_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's buy token 1 ATA, receives buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = user,
+    )]
+    pub user_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Offer's buy token 2 ATA, required only for offers with two buy tokens.
+    #[account(mut)]
+    pub offer_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// User's buy token 2 ATA, required only for offers with two buy tokens.
+    #[account(mut)]
+    pub user_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Derived PDA for token authority, controls offer token accounts.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation.
+    #[account(seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()], bump)]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// The owner of the subscription, receives the fill, the refund and the subscription's rent.
+    ///
+    /// # Note
+    /// This account is marked with `CHECK` as it's validated by the seed derivation of `subscription`.
+    #[account(mut)]
+    pub user: AccountInfo<'info>,
+
+    /// The caller claiming the subscription, anyone so the boss can settle every subscription.
+    pub payer: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claims the fill of a subscription once the offer has ended.
+///
+/// The offer sells its whole buy token inventory for `sell_token_end_amount`. If the deposits
+/// exceed that amount, every deposit is filled pro-rata, rounding down, and the remainder is
+/// refunded. The buy tokens are paid at the end price, rounding down. Anyone can claim on behalf
+/// of the user, the tokens always go to the user's ATAs. Emits a `SubscriptionClaimed` event.
+///
+/// # Errors
+/// - [`SubscriptionErrorCode::OfferNotEnded`] if `offer_end_time` has not passed yet.
+/// - `OfferInventoryErrorCode` errors if the offer has two buy tokens and their accounts are missing or invalid.
+/// - `PricingErrorCode::CalculationOverflow` if the allocation calculation overflows.
+/// - [`SubscriptionErrorCode::CalculationOverflow`] if the open subscription count underflows or
+///   the total raised overflows.
+pub fn claim_subscription(ctx: Context<ClaimSubscription>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        current_time >= offer.offer_end_time,
+        SubscriptionErrorCode::OfferNotEnded
+    );
+    let buy_token_2_accounts = verify_buy_token_2_accounts(
+        offer,
+        ctx.accounts.offer_buy_token_2_account.as_deref(),
+        ctx.accounts.user_buy_token_2_account.as_deref(),
+        &ctx.accounts.offer_token_authority.key(),
+        &ctx.accounts.user.key(),
+    )?;
+
+    let deposit = ctx.accounts.subscription.sell_token_amount;
+    let filled_sell_token_amount = if offer.total_subscribed > offer.sell_token_end_amount {
+        mul_div(
+            deposit,
+            offer.sell_token_end_amount,
+            offer.total_subscribed,
+            Rounding::Down,
+        )?
+    } else {
+        deposit
+    };
+    let refund_amount = deposit - filled_sell_token_amount;
+    let buy_token_1_amount = mul_div(
+        filled_sell_token_amount,
+        offer.buy_token_1.amount,
+        offer.sell_token_end_amount,
+        Rounding::Down,
+    )?;
+    let buy_token_2_amount = mul_div(
+        filled_sell_token_amount,
+        offer.buy_token_2.amount,
+        offer.sell_token_end_amount,
+        Rounding::Down,
+    )?;
+
+    let offer_id_bytes = &offer.offer_id.to_le_bytes();
+    let seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes,
+        &[offer.authority_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    if buy_token_1_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: ctx.accounts.user_buy_token_1_account.to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
+    }
+
+    if refund_amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_sell_token_account.to_account_info(),
+                    to: ctx.accounts.user_sell_token_account.to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_amount,
+        )?;
+        msg!("Transferring {} sell tokens refund from offer to user", refund_amount);
+    }
+
+    if let Some((offer_buy_token_2_account, user_buy_token_2_account)) = buy_token_2_accounts {
+        if buy_token_2_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: offer_buy_token_2_account.to_account_info(),
+                        to: user_buy_token_2_account.to_account_info(),
+                        authority: ctx.accounts.offer_token_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                buy_token_2_amount,
+            )?;
+            msg!("Transferring {} buy tokens 2 from offer to user", buy_token_2_amount);
+        }
+    }
+
+    emit!(SubscriptionClaimed {
+        offer_id: offer.offer_id,
+        user: ctx.accounts.user.key(),
+        filled_sell_token_amount,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        refund_amount,
+    });
+
+    let offer = &mut ctx.accounts.offer;
+    offer.open_subscriptions = offer
+        .open_subscriptions
+        .checked_sub(1)
+        .ok_or(SubscriptionErrorCode::CalculationOverflow)?;
+    offer.total_raised = offer
+        .total_raised
+        .checked_add(filled_sell_token_amount)
+        .ok_or(SubscriptionErrorCode::CalculationOverflow)?;
+
+    Ok(())
+}
+
+/// Checks that an offer is not in subscription mode, as subscription offers cannot be taken.
+///
+/// # Errors
+/// - [`SubscriptionErrorCode::SubscriptionOffer`] if the offer is in subscription mode.
+pub fn check_not_subscription(offer: &Offer) -> Result<()> {
+    require!(!offer.subscription, SubscriptionErrorCode::SubscriptionOffer);
+    Ok(())
+}
+
+/// Checks that every subscription of an offer was claimed before its tokens or schedule change.
+///
+/// # Errors
+/// - [`SubscriptionErrorCode::OpenSubscriptions`] if subscriptions are still open.
+pub fn check_no_open_subscriptions(offer: &Offer) -> Result<()> {
+    require!(
+        offer.open_subscriptions == 0,
+        SubscriptionErrorCode::OpenSubscriptions
+    );
+    Ok(())
+}
+
+/// Error codes for subscription operations.
+#[error_code]
+pub enum SubscriptionErrorCode {
+    /// Triggered when changing the subscription mode of an offer that was already taken or subscribed.
+    #[msg("The offer was already taken or subscribed")]
+    OfferAlreadyTaken,
+
+    /// Triggered when enabling subscriptions on a NAV priced or uniform price offer.
+    #[msg("Subscriptions require an interval priced offer")]
+    UnsupportedPricing,
+
+    /// Triggered when subscribing to an offer not in subscription mode.
+    #[msg("The offer is not in subscription mode")]
+    NotSubscriptionOffer,
+
+    /// Triggered when taking or limiting an offer in subscription mode.
+    #[msg("Subscription offers cannot be taken or limited")]
+    SubscriptionOffer,
+
+    /// Triggered when subscribing outside the offer's active period.
+    #[msg("Invalid current time")]
+    InvalidCurrentTime,
+
+    /// Triggered when depositing zero sell tokens.
+    #[msg("Invalid amount")]
+    InvalidAmount,
+
+    /// Triggered when claiming before the offer ended.
+    #[msg("The offer has not ended")]
+    OfferNotEnded,
+
+    /// Triggered when withdrawing from, changing or closing an offer with unclaimed subscriptions.
+    #[msg("The offer has unclaimed subscriptions")]
+    OpenSubscriptions,
+
+    /// Triggered when an allocation calculation overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -1,4 +1,14 @@
-use crate::state::Offer;
+use crate::instructions::dutch_auction::record_auction_fill;
+use crate::instructions::kyc::verify_kyc;
+use crate::instructions::min_raise::escrow_take;
+use crate::instructions::offer_limits::record_purchase;
+use crate::instructions::pause::check_not_paused;
+use crate::instructions::pricing::{calculate_buy_amount, calculate_current_sell_amount};
+use crate::instructions::protocol_fee::{calculate_fee, verify_fee_vault};
+use crate::instructions::referral::accrue_referral_fee;
+use crate::instructions::subscription::check_not_subscription;
+use crate::instructions::vesting::{lock_vested_take, verify_vesting_token_account};
+use crate::state::{KycRecord, NavOracle, Offer, Position, ReferrerAccount, State, VestingEscrow};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
@@ -10,6 +20,12 @@ pub struct OfferTakenOne {
     pub sell_token_amount: u64,
     pub buy_token_1_amount: u64,
     pub remaining_buy_token_amount: u64,
+    /// Part of `sell_token_amount` sent to the fee vault.
+    pub fee_amount: u64,
+    /// The referrer of the take, if any.
+    pub referrer: Option<Pubkey>,
+    /// Part of `sell_token_amount` credited to the referrer.
+    pub referral_fee_amount: u64,
 }
 
 /// Event emitted when an offer with two buy tokens is taken.
@@ -22,6 +38,12 @@ pub struct OfferTakenTwo {
     pub buy_token_2_amount: u64,
     pub remaining_buy_token_1_amount: u64,
     pub remaining_buy_token_2_amount: u64,
+    /// Part of `sell_token_amount` sent to the fee vault.
+    pub fee_amount: u64,
+    /// The referrer of the take, if any.
+    pub referrer: Option<Pubkey>,
+    /// Part of `sell_token_amount` credited to the referrer.
+    pub referral_fee_amount: u64,
 }
 
 /// Account structure for taking an offer with one buy token.
@@ -38,6 +60,7 @@ pub struct TakeOfferOne<'info> {
     /// The offer account being taken, providing offer details.
     /// Ensures this is a single buy token offer by checking `buy_token_mint_2`.
     #[account(
+        mut,
         constraint = offer.buy_token_2.mint == Pubkey::default() @ TakeOfferErrorCode::InvalidTakeOffer
     )]
     pub offer: Account<'info, Offer>,
@@ -88,71 +111,68 @@ pub struct TakeOfferOne<'info> {
   )]
     pub offer_token_authority: AccountInfo<'info>,
 
-    /// The user taking the offer, signs the transaction.
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// NAV oracle referenced by the offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// KYC record of `user`, required only for offers with a minimum KYC level.
+    pub kyc_record: Option<Box<Account<'info, KycRecord>>>,
+
+    /// Fee vault of the sell token, required only when a protocol fee is due.
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Referrer account of the take's referrer, credited with the offer's referral fee.
+    #[account(mut)]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
+    /// Referral vault of the sell token, required only when a referral fee is due.
+    #[account(mut)]
+    pub referral_vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The user's vesting escrow, required only for offers with a vesting schedule.
+    #[account(mut)]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    /// Vesting escrow's buy token 1 ATA, receives the locked buy token 1 of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_1_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The user's position in the offer, created on the first take with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", offer.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// The user taking the offer, signs the transaction and pays for the position account.
+    #[account(mut)]
     pub user: Signer<'info>,
 
     /// SPL Token program for token operations.
     pub token_program: Program<'info, Token>,
 
-    /// Solana System program, included for potential rent accounting.
+    /// Solana System program for the position account creation.
     pub system_program: Program<'info, System>,
 }
 
-/// Calculates the current sell token amount based on the offer's dynamic pricing model.
-///
-/// The price of the sell token (how much is required per buy token) changes linearly over the offer's duration.
-/// The offer is divided into intervals, each lasting `price_fix_duration` seconds.
-/// The sell token amount starts at `sell_token_start_amount` + `one_interval_amount` at the beginning of the first interval
-/// and progresses towards `sell_token_end_amount` by the end of the last interval.
-///
-/// # Arguments
-/// - `offer`: A reference to the `Offer` account containing pricing parameters like
-///   `offer_start_time`, `offer_end_time`, `price_fix_duration`,
-///   `sell_token_start_amount`, and `sell_token_end_amount`.
-///
-/// # Returns
-/// The calculated sell token amount effectively representing the "price" for the current interval.
-/// This is the amount of sell tokens that corresponds to the total `buy_token_X_amount` defined in the offer for the current time interval.
-///
-/// # Errors
-/// - [`TakeOfferErrorCode::InvalidCurrentTime`] if the current time is outside the offer's active period.
-fn calculate_current_sell_amount(
-    offer: &Offer
-) -> Result<u64> {
-    let current_time = Clock::get()?.unix_timestamp as u64;
-    
-    require!(
-        current_time >= offer.offer_start_time && current_time < offer.offer_end_time,
-        TakeOfferErrorCode::InvalidCurrentTime
-    );
-
-    let total_duration = offer.offer_end_time.checked_sub(offer.offer_start_time).unwrap();
-    let number_of_intervals = total_duration.checked_div(offer.price_fix_duration).unwrap();
-    let current_interval = current_time
-        .checked_sub(offer.offer_start_time)
-        .unwrap()
-        .checked_div(offer.price_fix_duration)
-        .unwrap();
-
-    let sell_token_amount_per_interval = offer.sell_token_end_amount
-        .checked_sub(offer.sell_token_start_amount)
-        .unwrap()
-        .checked_div(number_of_intervals)
-        .unwrap();
-
-    let sell_token_current_amount = offer.sell_token_start_amount
-        .checked_add(sell_token_amount_per_interval.checked_mul(current_interval + 1).unwrap())
-        .unwrap();
-
-    Ok(sell_token_current_amount)
-}
-
 /// Takes an offer with one buy token.
 ///
 /// Allows a user to exchange their sell tokens for buy tokens from the offer.
 /// The amount of buy token received depends on the `sell_token_amount` provided by the user
 /// and the current price determined by the offer's dynamic pricing parameters
 /// (`sell_token_start_amount`, `sell_token_end_amount`, `offer_start_time`, `offer_end_time`, `price_fix_duration`).
+/// The protocol fee is deducted from `sell_token_amount` and sent to the fee vault, the remainder
+/// pays for the buy tokens. The referral fee credited to the referrer account, if one is passed,
+/// comes out of the offer's proceeds and is sent to the referral vault. The buy tokens stay
+/// escrowed in the offer until `claim_receipt` if the offer has a minimum raise, and
+/// are locked in the user's vesting escrow until `claim_vested` if the offer has a vesting schedule.
 /// Transfers tokens between accounts and emits an `OfferTakenOne` event.
 ///
 /// # Arguments
@@ -160,27 +180,111 @@ fn calculate_current_sell_amount(
 /// - `sell_token_amount`: Amount of sell tokens the user provides to exchange for buy tokens.
 ///
 /// # Errors
-/// - [`TakeOfferErrorCode::InvalidCurrentTime`] if the offer is not active.
+/// - `PricingErrorCode::InvalidCurrentTime` if the offer is not active.
+/// - `NavOracleErrorCode` errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+/// - `PauseErrorCode` errors if the program or the offer is paused.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+/// - `MinRaiseErrorCode::CalculationOverflow` if the take of a `min_raise` offer overflows its escrow.
+/// - `VestingErrorCode` errors if the offer vests and the vesting escrow or its token accounts are missing or invalid.
+/// - `KycErrorCode` errors if the offer requires KYC and the user's record is missing, insufficient or expired.
+/// - `OfferLimitsErrorCode` errors if the take violates the offer's per take, per user or raise limits.
+/// - `ProtocolFeeErrorCode` errors if a fee is due and the fee vault is missing or invalid.
+/// - `ReferralErrorCode` errors if the referrer account or the referral vault is invalid.
+/// - `DutchAuctionErrorCode::AuctionAlreadySettled` if the offer is in uniform price mode and was settled.
 /// - [`TakeOfferErrorCode::InsufficientOfferTokenOneBalance`] if the offer lacks sufficient buy tokens to fulfill the exchange at the current price.
-/// - [`TakeOfferErrorCode::CalculationOverflow`] if intermediate amount calculations overflow.
-/// - [`TakeOfferErrorCode::ZeroBuyTokenAmount`] if the calculated buy token amount to be received is zero.
+/// - `PricingErrorCode::CalculationOverflow` if amount calculations overflow.
+/// - `PricingErrorCode::ZeroBuyTokenAmount` if the calculated buy token amount to be received is zero.
 pub fn take_offer_one(ctx: Context<TakeOfferOne>, sell_token_amount: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.state, &ctx.accounts.offer)?;
+    check_not_subscription(&ctx.accounts.offer)?;
+
+    record_purchase(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        ctx.accounts.user.key(),
+        ctx.bumps.position,
+        sell_token_amount,
+    )?;
+
     let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
 
-    let current_sell_token_amount = calculate_current_sell_amount(&offer).unwrap();
+    verify_kyc(
+        offer.min_kyc_level,
+        ctx.accounts.kyc_record.as_deref(),
+        ctx.accounts.user.key(),
+        current_time,
+    )?;
+
+    let fee_amount = calculate_fee(&ctx.accounts.state, offer, sell_token_amount)?;
+    verify_fee_vault(
+        ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.key()),
+        &offer.sell_token_mint,
+        fee_amount,
+    )?;
+    let referral_fee_amount = accrue_referral_fee(
+        offer,
+        ctx.accounts.referrer_account.as_deref_mut(),
+        ctx.accounts.referral_vault_token_account.as_ref().map(|account| account.key()),
+        &ctx.accounts.user.key(),
+        sell_token_amount,
+    )?;
+    let priced_sell_token_amount = sell_token_amount - fee_amount;
+    let net_sell_token_amount = priced_sell_token_amount - referral_fee_amount;
+
+    let current_sell_token_amount =
+        calculate_current_sell_amount(offer, ctx.accounts.nav_oracle.as_deref(), current_time)?;
     msg!("Calculated current sell token amount: {}", current_sell_token_amount);
 
     let buy_token_1_amount = calculate_buy_amount(
-        sell_token_amount,
+        priced_sell_token_amount,
         offer.buy_token_1.amount,
         current_sell_token_amount,
     )?;
-    
+
     msg!("Calculated buy token 1 amount: {}", buy_token_1_amount);
+    let available_buy_token_1_amount = ctx
+        .accounts
+        .offer_buy_token_1_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_1_total)
+        .ok_or(TakeOfferErrorCode::InsufficientOfferTokenOneBalance)?;
     require!(
-        ctx.accounts.offer_buy_token_1_account.amount >= buy_token_1_amount,
+        available_buy_token_1_amount >= buy_token_1_amount,
         TakeOfferErrorCode::InsufficientOfferTokenOneBalance
     );
+    record_auction_fill(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        priced_sell_token_amount,
+        buy_token_1_amount,
+        current_sell_token_amount,
+    )?;
+    let escrowed = escrow_take(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        net_sell_token_amount,
+        buy_token_1_amount,
+        0,
+    )?;
+    let offer_key = ctx.accounts.offer.key();
+    let vesting_escrow = lock_vested_take(
+        &ctx.accounts.offer,
+        offer_key,
+        ctx.accounts.user.key(),
+        ctx.accounts.vesting_escrow.as_deref_mut(),
+        buy_token_1_amount,
+        0,
+    )?;
+    let offer = &ctx.accounts.offer;
+    let buy_token_1_destination = match &vesting_escrow {
+        Some(vesting_escrow) => verify_vesting_token_account(
+            ctx.accounts.vesting_buy_token_1_account.as_ref().map(|account| account.to_account_info()),
+            vesting_escrow,
+            &offer.buy_token_1.mint,
+        )?,
+        None => ctx.accounts.user_buy_token_1_account.to_account_info(),
+    };
 
     token::transfer(
         CpiContext::new(
@@ -191,9 +295,43 @@ pub fn take_offer_one(ctx: Context<TakeOfferOne>, sell_token_amount: u64) -> Res
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        sell_token_amount,
+        net_sell_token_amount,
     )?;
-    msg!("Transferring {} sell tokens from user to offer", sell_token_amount);
+    msg!("Transferring {} sell tokens from user to offer", net_sell_token_amount);
+
+    if fee_amount > 0 {
+        if let Some(fee_vault_token_account) = &ctx.accounts.fee_vault_token_account {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_sell_token_account.to_account_info(),
+                        to: fee_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                fee_amount,
+            )?;
+            msg!("Transferring {} sell tokens from user to fee vault", fee_amount);
+        }
+    }
+
+    if referral_fee_amount > 0 {
+        if let Some(referral_vault_token_account) = &ctx.accounts.referral_vault_token_account {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_sell_token_account.to_account_info(),
+                        to: referral_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                referral_fee_amount,
+            )?;
+            msg!("Transferring {} sell tokens from user to referral vault", referral_fee_amount);
+        }
+    }
 
     let offer_id_bytes = &offer.offer_id.to_le_bytes();
     let seeds = &[
@@ -202,25 +340,41 @@ pub fn take_offer_one(ctx: Context<TakeOfferOne>, sell_token_amount: u64) -> Res
         &[offer.authority_bump],
     ];
     let signer_seeds = &[&seeds[..]];
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
-                to: ctx.accounts.user_buy_token_1_account.to_account_info(),
-                authority: ctx.accounts.offer_token_authority.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        buy_token_1_amount,
-    )?;
-    msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
+    if escrowed {
+        msg!("Escrowing {} buy tokens 1 until the offer ends", buy_token_1_amount);
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: buy_token_1_destination,
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        if vesting_escrow.is_some() {
+            msg!("Transferring {} buy tokens 1 from offer to vesting escrow", buy_token_1_amount);
+        } else {
+            msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
+        }
+    }
     emit!(OfferTakenOne {
         offer_id: offer.offer_id,
         user: ctx.accounts.user.key(),
         sell_token_amount,
         buy_token_1_amount,
-        remaining_buy_token_amount: ctx.accounts.offer_buy_token_1_account.amount - buy_token_1_amount
+        remaining_buy_token_amount: ctx
+            .accounts
+            .offer_buy_token_1_account
+            .amount
+            .checked_sub(buy_token_1_amount)
+            .ok_or(TakeOfferErrorCode::InsufficientOfferTokenOneBalance)?,
+        fee_amount,
+        referrer: ctx.accounts.referrer_account.as_ref().map(|account| account.referrer),
+        referral_fee_amount,
     });
 
     Ok(())
@@ -239,6 +393,7 @@ pub fn take_offer_one(ctx: Context<TakeOfferOne>, sell_token_amount: u64) -> Res
 pub struct TakeOfferTwo<'info> {
     /// The offer account being taken, providing offer details.
     #[account(
+        mut,
         constraint = offer.buy_token_2.mint != Pubkey::default() @ TakeOfferErrorCode::InvalidTakeOffer
     )]
     pub offer: Account<'info, Offer>,
@@ -307,13 +462,58 @@ pub struct TakeOfferTwo<'info> {
   )]
     pub offer_token_authority: AccountInfo<'info>,
 
-    /// The user taking the offer, signs the transaction.
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// NAV oracle referenced by the offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// KYC record of `user`, required only for offers with a minimum KYC level.
+    pub kyc_record: Option<Box<Account<'info, KycRecord>>>,
+
+    /// Fee vault of the sell token, required only when a protocol fee is due.
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Referrer account of the take's referrer, credited with the offer's referral fee.
+    #[account(mut)]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
+    /// Referral vault of the sell token, required only when a referral fee is due.
+    #[account(mut)]
+    pub referral_vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The user's vesting escrow, required only for offers with a vesting schedule.
+    #[account(mut)]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    /// Vesting escrow's buy token 1 ATA, receives the locked buy token 1 of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_1_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Vesting escrow's buy token 2 ATA, receives the locked buy token 2 of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The user's position in the offer, created on the first take with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", offer.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// The user taking the offer, signs the transaction and pays for the position account.
+    #[account(mut)]
     pub user: Signer<'info>,
 
     /// SPL Token program for token operations.
     pub token_program: Program<'info, Token>,
 
-    /// Solana System program, included for potential rent accounting.
+    /// Solana System program for the position account creation.
     pub system_program: Program<'info, System>,
 }
 
@@ -323,6 +523,11 @@ pub struct TakeOfferTwo<'info> {
 /// The amount of each buy token received depends on the `sell_token_amount` provided by the user
 /// and the current price determined by the offer's dynamic pricing parameters
 /// (`sell_token_start_amount`, `sell_token_end_amount`, `offer_start_time`, `offer_end_time`, `price_fix_duration`).
+/// The protocol fee is deducted from `sell_token_amount` and sent to the fee vault, the remainder
+/// pays for the buy tokens. The referral fee credited to the referrer account, if one is passed,
+/// comes out of the offer's proceeds and is sent to the referral vault. The buy tokens stay
+/// escrowed in the offer until `claim_receipt` if the offer has a minimum raise, and
+/// are locked in the user's vesting escrow until `claim_vested` if the offer has a vesting schedule.
 /// Transfers tokens between accounts and emits an `OfferTakenTwo` event.
 ///
 /// # Arguments
@@ -330,38 +535,138 @@ pub struct TakeOfferTwo<'info> {
 /// - `sell_token_amount`: Amount of sell tokens the user provides to exchange for buy tokens.
 ///
 /// # Errors
-/// - [`TakeOfferErrorCode::InvalidCurrentTime`] if the offer is not active.
+/// - `PricingErrorCode::InvalidCurrentTime` if the offer is not active.
+/// - `NavOracleErrorCode` errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+/// - `PauseErrorCode` errors if the program or the offer is paused.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+/// - `MinRaiseErrorCode::CalculationOverflow` if the take of a `min_raise` offer overflows its escrow.
+/// - `VestingErrorCode` errors if the offer vests and the vesting escrow or its token accounts are missing or invalid.
+/// - `KycErrorCode` errors if the offer requires KYC and the user's record is missing, insufficient or expired.
+/// - `OfferLimitsErrorCode` errors if the take violates the offer's per take, per user or raise limits.
+/// - `ProtocolFeeErrorCode` errors if a fee is due and the fee vault is missing or invalid.
+/// - `ReferralErrorCode` errors if the referrer account or the referral vault is invalid.
+/// - `DutchAuctionErrorCode::AuctionAlreadySettled` if the offer is in uniform price mode and was settled.
 /// - [`TakeOfferErrorCode::InsufficientOfferTokenOneBalance`] if the offer lacks sufficient quantity of buy token 1 to fulfill the exchange at the current price.
 /// - [`TakeOfferErrorCode::InsufficientOfferTokenTwoBalance`] if the offer lacks sufficient quantity of buy token 2 to fulfill the exchange at the current price.
-/// - [`TakeOfferErrorCode::CalculationOverflow`] if intermediate amount calculations overflow.
-/// - [`TakeOfferErrorCode::ZeroBuyTokenAmount`] if the calculated amount for either buy token to be received is zero.
+/// - `PricingErrorCode::CalculationOverflow` if amount calculations overflow.
+/// - `PricingErrorCode::ZeroBuyTokenAmount` if the calculated amount for either buy token to be received is zero.
 pub fn take_offer_two(ctx: Context<TakeOfferTwo>, sell_token_amount: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.state, &ctx.accounts.offer)?;
+    check_not_subscription(&ctx.accounts.offer)?;
+
+    record_purchase(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        ctx.accounts.user.key(),
+        ctx.bumps.position,
+        sell_token_amount,
+    )?;
+
     let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    verify_kyc(
+        offer.min_kyc_level,
+        ctx.accounts.kyc_record.as_deref(),
+        ctx.accounts.user.key(),
+        current_time,
+    )?;
+
+    let fee_amount = calculate_fee(&ctx.accounts.state, offer, sell_token_amount)?;
+    verify_fee_vault(
+        ctx.accounts.fee_vault_token_account.as_ref().map(|account| account.key()),
+        &offer.sell_token_mint,
+        fee_amount,
+    )?;
+    let referral_fee_amount = accrue_referral_fee(
+        offer,
+        ctx.accounts.referrer_account.as_deref_mut(),
+        ctx.accounts.referral_vault_token_account.as_ref().map(|account| account.key()),
+        &ctx.accounts.user.key(),
+        sell_token_amount,
+    )?;
+    let priced_sell_token_amount = sell_token_amount - fee_amount;
+    let net_sell_token_amount = priced_sell_token_amount - referral_fee_amount;
 
-    let current_sell_token_amount = calculate_current_sell_amount(&offer).unwrap();
+    let current_sell_token_amount =
+        calculate_current_sell_amount(offer, ctx.accounts.nav_oracle.as_deref(), current_time)?;
     msg!("Calculated current sell token amount: {}", current_sell_token_amount);
 
     let buy_token_1_amount = calculate_buy_amount(
-        sell_token_amount,
+        priced_sell_token_amount,
         offer.buy_token_1.amount,
         current_sell_token_amount,
     )?;
     msg!("Calculated buy token 1 amount: {}", buy_token_1_amount);
+    let available_buy_token_1_amount = ctx
+        .accounts
+        .offer_buy_token_1_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_1_total)
+        .ok_or(TakeOfferErrorCode::InsufficientOfferTokenOneBalance)?;
     require!(
-        ctx.accounts.offer_buy_token_1_account.amount >= buy_token_1_amount,
+        available_buy_token_1_amount >= buy_token_1_amount,
         TakeOfferErrorCode::InsufficientOfferTokenOneBalance
     );
 
     let buy_token_2_amount = calculate_buy_amount(
-        sell_token_amount,
+        priced_sell_token_amount,
         offer.buy_token_2.amount,
         current_sell_token_amount,
     )?;
     msg!("Calculated buy token 2 amount: {}", buy_token_2_amount);
+    let available_buy_token_2_amount = ctx
+        .accounts
+        .offer_buy_token_2_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_2_total)
+        .ok_or(TakeOfferErrorCode::InsufficientOfferTokenTwoBalance)?;
     require!(
-        ctx.accounts.offer_buy_token_2_account.amount >= buy_token_2_amount,
+        available_buy_token_2_amount >= buy_token_2_amount,
         TakeOfferErrorCode::InsufficientOfferTokenTwoBalance
     );
+    record_auction_fill(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        priced_sell_token_amount,
+        buy_token_1_amount,
+        current_sell_token_amount,
+    )?;
+    let escrowed = escrow_take(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        net_sell_token_amount,
+        buy_token_1_amount,
+        buy_token_2_amount,
+    )?;
+    let offer_key = ctx.accounts.offer.key();
+    let vesting_escrow = lock_vested_take(
+        &ctx.accounts.offer,
+        offer_key,
+        ctx.accounts.user.key(),
+        ctx.accounts.vesting_escrow.as_deref_mut(),
+        buy_token_1_amount,
+        buy_token_2_amount,
+    )?;
+    let offer = &ctx.accounts.offer;
+    let (buy_token_1_destination, buy_token_2_destination) = match &vesting_escrow {
+        Some(vesting_escrow) => (
+            verify_vesting_token_account(
+                ctx.accounts.vesting_buy_token_1_account.as_ref().map(|account| account.to_account_info()),
+                vesting_escrow,
+                &offer.buy_token_1.mint,
+            )?,
+            verify_vesting_token_account(
+                ctx.accounts.vesting_buy_token_2_account.as_ref().map(|account| account.to_account_info()),
+                vesting_escrow,
+                &offer.buy_token_2.mint,
+            )?,
+        ),
+        None => (
+            ctx.accounts.user_buy_token_1_account.to_account_info(),
+            ctx.accounts.user_buy_token_2_account.to_account_info(),
+        ),
+    };
 
     token::transfer(
         CpiContext::new(
@@ -372,9 +677,43 @@ pub fn take_offer_two(ctx: Context<TakeOfferTwo>, sell_token_amount: u64) -> Res
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        sell_token_amount,
+        net_sell_token_amount,
     )?;
-    msg!("Transferring {} sell tokens from user to offer", sell_token_amount);
+    msg!("Transferring {} sell tokens from user to offer", net_sell_token_amount);
+
+    if fee_amount > 0 {
+        if let Some(fee_vault_token_account) = &ctx.accounts.fee_vault_token_account {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_sell_token_account.to_account_info(),
+                        to: fee_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                fee_amount,
+            )?;
+            msg!("Transferring {} sell tokens from user to fee vault", fee_amount);
+        }
+    }
+
+    if referral_fee_amount > 0 {
+        if let Some(referral_vault_token_account) = &ctx.accounts.referral_vault_token_account {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_sell_token_account.to_account_info(),
+                        to: referral_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                referral_fee_amount,
+            )?;
+            msg!("Transferring {} sell tokens from user to referral vault", referral_fee_amount);
+        }
+    }
 
     let offer_id_bytes = &offer.offer_id.to_le_bytes();
     let seeds = &[
@@ -383,32 +722,45 @@ pub fn take_offer_two(ctx: Context<TakeOfferTwo>, sell_token_amount: u64) -> Res
         &[offer.authority_bump],
     ];
     let signer_seeds = &[&seeds[..]];
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
-                to: ctx.accounts.user_buy_token_1_account.to_account_info(),
-                authority: ctx.accounts.offer_token_authority.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        buy_token_1_amount,
-    )?;
-    msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.offer_buy_token_2_account.to_account_info(),
-                to: ctx.accounts.user_buy_token_2_account.to_account_info(),
-                authority: ctx.accounts.offer_token_authority.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        buy_token_2_amount,
-    )?;
-    msg!("Transferring {} buy tokens 2 from offer to user", buy_token_2_amount);
+    if escrowed {
+        msg!("Escrowing {} buy tokens 1 until the offer ends", buy_token_1_amount);
+        msg!("Escrowing {} buy tokens 2 until the offer ends", buy_token_2_amount);
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: buy_token_1_destination,
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        if vesting_escrow.is_some() {
+            msg!("Transferring {} buy tokens 1 from offer to vesting escrow", buy_token_1_amount);
+        } else {
+            msg!("Transferring {} buy tokens 1 from offer to user", buy_token_1_amount);
+        }
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_2_account.to_account_info(),
+                    to: buy_token_2_destination,
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_2_amount,
+        )?;
+        if vesting_escrow.is_some() {
+            msg!("Transferring {} buy tokens 2 from offer to vesting escrow", buy_token_2_amount);
+        } else {
+            msg!("Transferring {} buy tokens 2 from offer to user", buy_token_2_amount);
+        }
+    }
 
     emit!(OfferTakenTwo {
         offer_id: offer.offer_id,
@@ -416,57 +768,26 @@ pub fn take_offer_two(ctx: Context<TakeOfferTwo>, sell_token_amount: u64) -> Res
         sell_token_amount,
         buy_token_1_amount,
         buy_token_2_amount,
-        remaining_buy_token_1_amount: ctx.accounts.offer_buy_token_1_account.amount - buy_token_1_amount,
-        remaining_buy_token_2_amount: ctx.accounts.offer_buy_token_2_account.amount - buy_token_2_amount,
+        remaining_buy_token_1_amount: ctx
+            .accounts
+            .offer_buy_token_1_account
+            .amount
+            .checked_sub(buy_token_1_amount)
+            .ok_or(TakeOfferErrorCode::InsufficientOfferTokenOneBalance)?,
+        remaining_buy_token_2_amount: ctx
+            .accounts
+            .offer_buy_token_2_account
+            .amount
+            .checked_sub(buy_token_2_amount)
+            .ok_or(TakeOfferErrorCode::InsufficientOfferTokenTwoBalance)?,
+        fee_amount,
+        referrer: ctx.accounts.referrer_account.as_ref().map(|account| account.referrer),
+        referral_fee_amount,
     });
 
     Ok(())
 }
 
-/// Calculates the proportional amount of a specific buy token a user receives for their sell tokens.
-///
-/// This function determines how many units of a particular buy token the user gets
-/// based on the amount of sell tokens they provide, the total amount of that buy token
-/// available in the offer, and the current effective total sell token amount required for the offer
-/// (as determined by `calculate_current_sell_amount`).
-///
-/// Essentially, `(user_sell_token_amount / offer_sell_token_amount_at_current_price) * offer_total_buy_token_X_amount`.
-///
-/// # Arguments
-/// - `user_sell_token_amount`: Amount of sell tokens provided by the user.
-/// - `offer_buy_token_amount`: Total amount of the specific buy token set in the offer (e.g., `offer.buy_token_1.amount`).
-/// - `offer_sell_token_amount`: Current effective total sell token amount for the offer at the current time interval,
-///   calculated by `calculate_current_sell_amount`.
-///
-/// # Returns
-/// The calculated amount of the specific buy token to transfer to the user, or an error if the calculation fails.
-///
-/// # Errors
-/// - [`TakeOfferErrorCode::InvalidSellTokenMint`] if `offer_sell_token_amount` (the denominator) is zero.
-/// - [`TakeOfferErrorCode::CalculationOverflow`] if multiplication or division results in overflow.
-/// - [`TakeOfferErrorCode::ZeroBuyTokenAmount`] if the calculated buy token amount for the user is zero.
-fn calculate_buy_amount(
-    user_sell_token_amount: u64,
-    offer_buy_token_amount: u64,
-    offer_sell_token_amount: u64,
-) -> Result<u64> {
-    if offer_sell_token_amount == 0 {
-        return Err(error!(TakeOfferErrorCode::InvalidSellTokenMint).into());
-    }
-    let result = (user_sell_token_amount as u128)
-        .checked_mul(offer_buy_token_amount as u128)
-        .ok_or(TakeOfferErrorCode::CalculationOverflow)?
-        .checked_div(offer_sell_token_amount as u128)
-        .ok_or(TakeOfferErrorCode::CalculationOverflow)?;
-    if result > u64::MAX as u128 {
-        return Err(error!(TakeOfferErrorCode::CalculationOverflow));
-    }
-    if result == 0 {
-        return Err(error!(TakeOfferErrorCode::ZeroBuyTokenAmount));
-    }
-    Ok(result as u64)
-}
-
 /// Error codes for offer taking operations.
 #[error_code]
 pub enum TakeOfferErrorCode {
@@ -488,16 +809,4 @@ pub enum TakeOfferErrorCode {
     /// Triggered when the offer type is invalid for the take instruction.
     #[msg("The offer is of 2 buy token type.")]
     InvalidTakeOffer,
-
-    /// Triggered when buy amount calculations overflow or are invalid.
-    #[msg("Calculation overflowed or invalid.")]
-    CalculationOverflow,
-
-    /// Triggered when the calculated buy token amount is zero.
-    #[msg("Zero buy token amount.")]
-    ZeroBuyTokenAmount,
-
-    /// Triggered when the current time is outside the offer's time range.
-    #[msg("Current time must be within the offer's start and end time range.")]
-    InvalidCurrentTime,
 }
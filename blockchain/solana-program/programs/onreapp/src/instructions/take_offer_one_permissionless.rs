@@ -1,4 +1,17 @@
-use crate::state::Offer;
+use crate::instructions::dutch_auction::record_auction_fill;
+use crate::instructions::kyc::verify_kyc;
+use crate::instructions::min_raise::escrow_take;
+use crate::instructions::offer_limits::record_purchase;
+use crate::instructions::pause::check_not_paused;
+use crate::instructions::pricing::{calculate_buy_amount, calculate_current_sell_amount};
+use crate::instructions::protocol_fee::{calculate_fee, verify_fee_vault};
+use crate::instructions::referral::accrue_referral_fee;
+use crate::instructions::subscription::check_not_subscription;
+use crate::instructions::vesting::{lock_vested_take, verify_vesting_token_account};
+use crate::instructions::permissionless_account::PermissionlessAccountErrorCode;
+use crate::state::{
+    KycRecord, NavOracle, Offer, PermissionlessAccount, Position, ReferrerAccount, State, VestingEscrow,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, Transfer};
@@ -13,6 +26,12 @@ pub struct OfferTakenOnePermissionless {
     pub buy_token_1_amount: u64,
     pub remaining_buy_token_amount: u64,
     pub intermediary_account: Pubkey,
+    /// Part of `sell_token_amount` sent to the fee vault.
+    pub fee_amount: u64,
+    /// The referrer of the take, if any.
+    pub referrer: Option<Pubkey>,
+    /// Part of `sell_token_amount` credited to the referrer.
+    pub referral_fee_amount: u64,
 }
 
 /// Account structure for taking an offer with one buy token via permissionless route.
@@ -24,16 +43,19 @@ pub struct OfferTakenOnePermissionless {
 /// 1. User provides sell tokens to intermediary account
 /// 2. Intermediary account transfers sell tokens to offer
 /// 3. Offer transfers buy tokens to intermediary account (program-controlled)
-/// 4. Intermediary account transfers buy tokens to user
+/// 4. Intermediary account transfers buy tokens to user, or to their vesting escrow for vesting offers
 ///
 /// # Preconditions
 /// - All user ATAs must be initialized prior to execution
 /// - Offer must have sufficient buy tokens available
+/// - The permissionless account `intermediary_index` must be initialized and enabled
 #[derive(Accounts)]
+#[instruction(intermediary_index: u64)]
 pub struct TakeOfferOnePermissionless<'info> {
     /// The offer account being taken, providing offer details.
     /// Ensures this is a single buy token offer by checking `buy_token_mint_2`.
     #[account(
+        mut,
         constraint = offer.buy_token_2.mint == Pubkey::default() @ TakeOfferPermissionlessErrorCode::InvalidTakeOffer
     )]
     pub offer: Box<Account<'info, Offer>>,
@@ -108,14 +130,54 @@ pub struct TakeOfferOnePermissionless<'info> {
     )]
     pub offer_token_authority: AccountInfo<'info>,
 
-    /// Derived PDA for intermediary authority, controls the intermediary token account.
-    /// Uses a unique seed to avoid conflicts with other PDAs.
-    /// CHECK: This account is validated by the seed derivation.
+    /// Permissionless account the take routes through, controls the intermediary token accounts.
+    /// Ensures the routing channel is enabled.
     #[account(
-        seeds = [b"permissionless-1"],
+        seeds = [b"permissionless", intermediary_index.to_le_bytes().as_ref()],
+        bump = intermediary_authority.bump,
+        constraint = intermediary_authority.enabled @ PermissionlessAccountErrorCode::PermissionlessAccountDisabled
+    )]
+    pub intermediary_authority: Box<Account<'info, PermissionlessAccount>>,
+
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// NAV oracle referenced by the offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// KYC record of `user`, required only for offers with a minimum KYC level.
+    pub kyc_record: Option<Box<Account<'info, KycRecord>>>,
+
+    /// Fee vault of the sell token, required only when a protocol fee is due.
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// Referrer account of the take's referrer, credited with the offer's referral fee.
+    #[account(mut)]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
+    /// Referral vault of the sell token, required only when a referral fee is due.
+    #[account(mut)]
+    pub referral_vault_token_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// The user's vesting escrow, required only for offers with a vesting schedule.
+    #[account(mut)]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    /// Vesting escrow's buy token 1 ATA, receives the locked buy tokens of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_1_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// The user's position in the offer, created on the first take with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", offer.key().as_ref(), user.key().as_ref()],
         bump
     )]
-    pub intermediary_authority: AccountInfo<'info>,
+    pub position: Box<Account<'info, Position>>,
 
     /// The user taking the offer, signs the transaction and pays for account creation.
     #[account(mut)]
@@ -131,147 +193,144 @@ pub struct TakeOfferOnePermissionless<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Calculates the current sell token amount based on the offer's dynamic pricing model.
-///
-/// The price of the sell token (how much is required per buy token) changes linearly over the offer's duration.
-/// The offer is divided into intervals, each lasting `price_fix_duration` seconds.
-/// The sell token amount starts at `sell_token_start_amount` + `one_interval_amount` at the beginning of the first interval
-/// and progresses towards `sell_token_end_amount` by the end of the last interval.
-///
-/// # Arguments
-/// - `offer`: A reference to the `Offer` account containing pricing parameters like
-///   `offer_start_time`, `offer_end_time`, `price_fix_duration`,
-///   `sell_token_start_amount`, and `sell_token_end_amount`.
-///
-/// # Returns
-/// The calculated sell token amount effectively representing the "price" for the current interval.
-/// This is the amount of sell tokens that corresponds to the total `buy_token_X_amount` defined in the offer for the current time interval.
-///
-/// # Errors
-/// - [`TakeOfferPermissionlessErrorCode::InvalidCurrentTime`] if the current time is outside the offer's active period.
-fn calculate_current_sell_amount(offer: &Offer) -> Result<u64> {
-    let current_time = Clock::get()?.unix_timestamp as u64;
-
-    require!(
-        current_time >= offer.offer_start_time && current_time < offer.offer_end_time,
-        TakeOfferPermissionlessErrorCode::InvalidCurrentTime
-    );
-
-    let total_duration = offer
-        .offer_end_time
-        .checked_sub(offer.offer_start_time)
-        .unwrap();
-    let number_of_intervals = total_duration
-        .checked_div(offer.price_fix_duration)
-        .unwrap();
-    let current_interval = current_time
-        .checked_sub(offer.offer_start_time)
-        .unwrap()
-        .checked_div(offer.price_fix_duration)
-        .unwrap();
-
-    let sell_token_amount_per_interval = offer
-        .sell_token_end_amount
-        .checked_sub(offer.sell_token_start_amount)
-        .unwrap()
-        .checked_div(number_of_intervals)
-        .unwrap();
-
-    let sell_token_current_amount = offer
-        .sell_token_start_amount
-        .checked_add(
-            sell_token_amount_per_interval
-                .checked_mul(current_interval + 1)
-                .unwrap(),
-        )
-        .unwrap();
-
-    Ok(sell_token_current_amount)
-}
-
-/// Calculates the proportional amount of a specific buy token a user receives for their sell tokens.
-///
-/// This function determines how many units of a particular buy token the user gets
-/// based on the amount of sell tokens they provide, the total amount of that buy token
-/// available in the offer, and the current effective total sell token amount required for the offer
-/// (as determined by `calculate_current_sell_amount`).
-///
-/// Essentially, `(user_sell_token_amount / offer_sell_token_amount_at_current_price) * offer_total_buy_token_X_amount`.
-///
-/// # Arguments
-/// - `user_sell_token_amount`: Amount of sell tokens provided by the user.
-/// - `offer_buy_token_amount`: Total amount of the specific buy token set in the offer (e.g., `offer.buy_token_1.amount`).
-/// - `offer_sell_token_amount`: Current effective total sell token amount for the offer at the current time interval,
-///   calculated by `calculate_current_sell_amount`.
-///
-/// # Returns
-/// The calculated amount of the specific buy token to transfer to the user, or an error if the calculation fails.
-///
-/// # Errors
-/// - [`TakeOfferPermissionlessErrorCode::InvalidSellTokenMint`] if `offer_sell_token_amount` (the denominator) is zero.
-/// - [`TakeOfferPermissionlessErrorCode::CalculationOverflow`] if multiplication or division results in overflow.
-/// - [`TakeOfferPermissionlessErrorCode::ZeroBuyTokenAmount`] if the calculated buy token amount for the user is zero.
-fn calculate_buy_amount(
-    user_sell_token_amount: u64,
-    offer_buy_token_amount: u64,
-    offer_sell_token_amount: u64,
-) -> Result<u64> {
-    if offer_sell_token_amount == 0 {
-        return Err(error!(TakeOfferPermissionlessErrorCode::InvalidSellTokenMint).into());
-    }
-    let result = (user_sell_token_amount as u128)
-        .checked_mul(offer_buy_token_amount as u128)
-        .ok_or(TakeOfferPermissionlessErrorCode::CalculationOverflow)?
-        .checked_div(offer_sell_token_amount as u128)
-        .ok_or(TakeOfferPermissionlessErrorCode::CalculationOverflow)?;
-    if result > u64::MAX as u128 {
-        return Err(error!(
-            TakeOfferPermissionlessErrorCode::CalculationOverflow
-        ));
-    }
-    if result == 0 {
-        return Err(error!(TakeOfferPermissionlessErrorCode::ZeroBuyTokenAmount));
-    }
-    Ok(result as u64)
-}
-
 /// Takes an offer with one buy token via permissionless route.
 ///
 /// Uses an intermediary account to route tokens through it. The intermediary account persists.
 /// This provides an additional layer of indirection while maintaining the same economic outcome.
+/// The protocol fee is deducted from `sell_token_amount` and, if a referrer account is passed, the
+/// referral fee from the offer's proceeds, both sent from the intermediary account to the fee and
+/// referral vaults.
+/// If the offer has a minimum raise, the buy tokens stay escrowed in the offer until `claim_receipt`,
+/// and if it has a vesting schedule, they are locked in the user's vesting escrow until `claim_vested`.
 ///
 /// # Arguments
 /// - `ctx`: Context containing the accounts for the offer take.
+/// - `intermediary_index`: Index of the permissionless account the take routes through.
 /// - `sell_token_amount`: Amount of sell tokens the user provides to exchange for buy tokens.
 ///
 /// # Errors
-/// - [`TakeOfferPermissionlessErrorCode::InvalidCurrentTime`] if the offer is not active.
+/// - `PermissionlessAccountErrorCode::PermissionlessAccountDisabled` if the permissionless account is disabled.
+/// - `PricingErrorCode::InvalidCurrentTime` if the offer is not active.
+/// - `NavOracleErrorCode` errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+/// - `PauseErrorCode` errors if the program or the offer is paused.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+/// - `MinRaiseErrorCode::CalculationOverflow` if the take of a `min_raise` offer overflows its escrow.
+/// - `VestingErrorCode` errors if the offer vests and the vesting escrow or its token account are missing or invalid.
+/// - `KycErrorCode` errors if the offer requires KYC and the user's record is missing, insufficient or expired.
+/// - `OfferLimitsErrorCode` errors if the take violates the offer's per take, per user or raise limits.
+/// - `ProtocolFeeErrorCode` errors if a fee is due and the fee vault is missing or invalid.
+/// - `ReferralErrorCode` errors if the referrer account or the referral vault is invalid.
+/// - `DutchAuctionErrorCode::AuctionAlreadySettled` if the offer is in uniform price mode and was settled.
 /// - [`TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance`] if the offer lacks sufficient buy tokens.
-/// - [`TakeOfferPermissionlessErrorCode::CalculationOverflow`] if amount calculations overflow.
-/// - [`TakeOfferPermissionlessErrorCode::ZeroBuyTokenAmount`] if the calculated buy token amount is zero.
+/// - `PricingErrorCode::CalculationOverflow` if amount calculations overflow.
+/// - `PricingErrorCode::ZeroBuyTokenAmount` if the calculated buy token amount is zero.
 pub fn take_offer_one_permissionless(
     ctx: Context<TakeOfferOnePermissionless>,
+    intermediary_index: u64,
     sell_token_amount: u64,
 ) -> Result<()> {
+    check_not_paused(&ctx.accounts.state, &ctx.accounts.offer)?;
+    check_not_subscription(&ctx.accounts.offer)?;
+
+    record_purchase(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        ctx.accounts.user.key(),
+        ctx.bumps.position,
+        sell_token_amount,
+    )?;
+
     let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    verify_kyc(
+        offer.min_kyc_level,
+        ctx.accounts.kyc_record.as_deref(),
+        ctx.accounts.user.key(),
+        current_time,
+    )?;
+
+    let fee_amount = calculate_fee(&ctx.accounts.state, offer, sell_token_amount)?;
+    verify_fee_vault(
+        ctx.accounts
+            .fee_vault_token_account
+            .as_ref()
+            .map(|account| account.key()),
+        &offer.sell_token_mint,
+        fee_amount,
+    )?;
+    let referral_fee_amount = accrue_referral_fee(
+        offer,
+        ctx.accounts.referrer_account.as_deref_mut(),
+        ctx.accounts
+            .referral_vault_token_account
+            .as_ref()
+            .map(|account| account.key()),
+        &ctx.accounts.user.key(),
+        sell_token_amount,
+    )?;
+    let priced_sell_token_amount = sell_token_amount - fee_amount;
+    let net_sell_token_amount = priced_sell_token_amount - referral_fee_amount;
 
-    let current_sell_token_amount = calculate_current_sell_amount(&offer).unwrap();
+    let current_sell_token_amount =
+        calculate_current_sell_amount(offer, ctx.accounts.nav_oracle.as_deref(), current_time)?;
     msg!(
         "Calculated current sell token amount: {}",
         current_sell_token_amount
     );
 
     let buy_token_1_amount = calculate_buy_amount(
-        sell_token_amount,
+        priced_sell_token_amount,
         offer.buy_token_1.amount,
         current_sell_token_amount,
     )?;
 
     msg!("Calculated buy token 1 amount: {}", buy_token_1_amount);
+    let available_buy_token_1_amount = ctx
+        .accounts
+        .offer_buy_token_1_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_1_total)
+        .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance)?;
     require!(
-        ctx.accounts.offer_buy_token_1_account.amount >= buy_token_1_amount,
+        available_buy_token_1_amount >= buy_token_1_amount,
         TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance
     );
+    record_auction_fill(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        priced_sell_token_amount,
+        buy_token_1_amount,
+        current_sell_token_amount,
+    )?;
+    let escrowed = escrow_take(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        net_sell_token_amount,
+        buy_token_1_amount,
+        0,
+    )?;
+    let offer_key = ctx.accounts.offer.key();
+    let vesting_escrow = lock_vested_take(
+        &ctx.accounts.offer,
+        offer_key,
+        ctx.accounts.user.key(),
+        ctx.accounts.vesting_escrow.as_deref_mut(),
+        buy_token_1_amount,
+        0,
+    )?;
+    let offer = &ctx.accounts.offer;
+    let buy_token_1_destination = match &vesting_escrow {
+        Some(vesting_escrow) => verify_vesting_token_account(
+            ctx.accounts
+                .vesting_buy_token_1_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            vesting_escrow,
+            &offer.buy_token_1.mint,
+        )?,
+        None => ctx.accounts.user_buy_token_1_account.to_account_info(),
+    };
 
     // Step 1: Transfer sell tokens from user to intermediary account
     token::transfer(
@@ -294,9 +353,11 @@ pub fn take_offer_one_permissionless(
     );
 
     // Step 2: Transfer sell tokens from intermediary account to offer
+    let intermediary_index_bytes = &intermediary_index.to_le_bytes();
     let intermediary_seeds = &[
-        b"permissionless-1".as_ref(),
-        &[ctx.bumps.intermediary_authority],
+        b"permissionless".as_ref(),
+        intermediary_index_bytes,
+        &[ctx.accounts.intermediary_authority.bump],
     ];
     let intermediary_signer_seeds = &[&intermediary_seeds[..]];
 
@@ -313,13 +374,61 @@ pub fn take_offer_one_permissionless(
             },
             intermediary_signer_seeds,
         ),
-        sell_token_amount,
+        net_sell_token_amount,
     )?;
     msg!(
         "Transferring {} sell tokens from intermediary to offer",
-        sell_token_amount
+        net_sell_token_amount
     );
 
+    if fee_amount > 0 {
+        if let Some(fee_vault_token_account) = &ctx.accounts.fee_vault_token_account {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx
+                            .accounts
+                            .intermediary_sell_token_account
+                            .to_account_info(),
+                        to: fee_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.intermediary_authority.to_account_info(),
+                    },
+                    intermediary_signer_seeds,
+                ),
+                fee_amount,
+            )?;
+            msg!(
+                "Transferring {} sell tokens from intermediary to fee vault",
+                fee_amount
+            );
+        }
+    }
+
+    if referral_fee_amount > 0 {
+        if let Some(referral_vault_token_account) = &ctx.accounts.referral_vault_token_account {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx
+                            .accounts
+                            .intermediary_sell_token_account
+                            .to_account_info(),
+                        to: referral_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.intermediary_authority.to_account_info(),
+                    },
+                    intermediary_signer_seeds,
+                ),
+                referral_fee_amount,
+            )?;
+            msg!(
+                "Transferring {} sell tokens from intermediary to referral vault",
+                referral_fee_amount
+            );
+        }
+    }
+
     // Step 3: Transfer buy tokens from offer to intermediary account
     let offer_id_bytes = &offer.offer_id.to_le_bytes();
     let offer_seeds = &[
@@ -329,55 +438,80 @@ pub fn take_offer_one_permissionless(
     ];
     let offer_signer_seeds = &[&offer_seeds[..]];
 
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
-                to: ctx
-                    .accounts
-                    .intermediary_buy_token_account
-                    .to_account_info(),
-                authority: ctx.accounts.offer_token_authority.to_account_info(),
-            },
-            offer_signer_seeds,
-        ),
-        buy_token_1_amount,
-    )?;
-    msg!(
-        "Transferring {} buy tokens from offer to intermediary account",
-        buy_token_1_amount
-    );
-
-    // Step 4: Transfer buy tokens from intermediary account to user
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx
-                    .accounts
-                    .intermediary_buy_token_account
-                    .to_account_info(),
-                to: ctx.accounts.user_buy_token_1_account.to_account_info(),
-                authority: ctx.accounts.intermediary_authority.to_account_info(),
-            },
-            intermediary_signer_seeds,
-        ),
-        buy_token_1_amount,
-    )?;
-    msg!(
-        "Transferring {} buy tokens from intermediary to user",
-        buy_token_1_amount
-    );
+    if escrowed {
+        msg!(
+            "Escrowing {} buy tokens until the offer ends",
+            buy_token_1_amount
+        );
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: ctx
+                        .accounts
+                        .intermediary_buy_token_account
+                        .to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                offer_signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        msg!(
+            "Transferring {} buy tokens from offer to intermediary account",
+            buy_token_1_amount
+        );
+
+        // Step 4: Transfer buy tokens from intermediary account to user, or their vesting escrow
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx
+                        .accounts
+                        .intermediary_buy_token_account
+                        .to_account_info(),
+                    to: buy_token_1_destination,
+                    authority: ctx.accounts.intermediary_authority.to_account_info(),
+                },
+                intermediary_signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        if vesting_escrow.is_some() {
+            msg!(
+                "Transferring {} buy tokens from intermediary to vesting escrow",
+                buy_token_1_amount
+            );
+        } else {
+            msg!(
+                "Transferring {} buy tokens from intermediary to user",
+                buy_token_1_amount
+            );
+        }
+    }
 
     emit!(OfferTakenOnePermissionless {
         offer_id: offer.offer_id,
         user: ctx.accounts.user.key(),
         sell_token_amount,
         buy_token_1_amount,
-        remaining_buy_token_amount: ctx.accounts.offer_buy_token_1_account.amount
-            - buy_token_1_amount,
+        remaining_buy_token_amount: ctx
+            .accounts
+            .offer_buy_token_1_account
+            .amount
+            .checked_sub(buy_token_1_amount)
+            .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance)?,
         intermediary_account: ctx.accounts.intermediary_buy_token_account.key(),
+        fee_amount,
+        referrer: ctx
+            .accounts
+            .referrer_account
+            .as_ref()
+            .map(|account| account.referrer),
+        referral_fee_amount,
     });
 
     Ok(())
@@ -402,15 +536,7 @@ pub enum TakeOfferPermissionlessErrorCode {
     #[msg("The offer is of 2 buy token type.")]
     InvalidTakeOffer,
 
-    /// Triggered when buy amount calculations overflow or are invalid.
-    #[msg("Calculation overflowed or invalid.")]
-    CalculationOverflow,
-
-    /// Triggered when the calculated buy token amount is zero.
-    #[msg("Zero buy token amount.")]
-    ZeroBuyTokenAmount,
-
-    /// Triggered when the current time is outside the offer's time range.
-    #[msg("Current time must be within the offer's start and end time range.")]
-    InvalidCurrentTime,
+    /// Triggered when the offer lacks sufficient buy token 2 to fulfill the take.
+    #[msg("Insufficient tokens remaining in the offer for token 2.")]
+    InsufficientOfferTokenTwoBalance,
 }
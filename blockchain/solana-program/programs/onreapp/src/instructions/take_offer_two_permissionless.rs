@@ -0,0 +1,635 @@
+use crate::instructions::dutch_auction::record_auction_fill;
+use crate::instructions::kyc::verify_kyc;
+use crate::instructions::min_raise::escrow_take;
+use crate::instructions::offer_limits::record_purchase;
+use crate::instructions::pause::check_not_paused;
+use crate::instructions::pricing::{calculate_buy_amount, calculate_current_sell_amount};
+use crate::instructions::permissionless_account::PermissionlessAccountErrorCode;
+use crate::instructions::protocol_fee::{calculate_fee, verify_fee_vault};
+use crate::instructions::referral::accrue_referral_fee;
+use crate::instructions::subscription::check_not_subscription;
+use crate::instructions::take_offer_one_permissionless::TakeOfferPermissionlessErrorCode;
+use crate::instructions::vesting::{lock_vested_take, verify_vesting_token_account};
+use crate::state::{
+    KycRecord, NavOracle, Offer, PermissionlessAccount, Position, ReferrerAccount, State, VestingEscrow,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount as InterfaceTokenAccount;
+
+/// Event emitted when an offer with two buy tokens is taken via permissionless route.
+#[event]
+pub struct OfferTakenTwoPermissionless {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    pub sell_token_amount: u64,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    pub remaining_buy_token_1_amount: u64,
+    pub remaining_buy_token_2_amount: u64,
+    /// Index of the permissionless account the take was routed through.
+    pub intermediary_index: u64,
+    /// Part of `sell_token_amount` sent to the fee vault.
+    pub fee_amount: u64,
+    /// The referrer of the take, if any.
+    pub referrer: Option<Pubkey>,
+    /// Part of `sell_token_amount` credited to the referrer.
+    pub referral_fee_amount: u64,
+}
+
+/// Account structure for taking an offer with two buy tokens via permissionless route.
+///
+/// Routes the sell tokens and both buy tokens through the intermediary token accounts of a
+/// permissionless account, like `TakeOfferOnePermissionless`.
+///
+/// # Flow
+/// 1. User provides sell tokens to intermediary account
+/// 2. Intermediary account transfers sell tokens to offer
+/// 3. Offer transfers both buy tokens to intermediary accounts (program-controlled)
+/// 4. Intermediary accounts transfer buy tokens to user, or to their vesting escrow for vesting offers
+///
+/// # Preconditions
+/// - All user and intermediary ATAs must be initialized prior to execution
+/// - Offer must have sufficient buy tokens available
+/// - The permissionless account `intermediary_index` must be initialized and enabled
+#[derive(Accounts)]
+#[instruction(intermediary_index: u64)]
+pub struct TakeOfferTwoPermissionless<'info> {
+    /// The offer account being taken, providing offer details.
+    /// Ensures this is a two buy token offer by checking `buy_token_mint_2`.
+    #[account(
+        mut,
+        constraint = offer.buy_token_2.mint != Pubkey::default() @ TakeOfferPermissionlessErrorCode::InvalidTakeOffer
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Offer's sell token ATA, receives the user's sell tokens.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_sell_token_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Offer's buy token 1 ATA, sends buy token 1 to the intermediary account.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_1.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_1_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Offer's buy token 2 ATA, sends buy token 2 to the intermediary account.
+    #[account(
+        mut,
+        associated_token::mint = offer.buy_token_2.mint,
+        associated_token::authority = offer_token_authority,
+    )]
+    pub offer_buy_token_2_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// User's sell token ATA, sends sell tokens to the intermediary account.
+    /// Ensures mint matches the offer's sell token mint.
+    #[account(
+        mut,
+        associated_token::mint = offer.sell_token_mint,
+        associated_token::authority = user,
+        constraint = offer.sell_token_mint == user_sell_token_account.mint @ TakeOfferPermissionlessErrorCode::InvalidSellTokenMint
+    )]
+    pub user_sell_token_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// User's buy token 1 ATA, receives buy token 1 from the intermediary account.
+    #[account(
+        mut,
+        associated_token::mint = buy_token_1_mint,
+        associated_token::authority = user,
+    )]
+    pub user_buy_token_1_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// User's buy token 2 ATA, receives buy token 2 from the intermediary account.
+    #[account(
+        mut,
+        associated_token::mint = buy_token_2_mint,
+        associated_token::authority = user,
+    )]
+    pub user_buy_token_2_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Intermediary token account that temporarily holds buy token 1.
+    /// This account is controlled by the program and must be pre-initialized.
+    #[account(
+        mut,
+        associated_token::mint = buy_token_1_mint,
+        associated_token::authority = intermediary_authority,
+    )]
+    pub intermediary_buy_token_1_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Intermediary token account that temporarily holds buy token 2.
+    /// This account is controlled by the program and must be pre-initialized.
+    #[account(
+        mut,
+        associated_token::mint = buy_token_2_mint,
+        associated_token::authority = intermediary_authority,
+    )]
+    pub intermediary_buy_token_2_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// Intermediary token account that temporarily holds sell tokens.
+    /// This account is controlled by the program and must be pre-initialized.
+    #[account(
+        mut,
+        associated_token::mint = sell_token_mint,
+        associated_token::authority = intermediary_authority,
+    )]
+    pub intermediary_sell_token_account: Box<InterfaceAccount<'info, InterfaceTokenAccount>>,
+
+    /// The mint account for the buy token 1.
+    #[account(
+        constraint = buy_token_1_mint.key() == offer.buy_token_1.mint @ TakeOfferPermissionlessErrorCode::InvalidBuyTokenMint
+    )]
+    pub buy_token_1_mint: Box<Account<'info, Mint>>,
+
+    /// The mint account for the buy token 2.
+    #[account(
+        constraint = buy_token_2_mint.key() == offer.buy_token_2.mint @ TakeOfferPermissionlessErrorCode::InvalidBuyTokenMint
+    )]
+    pub buy_token_2_mint: Box<Account<'info, Mint>>,
+
+    /// The mint account for the sell token.
+    #[account(constraint = sell_token_mint.key() == offer.sell_token_mint)]
+    pub sell_token_mint: Box<Account<'info, Mint>>,
+
+    /// Derived PDA for offer token authority, controls offer token accounts.
+    /// CHECK: This account is validated by the seed derivation.
+    #[account(
+        seeds = [b"offer_authority", offer.offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer_token_authority: AccountInfo<'info>,
+
+    /// Permissionless account the take routes through, controls the intermediary token accounts.
+    /// Ensures the routing channel is enabled.
+    #[account(
+        seeds = [b"permissionless", intermediary_index.to_le_bytes().as_ref()],
+        bump = intermediary_authority.bump,
+        constraint = intermediary_authority.enabled @ PermissionlessAccountErrorCode::PermissionlessAccountDisabled
+    )]
+    pub intermediary_authority: Box<Account<'info, PermissionlessAccount>>,
+
+    /// Program state, checked for the global pause.
+    #[account(seeds = [b"state"], bump)]
+    pub state: Box<Account<'info, State>>,
+
+    /// NAV oracle referenced by the offer, required only for NAV priced offers.
+    pub nav_oracle: Option<Box<Account<'info, NavOracle>>>,
+
+    /// KYC record of `user`, required only for offers with a minimum KYC level.
+    pub kyc_record: Option<Box<Account<'info, KycRecord>>>,
+
+    /// Fee vault of the sell token, required only when a protocol fee is due.
+    #[account(mut)]
+    pub fee_vault_token_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// Referrer account of the take's referrer, credited with the offer's referral fee.
+    #[account(mut)]
+    pub referrer_account: Option<Box<Account<'info, ReferrerAccount>>>,
+
+    /// Referral vault of the sell token, required only when a referral fee is due.
+    #[account(mut)]
+    pub referral_vault_token_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// The user's vesting escrow, required only for offers with a vesting schedule.
+    #[account(mut)]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    /// Vesting escrow's buy token 1 ATA, receives the locked buy token 1 of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_1_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// Vesting escrow's buy token 2 ATA, receives the locked buy token 2 of vesting offers.
+    #[account(mut)]
+    pub vesting_buy_token_2_account: Option<Box<InterfaceAccount<'info, InterfaceTokenAccount>>>,
+
+    /// The user's position in the offer, created on the first take with rent paid by `user`.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [b"position", offer.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// The user taking the offer, signs the transaction and pays for account creation.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+
+    /// Associated Token program for ATA operations.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Solana System program, required for account creation.
+    pub system_program: Program<'info, System>,
+}
+
+/// Takes an offer with two buy tokens via permissionless route.
+///
+/// The two buy token counterpart of `take_offer_one_permissionless`, with the economic outcome
+/// of `take_offer_two`. The protocol fee is deducted from `sell_token_amount` and, if a referrer
+/// account is passed, the referral fee from the offer's proceeds, both sent from the intermediary
+/// account to the fee and referral vaults. If the offer has a minimum raise, the buy tokens stay escrowed in the offer
+/// until `claim_receipt`, and if it has a vesting schedule, they are locked in the user's vesting
+/// escrow until `claim_vested`. Emits an `OfferTakenTwoPermissionless` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the offer take.
+/// - `intermediary_index`: Index of the permissionless account the take routes through.
+/// - `sell_token_amount`: Amount of sell tokens the user provides to exchange for buy tokens.
+///
+/// # Errors
+/// - `PermissionlessAccountErrorCode::PermissionlessAccountDisabled` if the permissionless account is disabled.
+/// - `PricingErrorCode::InvalidCurrentTime` if the offer is not active.
+/// - `NavOracleErrorCode` errors if the offer is NAV priced and the NAV is missing, stale or deactivated.
+/// - `PauseErrorCode` errors if the program or the offer is paused.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+/// - `MinRaiseErrorCode::CalculationOverflow` if the take of a `min_raise` offer overflows its escrow.
+/// - `VestingErrorCode` errors if the offer vests and the vesting escrow or its token accounts are missing or invalid.
+/// - `KycErrorCode` errors if the offer requires KYC and the user's record is missing, insufficient or expired.
+/// - `OfferLimitsErrorCode` errors if the take violates the offer's per take, per user or raise limits.
+/// - `ProtocolFeeErrorCode` errors if a fee is due and the fee vault is missing or invalid.
+/// - `ReferralErrorCode` errors if the referrer account or the referral vault is invalid.
+/// - `DutchAuctionErrorCode::AuctionAlreadySettled` if the offer is in uniform price mode and was settled.
+/// - [`TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance`] if the offer lacks sufficient buy token 1.
+/// - [`TakeOfferPermissionlessErrorCode::InsufficientOfferTokenTwoBalance`] if the offer lacks sufficient buy token 2.
+/// - `PricingErrorCode::CalculationOverflow` if amount calculations overflow.
+/// - `PricingErrorCode::ZeroBuyTokenAmount` if a calculated buy token amount is zero.
+pub fn take_offer_two_permissionless(
+    ctx: Context<TakeOfferTwoPermissionless>,
+    intermediary_index: u64,
+    sell_token_amount: u64,
+) -> Result<()> {
+    check_not_paused(&ctx.accounts.state, &ctx.accounts.offer)?;
+    check_not_subscription(&ctx.accounts.offer)?;
+
+    record_purchase(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        ctx.accounts.user.key(),
+        ctx.bumps.position,
+        sell_token_amount,
+    )?;
+
+    let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp as u64;
+
+    verify_kyc(
+        offer.min_kyc_level,
+        ctx.accounts.kyc_record.as_deref(),
+        ctx.accounts.user.key(),
+        current_time,
+    )?;
+
+    let fee_amount = calculate_fee(&ctx.accounts.state, offer, sell_token_amount)?;
+    verify_fee_vault(
+        ctx.accounts
+            .fee_vault_token_account
+            .as_ref()
+            .map(|account| account.key()),
+        &offer.sell_token_mint,
+        fee_amount,
+    )?;
+    let referral_fee_amount = accrue_referral_fee(
+        offer,
+        ctx.accounts.referrer_account.as_deref_mut(),
+        ctx.accounts
+            .referral_vault_token_account
+            .as_ref()
+            .map(|account| account.key()),
+        &ctx.accounts.user.key(),
+        sell_token_amount,
+    )?;
+    let priced_sell_token_amount = sell_token_amount - fee_amount;
+    let net_sell_token_amount = priced_sell_token_amount - referral_fee_amount;
+
+    let current_sell_token_amount =
+        calculate_current_sell_amount(offer, ctx.accounts.nav_oracle.as_deref(), current_time)?;
+    msg!(
+        "Calculated current sell token amount: {}",
+        current_sell_token_amount
+    );
+
+    let buy_token_1_amount = calculate_buy_amount(
+        priced_sell_token_amount,
+        offer.buy_token_1.amount,
+        current_sell_token_amount,
+    )?;
+    msg!("Calculated buy token 1 amount: {}", buy_token_1_amount);
+    let available_buy_token_1_amount = ctx
+        .accounts
+        .offer_buy_token_1_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_1_total)
+        .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance)?;
+    require!(
+        available_buy_token_1_amount >= buy_token_1_amount,
+        TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance
+    );
+
+    let buy_token_2_amount = calculate_buy_amount(
+        priced_sell_token_amount,
+        offer.buy_token_2.amount,
+        current_sell_token_amount,
+    )?;
+    msg!("Calculated buy token 2 amount: {}", buy_token_2_amount);
+    let available_buy_token_2_amount = ctx
+        .accounts
+        .offer_buy_token_2_account
+        .amount
+        .checked_sub(offer.escrowed_buy_token_2_total)
+        .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenTwoBalance)?;
+    require!(
+        available_buy_token_2_amount >= buy_token_2_amount,
+        TakeOfferPermissionlessErrorCode::InsufficientOfferTokenTwoBalance
+    );
+    record_auction_fill(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        priced_sell_token_amount,
+        buy_token_1_amount,
+        current_sell_token_amount,
+    )?;
+    let escrowed = escrow_take(
+        &mut ctx.accounts.offer,
+        &mut ctx.accounts.position,
+        net_sell_token_amount,
+        buy_token_1_amount,
+        buy_token_2_amount,
+    )?;
+    let offer_key = ctx.accounts.offer.key();
+    let vesting_escrow = lock_vested_take(
+        &ctx.accounts.offer,
+        offer_key,
+        ctx.accounts.user.key(),
+        ctx.accounts.vesting_escrow.as_deref_mut(),
+        buy_token_1_amount,
+        buy_token_2_amount,
+    )?;
+    let offer = &ctx.accounts.offer;
+    let (buy_token_1_destination, buy_token_2_destination) = match &vesting_escrow {
+        Some(vesting_escrow) => (
+            verify_vesting_token_account(
+                ctx.accounts
+                    .vesting_buy_token_1_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                vesting_escrow,
+                &offer.buy_token_1.mint,
+            )?,
+            verify_vesting_token_account(
+                ctx.accounts
+                    .vesting_buy_token_2_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                vesting_escrow,
+                &offer.buy_token_2.mint,
+            )?,
+        ),
+        None => (
+            ctx.accounts.user_buy_token_1_account.to_account_info(),
+            ctx.accounts.user_buy_token_2_account.to_account_info(),
+        ),
+    };
+
+    // Step 1: Transfer sell tokens from user to intermediary account
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_sell_token_account.to_account_info(),
+                to: ctx
+                    .accounts
+                    .intermediary_sell_token_account
+                    .to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        sell_token_amount,
+    )?;
+    msg!(
+        "Transferring {} sell tokens from user to intermediary",
+        sell_token_amount
+    );
+
+    // Step 2: Transfer sell tokens from intermediary account to offer
+    let intermediary_index_bytes = &intermediary_index.to_le_bytes();
+    let intermediary_seeds = &[
+        b"permissionless".as_ref(),
+        intermediary_index_bytes,
+        &[ctx.accounts.intermediary_authority.bump],
+    ];
+    let intermediary_signer_seeds = &[&intermediary_seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx
+                    .accounts
+                    .intermediary_sell_token_account
+                    .to_account_info(),
+                to: ctx.accounts.offer_sell_token_account.to_account_info(),
+                authority: ctx.accounts.intermediary_authority.to_account_info(),
+            },
+            intermediary_signer_seeds,
+        ),
+        net_sell_token_amount,
+    )?;
+    msg!(
+        "Transferring {} sell tokens from intermediary to offer",
+        net_sell_token_amount
+    );
+
+    if fee_amount > 0 {
+        if let Some(fee_vault_token_account) = &ctx.accounts.fee_vault_token_account {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx
+                            .accounts
+                            .intermediary_sell_token_account
+                            .to_account_info(),
+                        to: fee_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.intermediary_authority.to_account_info(),
+                    },
+                    intermediary_signer_seeds,
+                ),
+                fee_amount,
+            )?;
+            msg!(
+                "Transferring {} sell tokens from intermediary to fee vault",
+                fee_amount
+            );
+        }
+    }
+
+    if referral_fee_amount > 0 {
+        if let Some(referral_vault_token_account) = &ctx.accounts.referral_vault_token_account {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx
+                            .accounts
+                            .intermediary_sell_token_account
+                            .to_account_info(),
+                        to: referral_vault_token_account.to_account_info(),
+                        authority: ctx.accounts.intermediary_authority.to_account_info(),
+                    },
+                    intermediary_signer_seeds,
+                ),
+                referral_fee_amount,
+            )?;
+            msg!(
+                "Transferring {} sell tokens from intermediary to referral vault",
+                referral_fee_amount
+            );
+        }
+    }
+
+    // Step 3: Transfer buy tokens from offer to intermediary accounts
+    let offer_id_bytes = &offer.offer_id.to_le_bytes();
+    let offer_seeds = &[
+        b"offer_authority".as_ref(),
+        offer_id_bytes,
+        &[offer.authority_bump],
+    ];
+    let offer_signer_seeds = &[&offer_seeds[..]];
+
+    if escrowed {
+        msg!(
+            "Escrowing {} buy tokens 1 until the offer ends",
+            buy_token_1_amount
+        );
+        msg!(
+            "Escrowing {} buy tokens 2 until the offer ends",
+            buy_token_2_amount
+        );
+    } else {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_1_account.to_account_info(),
+                    to: ctx
+                        .accounts
+                        .intermediary_buy_token_1_account
+                        .to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                offer_signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+        msg!(
+            "Transferring {} buy tokens 1 from offer to intermediary account",
+            buy_token_1_amount
+        );
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.offer_buy_token_2_account.to_account_info(),
+                    to: ctx
+                        .accounts
+                        .intermediary_buy_token_2_account
+                        .to_account_info(),
+                    authority: ctx.accounts.offer_token_authority.to_account_info(),
+                },
+                offer_signer_seeds,
+            ),
+            buy_token_2_amount,
+        )?;
+        msg!(
+            "Transferring {} buy tokens 2 from offer to intermediary account",
+            buy_token_2_amount
+        );
+
+        // Step 4: Transfer buy tokens from intermediary accounts to user, or their vesting escrow
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx
+                        .accounts
+                        .intermediary_buy_token_1_account
+                        .to_account_info(),
+                    to: buy_token_1_destination,
+                    authority: ctx.accounts.intermediary_authority.to_account_info(),
+                },
+                intermediary_signer_seeds,
+            ),
+            buy_token_1_amount,
+        )?;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx
+                        .accounts
+                        .intermediary_buy_token_2_account
+                        .to_account_info(),
+                    to: buy_token_2_destination,
+                    authority: ctx.accounts.intermediary_authority.to_account_info(),
+                },
+                intermediary_signer_seeds,
+            ),
+            buy_token_2_amount,
+        )?;
+        if vesting_escrow.is_some() {
+            msg!(
+                "Transferring {} buy tokens 1 and {} buy tokens 2 from intermediary to vesting escrow",
+                buy_token_1_amount,
+                buy_token_2_amount
+            );
+        } else {
+            msg!(
+                "Transferring {} buy tokens 1 and {} buy tokens 2 from intermediary to user",
+                buy_token_1_amount,
+                buy_token_2_amount
+            );
+        }
+    }
+
+    emit!(OfferTakenTwoPermissionless {
+        offer_id: offer.offer_id,
+        user: ctx.accounts.user.key(),
+        sell_token_amount,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        remaining_buy_token_1_amount: ctx
+            .accounts
+            .offer_buy_token_1_account
+            .amount
+            .checked_sub(buy_token_1_amount)
+            .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenOneBalance)?,
+        remaining_buy_token_2_amount: ctx
+            .accounts
+            .offer_buy_token_2_account
+            .amount
+            .checked_sub(buy_token_2_amount)
+            .ok_or(TakeOfferPermissionlessErrorCode::InsufficientOfferTokenTwoBalance)?,
+        intermediary_index,
+        fee_amount,
+        referrer: ctx
+            .accounts
+            .referrer_account
+            .as_ref()
+            .map(|account| account.referrer),
+        referral_fee_amount,
+    });
+
+    Ok(())
+}
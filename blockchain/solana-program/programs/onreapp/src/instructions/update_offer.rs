@@ -0,0 +1,139 @@
+use crate::instructions::make_offer::{validate_dynamic_price_params, validate_non_zero_token_amounts};
+use crate::instructions::pricing::{current_interval, schedule_start_time, scheduled_sell_amount};
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::subscription::check_no_open_subscriptions;
+use crate::state::{Offer, Role, State};
+use anchor_lang::prelude::*;
+
+/// Event emitted when the end time or the price schedule of an offer is changed.
+#[event]
+pub struct OfferUpdated {
+    pub offer_id: u64,
+    pub old_sell_token_start_amount: u64,
+    pub new_sell_token_start_amount: u64,
+    pub old_sell_token_end_amount: u64,
+    pub new_sell_token_end_amount: u64,
+    pub old_offer_end_time: u64,
+    pub new_offer_end_time: u64,
+    pub old_price_fix_duration: u64,
+    pub new_price_fix_duration: u64,
+    /// Unix timestamp the new schedule starts at, the price of the current interval being kept until then.
+    pub schedule_start_time: u64,
+}
+
+/// Account structure for updating an offer.
+#[derive(Accounts)]
+pub struct UpdateOffer<'info> {
+    /// The offer being updated.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the update.
+    pub authority: Signer<'info>,
+}
+
+/// Updates the end time and the price schedule of an offer.
+///
+/// Lets an offer be extended or repriced in place instead of being closed and made again.
+/// Before the offer starts, the new schedule replaces the old one from `offer_start_time`. Once
+/// it started, the current interval keeps its price and the new schedule starts at the next
+/// interval boundary, or at the old end time if the offer already expired, so elapsed intervals
+/// are not repriced. Emits an `OfferUpdated` event.
+///
+/// # Arguments
+/// - `ctx`: Context containing the accounts for the update.
+/// - `sell_token_start_amount`: New sell token amount in the first interval of the new schedule.
+/// - `sell_token_end_amount`: New sell token amount in the last interval of the new schedule.
+/// - `offer_end_time`: New Unix timestamp when the offer expires.
+/// - `price_fix_duration`: New duration in seconds of each fixed price interval.
+///
+/// # Errors
+/// - [`UpdateOfferErrorCode::InvalidOfferEndTime`] if `offer_end_time` is not in the future.
+/// - [`UpdateOfferErrorCode::UniformPriceOffer`] if the offer is in uniform price mode.
+/// - `MakeOfferErrorCode` errors if the new price schedule is invalid from its start, see `make_offer`.
+/// - `SubscriptionErrorCode::OpenSubscriptions` if subscriptions of the offer were not claimed.
+/// - `PricingErrorCode` errors if the price of the current interval can't be calculated.
+pub fn update_offer(
+    ctx: Context<UpdateOffer>,
+    sell_token_start_amount: u64,
+    sell_token_end_amount: u64,
+    offer_end_time: u64,
+    price_fix_duration: u64,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    require!(
+        offer_end_time > current_time,
+        UpdateOfferErrorCode::InvalidOfferEndTime
+    );
+
+    let offer = &mut ctx.accounts.offer;
+    require!(!offer.uniform_price, UpdateOfferErrorCode::UniformPriceOffer);
+    check_no_open_subscriptions(offer)?;
+    validate_non_zero_token_amounts(&[sell_token_start_amount, sell_token_end_amount])?;
+
+    let (schedule_start_time, carried_sell_token_amount) = if current_time < offer.offer_start_time {
+        (0, 0)
+    } else if current_time >= offer.offer_end_time {
+        (offer.offer_end_time, 0)
+    } else if current_time < offer.schedule_start_time {
+        (offer.schedule_start_time, offer.carried_sell_token_amount)
+    } else {
+        let (interval, _) = current_interval(
+            schedule_start_time(offer),
+            offer.offer_end_time,
+            offer.price_fix_duration,
+            current_time,
+        )?;
+        (
+            schedule_start_time(offer) + (interval + 1) * offer.price_fix_duration,
+            scheduled_sell_amount(offer, current_time)?,
+        )
+    };
+    validate_dynamic_price_params(
+        offer.offer_start_time.max(schedule_start_time),
+        offer_end_time,
+        price_fix_duration,
+    )?;
+
+    emit!(OfferUpdated {
+        offer_id: offer.offer_id,
+        old_sell_token_start_amount: offer.sell_token_start_amount,
+        new_sell_token_start_amount: sell_token_start_amount,
+        old_sell_token_end_amount: offer.sell_token_end_amount,
+        new_sell_token_end_amount: sell_token_end_amount,
+        old_offer_end_time: offer.offer_end_time,
+        new_offer_end_time: offer_end_time,
+        old_price_fix_duration: offer.price_fix_duration,
+        new_price_fix_duration: price_fix_duration,
+        schedule_start_time: offer.offer_start_time.max(schedule_start_time),
+    });
+
+    offer.sell_token_start_amount = sell_token_start_amount;
+    offer.sell_token_end_amount = sell_token_end_amount;
+    offer.offer_end_time = offer_end_time;
+    offer.price_fix_duration = price_fix_duration;
+    offer.schedule_start_time = schedule_start_time;
+    offer.carried_sell_token_amount = carried_sell_token_amount;
+
+    Ok(())
+}
+
+/// Error codes for offer updates.
+#[error_code]
+pub enum UpdateOfferErrorCode {
+    /// Triggered when the new end time is not in the future.
+    #[msg("Offer end time must be in the future")]
+    InvalidOfferEndTime,
+
+    /// Triggered when updating the schedule of a uniform price offer, whose fills share the clearing price.
+    #[msg("Uniform price offers cannot be updated")]
+    UniformPriceOffer,
+}
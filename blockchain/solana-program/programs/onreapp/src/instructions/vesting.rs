@@ -0,0 +1,456 @@
+use crate::instructions::min_raise::check_no_min_raise;
+use crate::instructions::roles::RoleErrorCode;
+use crate::instructions::subscription::check_not_subscription;
+use crate::state::{Offer, Role, State, VestingEscrow};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Event emitted when the vesting schedule of an offer is changed.
+#[event]
+pub struct OfferVestingSet {
+    pub offer_id: u64,
+    pub start_time: u64,
+    pub cliff_duration: u64,
+    pub duration: u64,
+}
+
+/// Event emitted when the buy tokens of a take are locked in the user's vesting escrow.
+#[event]
+pub struct TokensLocked {
+    pub offer_id: u64,
+    pub user: Pubkey,
+    pub vesting_escrow: Pubkey,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    /// Cumulative buy token 1 amount locked in the escrow, including this take.
+    pub buy_token_1_locked: u64,
+    /// Cumulative buy token 2 amount locked in the escrow, including this take.
+    pub buy_token_2_locked: u64,
+}
+
+/// Event emitted when vested buy tokens are released to the user.
+#[event]
+pub struct VestedClaimed {
+    pub vesting_escrow: Pubkey,
+    pub user: Pubkey,
+    pub buy_token_1_amount: u64,
+    pub buy_token_2_amount: u64,
+    /// Buy token 1 amount still locked in the escrow after the claim.
+    pub buy_token_1_remaining: u64,
+    /// Buy token 2 amount still locked in the escrow after the claim.
+    pub buy_token_2_remaining: u64,
+}
+
+/// Account structure for changing the vesting schedule of an offer.
+#[derive(Accounts)]
+pub struct SetOfferVesting<'info> {
+    /// The offer whose vesting schedule is changed.
+    #[account(mut)]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// Program state, ensures `authority` holds the offer manager role.
+    #[account(
+        seeds = [b"state"],
+        bump,
+        constraint = state.has_role(Role::OfferManager, &authority.key()) @ RoleErrorCode::MissingRole
+    )]
+    pub state: Account<'info, State>,
+
+    /// The boss or an offer manager authorizing the change.
+    pub authority: Signer<'info>,
+}
+
+/// Sets the vesting schedule of an offer, a `duration` of `0` for takes to settle instantly.
+///
+/// While the schedule is set, the buy tokens of every take are locked in the user's
+/// `VestingEscrow` instead of being sent to the user. Nothing vests before
+/// `start_time + cliff_duration`, after which the locked tokens vest linearly from `start_time`
+/// until all of them have vested at `start_time + duration`. Emits an `OfferVestingSet` event.
+///
+/// # Errors
+/// - [`VestingErrorCode::OfferAlreadyTaken`] if the offer was already taken.
+/// - [`VestingErrorCode::InvalidVestingSchedule`] if the cliff outlasts the vesting duration.
+/// - `MinRaiseErrorCode::MinRaiseOffer` if the offer has a minimum raise.
+/// - `SubscriptionErrorCode::SubscriptionOffer` if the offer is in subscription mode.
+pub fn set_offer_vesting(
+    ctx: Context<SetOfferVesting>,
+    start_time: u64,
+    cliff_duration: u64,
+    duration: u64,
+) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    require!(offer.total_raised == 0, VestingErrorCode::OfferAlreadyTaken);
+    require!(
+        cliff_duration <= duration,
+        VestingErrorCode::InvalidVestingSchedule
+    );
+    check_no_min_raise(offer)?;
+    check_not_subscription(offer)?;
+
+    offer.vesting_start_time = start_time;
+    offer.vesting_cliff_duration = cliff_duration;
+    offer.vesting_duration = duration;
+
+    emit!(OfferVestingSet {
+        offer_id: offer.offer_id,
+        start_time,
+        cliff_duration,
+        duration,
+    });
+
+    Ok(())
+}
+
+/// Account structure for opening the vesting escrow of a user.
+#[derive(Accounts)]
+pub struct OpenVestingEscrow<'info> {
+    /// The vesting offer the escrow is opened for.
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The user's vesting escrow, created with rent paid by `user`.
+    #[account(
+        init,
+        payer = user,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [b"vesting_escrow", offer.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    /// The user the escrow belongs to, signs and pays for the escrow account.
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Solana System program for the escrow account creation.
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the vesting escrow of a user, required before taking a vesting offer.
+///
+/// The associated token accounts of the escrow PDA for the offer's buy tokens must be created
+/// alongside, they hold the locked tokens.
+///
+/// # Errors
+/// - [`VestingErrorCode::NotVestingOffer`] if the offer has no vesting schedule.
+pub fn open_vesting_escrow(ctx: Context<OpenVestingEscrow>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    require!(offer.vesting_duration > 0, VestingErrorCode::NotVestingOffer);
+
+    let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+    vesting_escrow.offer = offer.key();
+    vesting_escrow.user = ctx.accounts.user.key();
+    vesting_escrow.buy_token_1_mint = offer.buy_token_1.mint;
+    vesting_escrow.buy_token_2_mint = offer.buy_token_2.mint;
+    vesting_escrow.start_time = offer.vesting_start_time;
+    vesting_escrow.cliff_duration = offer.vesting_cliff_duration;
+    vesting_escrow.duration = offer.vesting_duration;
+    vesting_escrow.buy_token_1_locked = 0;
+    vesting_escrow.buy_token_1_claimed = 0;
+    vesting_escrow.buy_token_2_locked = 0;
+    vesting_escrow.buy_token_2_claimed = 0;
+    vesting_escrow.bump = ctx.bumps.vesting_escrow;
+
+    Ok(())
+}
+
+/// Account structure for claiming vested buy tokens.
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    /// The user's vesting escrow.
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", vesting_escrow.offer.as_ref(), user.key().as_ref()],
+        bump = vesting_escrow.bump
+    )]
+    pub vesting_escrow: Box<Account<'info, VestingEscrow>>,
+
+    /// Escrow's buy token 1 ATA, sends the vested buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = vesting_escrow.buy_token_1_mint,
+        associated_token::authority = vesting_escrow,
+    )]
+    pub vesting_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's buy token 1 ATA, receives the vested buy token 1.
+    #[account(
+        mut,
+        associated_token::mint = vesting_escrow.buy_token_1_mint,
+        associated_token::authority = user,
+    )]
+    pub user_buy_token_1_account: Box<Account<'info, TokenAccount>>,
+
+    /// Escrow's buy token 2 ATA, required only for escrows of offers with two buy tokens.
+    #[account(mut)]
+    pub vesting_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// User's buy token 2 ATA, required only for escrows of offers with two buy tokens.
+    #[account(mut)]
+    pub user_buy_token_2_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// The owner of the escrow, signs the claim.
+    pub user: Signer<'info>,
+
+    /// SPL Token program for token operations.
+    pub token_program: Program<'info, Token>,
+}
+
+/// Releases the buy tokens vested so far to the user.
+///
+/// Can be called any number of times, each claim releasing what vested since the previous one.
+/// Emits a `VestedClaimed` event.
+///
+/// # Errors
+/// - [`VestingErrorCode::NothingToClaim`] if no new tokens vested since the last claim.
+/// - [`VestingErrorCode::MissingVestingAccounts`] if the escrow holds buy token 2 and its accounts are missing.
+/// - [`VestingErrorCode::InvalidVestingAccount`] if a buy token 2 account is not the expected ATA.
+/// - [`VestingErrorCode::CalculationOverflow`] if the vested amount calculation overflows.
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let vesting_escrow = &ctx.accounts.vesting_escrow;
+
+    let buy_token_1_amount = calculate_vested_amount(
+        vesting_escrow,
+        vesting_escrow.buy_token_1_locked,
+        current_time,
+    )?
+    .saturating_sub(vesting_escrow.buy_token_1_claimed);
+    let buy_token_2_amount = calculate_vested_amount(
+        vesting_escrow,
+        vesting_escrow.buy_token_2_locked,
+        current_time,
+    )?
+    .saturating_sub(vesting_escrow.buy_token_2_claimed);
+    require!(
+        buy_token_1_amount > 0 || buy_token_2_amount > 0,
+        VestingErrorCode::NothingToClaim
+    );
+
+    let user_key = ctx.accounts.user.key();
+    let seeds = &[
+        b"vesting_escrow".as_ref(),
+        vesting_escrow.offer.as_ref(),
+        user_key.as_ref(),
+        &[vesting_escrow.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vesting_buy_token_1_account.to_account_info(),
+                to: ctx.accounts.user_buy_token_1_account.to_account_info(),
+                authority: vesting_escrow.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        buy_token_1_amount,
+    )?;
+    msg!("Transferring {} buy tokens 1 from vesting escrow to user", buy_token_1_amount);
+
+    let mint = vesting_escrow.buy_token_2_mint;
+    if mint != Pubkey::default() {
+        let (Some(vesting_buy_token_2_account), Some(user_buy_token_2_account)) = (
+            ctx.accounts.vesting_buy_token_2_account.as_ref(),
+            ctx.accounts.user_buy_token_2_account.as_ref(),
+        ) else {
+            return err!(VestingErrorCode::MissingVestingAccounts);
+        };
+        require!(
+            vesting_buy_token_2_account.key()
+                == get_associated_token_address(&vesting_escrow.key(), &mint)
+                && user_buy_token_2_account.key() == get_associated_token_address(&user_key, &mint),
+            VestingErrorCode::InvalidVestingAccount
+        );
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vesting_buy_token_2_account.to_account_info(),
+                    to: user_buy_token_2_account.to_account_info(),
+                    authority: vesting_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            buy_token_2_amount,
+        )?;
+        msg!("Transferring {} buy tokens 2 from vesting escrow to user", buy_token_2_amount);
+    }
+
+    let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+    vesting_escrow.buy_token_1_claimed += buy_token_1_amount;
+    vesting_escrow.buy_token_2_claimed += buy_token_2_amount;
+
+    emit!(VestedClaimed {
+        vesting_escrow: vesting_escrow.key(),
+        user: user_key,
+        buy_token_1_amount,
+        buy_token_2_amount,
+        buy_token_1_remaining: vesting_escrow.buy_token_1_locked - vesting_escrow.buy_token_1_claimed,
+        buy_token_2_remaining: vesting_escrow.buy_token_2_locked - vesting_escrow.buy_token_2_claimed,
+    });
+
+    Ok(())
+}
+
+/// Calculates how much of `locked_amount` has vested at `current_time` under the escrow's schedule.
+///
+/// # Errors
+/// - [`VestingErrorCode::CalculationOverflow`] if the calculation overflows.
+fn calculate_vested_amount(
+    vesting_escrow: &VestingEscrow,
+    locked_amount: u64,
+    current_time: u64,
+) -> Result<u64> {
+    let elapsed = current_time.saturating_sub(vesting_escrow.start_time);
+    if elapsed < vesting_escrow.cliff_duration {
+        return Ok(0);
+    }
+    if elapsed >= vesting_escrow.duration {
+        return Ok(locked_amount);
+    }
+
+    let vested_amount = (locked_amount as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(VestingErrorCode::CalculationOverflow)?
+        / vesting_escrow.duration as u128;
+    Ok(vested_amount as u64)
+}
+
+/// Locks the buy tokens of a take of a vesting offer in the user's vesting escrow.
+///
+/// Does nothing for offers without a vesting schedule. The escrow's schedule is refreshed from
+/// the offer, which cannot change it once taken. Emits a `TokensLocked` event.
+///
+/// # Arguments
+/// - `offer`: The taken offer.
+/// - `offer_key`: Address of the taken offer.
+/// - `user`: The user taking the offer.
+/// - `vesting_escrow`: The vesting escrow passed to the take, if any.
+/// - `buy_token_1_amount`: Buy token 1 amount bought.
+/// - `buy_token_2_amount`: Buy token 2 amount bought, `0` for offers with one buy token.
+///
+/// # Returns
+/// The address of the vesting escrow if the take was locked, in which case the caller sends the
+/// buy tokens to the escrow's ATAs, see [`verify_vesting_token_account`].
+///
+/// # Errors
+/// - [`VestingErrorCode::MissingVestingAccounts`] if the offer vests and no escrow was passed.
+/// - [`VestingErrorCode::InvalidVestingEscrow`] if the escrow belongs to another offer or user.
+/// - [`VestingErrorCode::CalculationOverflow`] if a cumulative amount overflows.
+pub fn lock_vested_take(
+    offer: &Offer,
+    offer_key: Pubkey,
+    user: Pubkey,
+    vesting_escrow: Option<&mut Account<VestingEscrow>>,
+    buy_token_1_amount: u64,
+    buy_token_2_amount: u64,
+) -> Result<Option<Pubkey>> {
+    if offer.vesting_duration == 0 {
+        return Ok(None);
+    }
+
+    let vesting_escrow = vesting_escrow.ok_or(VestingErrorCode::MissingVestingAccounts)?;
+    require!(
+        vesting_escrow.offer == offer_key && vesting_escrow.user == user,
+        VestingErrorCode::InvalidVestingEscrow
+    );
+
+    vesting_escrow.start_time = offer.vesting_start_time;
+    vesting_escrow.cliff_duration = offer.vesting_cliff_duration;
+    vesting_escrow.duration = offer.vesting_duration;
+    vesting_escrow.buy_token_1_locked = vesting_escrow
+        .buy_token_1_locked
+        .checked_add(buy_token_1_amount)
+        .ok_or(VestingErrorCode::CalculationOverflow)?;
+    vesting_escrow.buy_token_2_locked = vesting_escrow
+        .buy_token_2_locked
+        .checked_add(buy_token_2_amount)
+        .ok_or(VestingErrorCode::CalculationOverflow)?;
+
+    emit!(TokensLocked {
+        offer_id: offer.offer_id,
+        user,
+        vesting_escrow: vesting_escrow.key(),
+        buy_token_1_amount,
+        buy_token_2_amount,
+        buy_token_1_locked: vesting_escrow.buy_token_1_locked,
+        buy_token_2_locked: vesting_escrow.buy_token_2_locked,
+    });
+
+    Ok(Some(vesting_escrow.key()))
+}
+
+/// Verifies that a token account passed to a take is the vesting escrow's ATA of `mint`.
+///
+/// # Returns
+/// The account info of the token account, the destination of the locked buy tokens.
+///
+/// # Errors
+/// - [`VestingErrorCode::MissingVestingAccounts`] if the token account is missing.
+/// - [`VestingErrorCode::InvalidVestingAccount`] if the token account is not the expected ATA.
+pub fn verify_vesting_token_account<'info>(
+    vesting_token_account: Option<AccountInfo<'info>>,
+    vesting_escrow: &Pubkey,
+    mint: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    let vesting_token_account =
+        vesting_token_account.ok_or(VestingErrorCode::MissingVestingAccounts)?;
+    require!(
+        vesting_token_account.key() == get_associated_token_address(vesting_escrow, mint),
+        VestingErrorCode::InvalidVestingAccount
+    );
+    Ok(vesting_token_account)
+}
+
+/// Checks that an offer has no vesting schedule, which other settlement modes don't support.
+///
+/// # Errors
+/// - [`VestingErrorCode::VestingOffer`] if the offer has a vesting schedule.
+pub fn check_no_vesting(offer: &Offer) -> Result<()> {
+    require!(offer.vesting_duration == 0, VestingErrorCode::VestingOffer);
+    Ok(())
+}
+
+/// Error codes for vesting operations.
+#[error_code]
+pub enum VestingErrorCode {
+    /// Triggered when changing the vesting schedule of an offer that was already taken.
+    #[msg("The offer was already taken")]
+    OfferAlreadyTaken,
+
+    /// Triggered when the cliff of a vesting schedule outlasts its duration.
+    #[msg("The vesting cliff must not exceed the vesting duration")]
+    InvalidVestingSchedule,
+
+    /// Triggered when opening a vesting escrow for an offer without a vesting schedule.
+    #[msg("The offer has no vesting schedule")]
+    NotVestingOffer,
+
+    /// Triggered when combining vesting with a minimum raise or subscriptions.
+    #[msg("Vesting offers cannot use a minimum raise or subscriptions")]
+    VestingOffer,
+
+    /// Triggered when taking a vesting offer without the vesting escrow or its token accounts.
+    #[msg("Missing vesting escrow accounts")]
+    MissingVestingAccounts,
+
+    /// Triggered when the vesting escrow belongs to another offer or user.
+    #[msg("The vesting escrow does not belong to the offer and user")]
+    InvalidVestingEscrow,
+
+    /// Triggered when a token account is not the vesting escrow's or user's ATA.
+    #[msg("Invalid vesting token account")]
+    InvalidVestingAccount,
+
+    /// Triggered when claiming before any new tokens vested.
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    /// Triggered when a vesting calculation overflows.
+    #[msg("Calculation overflow")]
+    CalculationOverflow,
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{ProposalAccount, Role};
 
 // Program ID declaration
 declare_id!("onreuGhHHgVzMWSkj2oQDLDtvvGvoepBPkqyaubFcwe");
@@ -16,10 +17,61 @@ pub mod state;
 /// sell token required can change over the offer's duration based on predefined parameters.
 ///
 /// Core functionalities include:
-/// - Making offers with dynamic pricing (`make_offer_one`, `make_offer_two`).
+/// - Making offers with dynamic pricing (`make_offer_one`, `make_offer_two`), with IDs handed out
+///   in order by the state and the open offers listed in a registry (`set_next_offer_id`,
+///   `initialize_offer_registry`).
 /// - Taking offers, respecting the current price (`take_offer_one`, `take_offer_two`).
-/// - Closing offers (`close_offer_one`, `close_offer_two`).
-/// - Program state initialization and boss management (`initialize`, `set_boss`).
+/// - Quoting offers at the current price for simulation via return data (`quote_offer`).
+/// - Routing takes through program-controlled intermediaries, one per routing channel
+///   (`initialize_permissionless_account`, `set_permissionless_account_enabled`,
+///   `take_offer_one_permissionless`, `take_offer_two_permissionless`), and recovering stray
+///   intermediary balances and rent (`sweep_intermediary`, `close_intermediary`).
+/// - Closing offers (`close_offer_one`, `close_offer_two`), and expired offers by anyone for an
+///   optional bounty (`close_expired_offer`, `set_crank_bounty`).
+/// - Extending or repricing offers in place (`update_offer`).
+/// - Adding or withdrawing buy tokens without changing the price (`top_up_offer`, `withdraw_unsold`).
+/// - Withdrawing sale proceeds of open offers to the treasury (`set_treasury`, `withdraw_proceeds`).
+/// - Uniform price Dutch auctions, where every filler pays the clearing price and claims the
+///   difference as a rebate (`set_offer_uniform_price`, `settle_auction`, `claim_rebate`).
+/// - Subscription offers collecting deposits filled pro-rata once the offer ends
+///   (`set_offer_subscription`, `subscribe`, `claim_subscription`).
+/// - Soft-cap offers escrowing takes until the offer ends, refunded if the minimum raise is
+///   missed (`set_offer_min_raise`, `claim_receipt`).
+/// - Vesting offers locking bought tokens in per-user escrows released after a cliff and linearly
+///   over time (`set_offer_vesting`, `open_vesting_escrow`, `claim_vested`).
+/// - Program state initialization and two-step boss handover (`initialize`, `propose_boss`,
+///   `accept_boss`, `cancel_boss_proposal`).
+/// - NAV oracles pricing offers at a pool's published NAV (`initialize_nav_oracle`, `update_nav`,
+///   `configure_nav_oracle`, `set_offer_nav_pricing`).
+/// - Redemption offers buying tokens back from holders (`make_redemption_offer`,
+///   `take_redemption_offer`, `close_redemption_offer`).
+/// - Redemption queues settling locked tokens pro-rata per epoch (`initialize_redemption_queue`,
+///   `request_redemption`, `cancel_redemption`, `settle_redemption_epoch`, `claim_redemption`).
+/// - A KYC registry gating offers by minimum KYC level (`approve_kyc`, `revoke_kyc`,
+///   `set_offer_min_kyc_level`).
+/// - Per user, per take and overall purchase limits on offers, tracked in per user `Position`
+///   accounts (`set_offer_limits`).
+/// - Pausing all offers or a single offer (`set_paused`, `set_offer_paused`).
+/// - Migrating the program state and offers to the current layout (`migrate_state`, `migrate_offer`).
+/// - Role based access control, letting operational keys act without holding the boss key
+///   (`grant_role`, `revoke_role`).
+/// - An m-of-n multisig executing approved onreapp instructions as its authority PDA, which can
+///   be handed the boss key (`create_multisig`, `change_multisig`, `create_proposal`,
+///   `approve_proposal`, `reject_proposal`, `execute_proposal`, `close_proposal`).
+/// - A protocol fee deducted from the sell tokens of every take and collected in fee vaults
+///   (`set_protocol_fee`, `set_offer_fee`, `withdraw_fees`).
+/// - Referral fees credited to the referrer of a take and claimed per sell token
+///   (`register_referrer`, `set_offer_referral_fee`, `claim_referral_fees`).
+///
+/// # Roles
+/// Privileged instructions are gated by roles stored in the program state rather than by the
+/// boss key alone. The boss implicitly holds every role.
+/// - `OfferManager`: makes, configures and closes offers, redemption offers and queues.
+/// - `Pauser`: pauses and unpauses the program and offers.
+/// - `Treasurer`: withdraws proceeds and protocol fees.
+/// - `Compliance`: approves and revokes KYC records.
+/// - `Admin`: grants and revokes roles, initializes, enables and disables permissionless accounts,
+///   registers referrers.
 ///
 /// # Dynamic Pricing Model
 /// The price (amount of sell tokens per buy token) is determined by:
@@ -27,7 +79,14 @@ pub mod state;
 /// - `sell_token_end_amount`: Sell token amount at the end of the offer.
 /// - `offer_start_time`, `offer_end_time`: Defines the offer's active duration.
 /// - `price_fix_duration`: The duration of each discrete pricing interval within the offer period.
-/// The price interpolates linearly across these intervals.
+/// The price interpolates linearly across these intervals, from the start amount in the first
+/// interval to the end amount in the last one, rounded in favour of the offer. It rises when the
+/// end amount exceeds the start amount, and decays towards the end amount floor otherwise, as in
+/// a Dutch auction. The calculations live in the `pricing` module.
+///
+/// Alternatively, an offer can reference a `NavOracle` account, in which case takers pay the
+/// latest NAV plus a per-offer spread, and trades are rejected while the NAV is stale or the
+/// pool is deactivated.
 ///
 /// # Security
 /// - Access controls are enforced, for example, ensuring only the `boss` can create offers or update critical state.
@@ -42,11 +101,12 @@ pub mod onre_app {
     /// Delegates to `make_offer::make_offer_one`.
     /// The price of the sell token changes over time based on `sell_token_start_amount`,
     /// `sell_token_end_amount`, and `price_fix_duration` within the offer's active time window.
+    /// The offer gets `State::next_offer_id` as its ID, starts out active and is listed in the
+    /// offer registry.
     /// Emits an `OfferMadeOne` event upon success.
     ///
     /// # Arguments
     /// - `ctx`: Context for `MakeOfferOne`.
-    /// - `offer_id`: Unique ID for the offer.
     /// - `buy_token_total_amount`: Total amount of the buy token offered.
     /// - `sell_token_start_amount`: Sell token amount at the start of the offer.
     /// - `sell_token_end_amount`: Sell token amount at the end of the offer.
@@ -55,7 +115,6 @@ pub mod onre_app {
     /// - `price_fix_duration`: Duration of each price interval.
     pub fn make_offer_one(
         ctx: Context<MakeOfferOne>,
-        offer_id: u64,
         buy_token_total_amount: u64,
         sell_token_start_amount: u64,
         sell_token_end_amount: u64,
@@ -65,7 +124,6 @@ pub mod onre_app {
     ) -> Result<()> {
         make_offer::make_offer_one(
             ctx,
-            offer_id,
             buy_token_total_amount,
             sell_token_start_amount,
             sell_token_end_amount,
@@ -80,11 +138,12 @@ pub mod onre_app {
     /// Delegates to `make_offer::make_offer_two`.
     /// The price of the sell token changes over time based on `sell_token_start_amount`,
     /// `sell_token_end_amount`, and `price_fix_duration` within the offer's active time window.
+    /// The offer gets `State::next_offer_id` as its ID, starts out active and is listed in the
+    /// offer registry.
     /// Emits an `OfferMadeTwo` event upon success.
     ///
     /// # Arguments
     /// - `ctx`: Context for `MakeOfferTwo`.
-    /// - `offer_id`: Unique ID for the offer.
     /// - `buy_token_1_total_amount`: Total amount of the first buy token offered.
     /// - `buy_token_2_total_amount`: Total amount of the second buy token offered.
     /// - `sell_token_start_amount`: Sell token amount at the start of the offer.
@@ -94,7 +153,6 @@ pub mod onre_app {
     /// - `price_fix_duration`: Duration of each price interval.
     pub fn make_offer_two(
         ctx: Context<MakeOfferTwo>,
-        offer_id: u64,
         buy_token_1_total_amount: u64,
         buy_token_2_total_amount: u64,
         sell_token_start_amount: u64,
@@ -105,7 +163,6 @@ pub mod onre_app {
     ) -> Result<()> {
         make_offer::make_offer_two(
             ctx,
-            offer_id,
             buy_token_1_total_amount,
             buy_token_2_total_amount,
             sell_token_start_amount,
@@ -132,9 +189,186 @@ pub mod onre_app {
         close_offer::close_offer_two(ctx)
     }
 
+    /// Closes an expired offer, callable by anyone.
+    ///
+    /// Delegates to `close_offer::close_expired_offer` to return the remaining tokens to the boss
+    /// recorded on the offer and close the offer.
+    /// Emits `TokensTransferred` and `ExpiredOfferClosed` events.
+    pub fn close_expired_offer(ctx: Context<CloseExpiredOffer>) -> Result<()> {
+        close_offer::close_expired_offer(ctx)
+    }
+
+    /// Sets the bounty paid to the caller of `close_expired_offer`.
+    ///
+    /// Delegates to `close_offer::set_crank_bounty`.
+    /// Emits a `CrankBountySet` event.
+    pub fn set_crank_bounty(ctx: Context<SetCrankBounty>, crank_bounty_lamports: u64) -> Result<()> {
+        close_offer::set_crank_bounty(ctx, crank_bounty_lamports)
+    }
+
+    /// Creates the offer registry of a state initialized before the registry existed.
+    ///
+    /// Delegates to `offer_registry::initialize_offer_registry`.
+    pub fn initialize_offer_registry(ctx: Context<InitializeOfferRegistry>) -> Result<()> {
+        offer_registry::initialize_offer_registry(ctx)
+    }
+
+    /// Moves the ID given to the next offer forward.
+    ///
+    /// Delegates to `offer_registry::set_next_offer_id`, e.g. to skip the IDs of offers made
+    /// before the state handed out offer IDs. Emits a `NextOfferIdSet` event.
+    pub fn set_next_offer_id(ctx: Context<SetNextOfferId>, next_offer_id: u64) -> Result<()> {
+        offer_registry::set_next_offer_id(ctx, next_offer_id)
+    }
+
+    /// Updates the end time and the price schedule of an offer.
+    ///
+    /// Delegates to `update_offer::update_offer`.
+    /// Emits an `OfferUpdated` event.
+    pub fn update_offer(
+        ctx: Context<UpdateOffer>,
+        sell_token_start_amount: u64,
+        sell_token_end_amount: u64,
+        offer_end_time: u64,
+        price_fix_duration: u64,
+    ) -> Result<()> {
+        update_offer::update_offer(
+            ctx,
+            sell_token_start_amount,
+            sell_token_end_amount,
+            offer_end_time,
+            price_fix_duration,
+        )
+    }
+
+    /// Adds buy tokens to an offer, scaling it so its price is unchanged.
+    ///
+    /// Delegates to `offer_inventory::top_up_offer`.
+    /// Emits an `OfferToppedUp` event.
+    pub fn top_up_offer(ctx: Context<TopUpOffer>, buy_token_1_amount: u64) -> Result<()> {
+        offer_inventory::top_up_offer(ctx, buy_token_1_amount)
+    }
+
+    /// Withdraws unsold buy tokens from an offer, scaling it so its price is unchanged.
+    ///
+    /// Delegates to `offer_inventory::withdraw_unsold`.
+    /// Emits an `UnsoldWithdrawn` event.
+    pub fn withdraw_unsold(ctx: Context<WithdrawUnsold>, buy_token_1_amount: u64) -> Result<()> {
+        offer_inventory::withdraw_unsold(ctx, buy_token_1_amount)
+    }
+
+    /// Sets the owner of the token accounts receiving withdrawn proceeds.
+    ///
+    /// Delegates to `proceeds::set_treasury`.
+    /// Emits a `TreasurySet` event.
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        proceeds::set_treasury(ctx, treasury)
+    }
+
+    /// Withdraws sale proceeds from an offer to the treasury.
+    ///
+    /// Delegates to `proceeds::withdraw_proceeds`.
+    /// Emits a `ProceedsWithdrawn` event.
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>, amount: u64) -> Result<()> {
+        proceeds::withdraw_proceeds(ctx, amount)
+    }
+
+    /// Sets whether every filler of an offer pays the clearing price.
+    ///
+    /// Delegates to `dutch_auction::set_offer_uniform_price`.
+    /// Emits an `OfferUniformPriceSet` event.
+    pub fn set_offer_uniform_price(ctx: Context<SetOfferUniformPrice>, uniform_price: bool) -> Result<()> {
+        dutch_auction::set_offer_uniform_price(ctx, uniform_price)
+    }
+
+    /// Fixes the clearing price of a uniform price offer and reserves the rebates of its fillers.
+    ///
+    /// Delegates to `dutch_auction::settle_auction`.
+    /// Emits an `AuctionSettled` event.
+    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
+        dutch_auction::settle_auction(ctx)
+    }
+
+    /// Claims the rebate of a filler of a settled uniform price offer.
+    ///
+    /// Delegates to `dutch_auction::claim_rebate`.
+    /// Emits a `RebateClaimed` event.
+    pub fn claim_rebate(ctx: Context<ClaimRebate>) -> Result<()> {
+        dutch_auction::claim_rebate(ctx)
+    }
+
+    /// Sets whether an offer collects subscriptions instead of being taken.
+    ///
+    /// Delegates to `subscription::set_offer_subscription`.
+    /// Emits an `OfferSubscriptionSet` event.
+    pub fn set_offer_subscription(ctx: Context<SetOfferSubscription>, subscription: bool) -> Result<()> {
+        subscription::set_offer_subscription(ctx, subscription)
+    }
+
+    /// Deposits sell tokens into the user's subscription to an offer.
+    ///
+    /// Delegates to `subscription::subscribe`.
+    /// Emits a `Subscribed` event.
+    pub fn subscribe(ctx: Context<Subscribe>, sell_token_amount: u64) -> Result<()> {
+        subscription::subscribe(ctx, sell_token_amount)
+    }
+
+    /// Claims the fill and the refund of a subscription once the offer has ended.
+    ///
+    /// Delegates to `subscription::claim_subscription`.
+    /// Emits a `SubscriptionClaimed` event.
+    pub fn claim_subscription(ctx: Context<ClaimSubscription>) -> Result<()> {
+        subscription::claim_subscription(ctx)
+    }
+
+    /// Sets the minimum raise of an offer, escrowing its takes until the offer ends.
+    ///
+    /// Delegates to `min_raise::set_offer_min_raise`.
+    /// Emits an `OfferMinRaiseSet` event.
+    pub fn set_offer_min_raise(ctx: Context<SetOfferMinRaise>, min_raise: u64) -> Result<()> {
+        min_raise::set_offer_min_raise(ctx, min_raise)
+    }
+
+    /// Delivers or refunds the escrowed takes of a user once a `min_raise` offer has ended.
+    ///
+    /// Delegates to `min_raise::claim_receipt`.
+    /// Emits a `ReceiptClaimed` event.
+    pub fn claim_receipt(ctx: Context<ClaimReceipt>) -> Result<()> {
+        min_raise::claim_receipt(ctx)
+    }
+
+    /// Sets the vesting schedule of an offer, locking the buy tokens of its takes.
+    ///
+    /// Delegates to `vesting::set_offer_vesting`.
+    /// Emits an `OfferVestingSet` event.
+    pub fn set_offer_vesting(
+        ctx: Context<SetOfferVesting>,
+        start_time: u64,
+        cliff_duration: u64,
+        duration: u64,
+    ) -> Result<()> {
+        vesting::set_offer_vesting(ctx, start_time, cliff_duration, duration)
+    }
+
+    /// Opens the vesting escrow of a user for a vesting offer.
+    ///
+    /// Delegates to `vesting::open_vesting_escrow`.
+    pub fn open_vesting_escrow(ctx: Context<OpenVestingEscrow>) -> Result<()> {
+        vesting::open_vesting_escrow(ctx)
+    }
+
+    /// Releases the buy tokens vested so far to the user.
+    ///
+    /// Delegates to `vesting::claim_vested`.
+    /// Emits a `VestedClaimed` event.
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        vesting::claim_vested(ctx)
+    }
+
     /// Initializes the program state.
     ///
-    /// Delegates to `initialize::initialize` to set the initial boss in the state account.
+    /// Delegates to `initialize::initialize` to set the initial boss in the state account and
+    /// create the empty offer registry.
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         initialize::initialize(ctx)
     }
@@ -142,20 +376,64 @@ pub mod onre_app {
     /// Initializes a permissionless account.
     ///
     /// Delegates to `initialize::initialize_permissionless_account` to create a new permissionless account.
-    /// The account is created as a PDA with the seed "permissionless-1".
+    /// The account is created as a PDA with the seeds "permissionless" and `index`.
     /// Only the boss can initialize permissionless accounts.
     pub fn initialize_permissionless_account(
         ctx: Context<InitializePermissionlessAccount>,
+        index: u64,
         name: String,
     ) -> Result<()> {
-        initialize::initialize_permissionless_account(ctx, name)
+        initialize::initialize_permissionless_account(ctx, index, name)
+    }
+
+    /// Enables or disables a permissionless account as a routing channel.
+    ///
+    /// Delegates to `permissionless_account::set_permissionless_account_enabled`.
+    /// Emits a `PermissionlessAccountEnabledSet` event.
+    pub fn set_permissionless_account_enabled(
+        ctx: Context<SetPermissionlessAccountEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        permissionless_account::set_permissionless_account_enabled(ctx, enabled)
     }
 
-    /// Updates the boss in the program state.
+    /// Sweeps the whole balance of an intermediary token account to the boss.
     ///
-    /// Delegates to `set_boss::set_boss` to change the boss, emitting a `BossUpdated` event.
-    pub fn set_boss(ctx: Context<SetBoss>, new_boss: Pubkey) -> Result<()> {
-        set_boss::set_boss(ctx, new_boss)
+    /// Delegates to `permissionless_account::sweep_intermediary`.
+    /// Only the boss can sweep intermediary accounts. Emits an `IntermediarySwept` event.
+    pub fn sweep_intermediary(ctx: Context<SweepIntermediary>) -> Result<()> {
+        permissionless_account::sweep_intermediary(ctx)
+    }
+
+    /// Closes a permissionless account and the empty intermediary token accounts passed as remaining accounts.
+    ///
+    /// Delegates to `permissionless_account::close_intermediary`.
+    /// Only the boss can close intermediaries. Emits an `IntermediaryClosed` event.
+    pub fn close_intermediary<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseIntermediary<'info>>,
+    ) -> Result<()> {
+        permissionless_account::close_intermediary(ctx)
+    }
+
+    /// Proposes a new boss, the first step of a boss handover.
+    ///
+    /// Delegates to `set_boss::propose_boss`, emitting a `BossUpdated` event of kind `Proposed`.
+    pub fn propose_boss(ctx: Context<ProposeBoss>, new_boss: Pubkey) -> Result<()> {
+        set_boss::propose_boss(ctx, new_boss)
+    }
+
+    /// Accepts a pending boss proposal, signed by the proposed boss.
+    ///
+    /// Delegates to `set_boss::accept_boss`, emitting a `BossUpdated` event of kind `Accepted`.
+    pub fn accept_boss(ctx: Context<AcceptBoss>) -> Result<()> {
+        set_boss::accept_boss(ctx)
+    }
+
+    /// Withdraws a pending boss proposal.
+    ///
+    /// Delegates to `set_boss::cancel_boss_proposal`, emitting a `BossUpdated` event of kind `Cancelled`.
+    pub fn cancel_boss_proposal(ctx: Context<CancelBossProposal>) -> Result<()> {
+        set_boss::cancel_boss_proposal(ctx)
     }
 
     /// Takes an offer with one buy token, respecting the current dynamic price.
@@ -197,11 +475,409 @@ pub mod onre_app {
     ///
     /// # Arguments
     /// - `ctx`: Context for `TakeOfferOnePermissionless`.
+    /// - `intermediary_index`: Index of the permissionless account to route through.
     /// - `sell_token_amount`: Amount of sell tokens the user provides.
     pub fn take_offer_one_permissionless(
         ctx: Context<TakeOfferOnePermissionless>,
+        intermediary_index: u64,
         sell_token_amount: u64,
     ) -> Result<()> {
-        take_offer_one_permissionless::take_offer_one_permissionless(ctx, sell_token_amount)
+        take_offer_one_permissionless::take_offer_one_permissionless(
+            ctx,
+            intermediary_index,
+            sell_token_amount,
+        )
+    }
+
+    /// Takes an offer with two buy tokens via permissionless route.
+    ///
+    /// Delegates to `take_offer_two_permissionless::take_offer_two_permissionless`.
+    /// Routes tokens from offer -> intermediary -> user like `take_offer_one_permissionless`,
+    /// with the economic outcome of `take_offer_two`.
+    /// Emits an `OfferTakenTwoPermissionless` event.
+    ///
+    /// # Arguments
+    /// - `ctx`: Context for `TakeOfferTwoPermissionless`.
+    /// - `intermediary_index`: Index of the permissionless account to route through.
+    /// - `sell_token_amount`: Amount of sell tokens the user provides.
+    pub fn take_offer_two_permissionless(
+        ctx: Context<TakeOfferTwoPermissionless>,
+        intermediary_index: u64,
+        sell_token_amount: u64,
+    ) -> Result<()> {
+        take_offer_two_permissionless::take_offer_two_permissionless(
+            ctx,
+            intermediary_index,
+            sell_token_amount,
+        )
+    }
+
+    /// Quotes an offer at the current price without taking it.
+    ///
+    /// Delegates to `quote_offer::quote_offer`. The quote is written as return data, so clients
+    /// can simulate the instruction for the interval, price, buy amounts and next price change.
+    ///
+    /// # Arguments
+    /// - `ctx`: Context containing the offer, the state and the optional NAV oracle.
+    /// - `amount`: Exact sell token amount to spend, or buy token 1 amount to receive.
+    pub fn quote_offer(ctx: Context<QuoteOffer>, amount: QuoteAmount) -> Result<OfferQuote> {
+        quote_offer::quote_offer(ctx, amount)
+    }
+
+    /// Creates the NAV oracle of a pool.
+    ///
+    /// Delegates to `nav_oracle::initialize_nav_oracle`.
+    /// Only the boss can create oracles. Emits a `NavOracleInitialized` event.
+    ///
+    /// # Arguments
+    /// - `ctx`: Context for `InitializeNavOracle`.
+    /// - `pool_id`: Identifier of the pool, used in PDA derivation.
+    /// - `oracle_authority`: Key allowed to publish NAV updates.
+    /// - `max_staleness`: Maximum age in seconds of a NAV that offers still accept.
+    pub fn initialize_nav_oracle(
+        ctx: Context<InitializeNavOracle>,
+        pool_id: u64,
+        oracle_authority: Pubkey,
+        max_staleness: u64,
+    ) -> Result<()> {
+        nav_oracle::initialize_nav_oracle(ctx, pool_id, oracle_authority, max_staleness)
+    }
+
+    /// Publishes a new NAV for a pool.
+    ///
+    /// Delegates to `nav_oracle::update_nav`.
+    /// Only the oracle authority can publish. Emits a `NavUpdated` event.
+    pub fn update_nav(ctx: Context<UpdateNav>, nav: u64) -> Result<()> {
+        nav_oracle::update_nav(ctx, nav)
+    }
+
+    /// Reconfigures a NAV oracle.
+    ///
+    /// Delegates to `nav_oracle::configure_nav_oracle` to rotate the oracle authority, change the
+    /// staleness window or (de)activate the pool. Emits a `NavOracleConfigured` event.
+    pub fn configure_nav_oracle(
+        ctx: Context<ConfigureNavOracle>,
+        oracle_authority: Pubkey,
+        max_staleness: u64,
+        is_active: bool,
+    ) -> Result<()> {
+        nav_oracle::configure_nav_oracle(ctx, oracle_authority, max_staleness, is_active)
+    }
+
+    /// Switches an offer to or from NAV based pricing.
+    ///
+    /// Delegates to `nav_oracle::set_offer_nav_pricing`.
+    /// Passing a NAV oracle prices the offer at NAV plus `nav_spread_bps`; omitting it restores
+    /// the interval schedule. Emits an `OfferNavPricingSet` event.
+    pub fn set_offer_nav_pricing(
+        ctx: Context<SetOfferNavPricing>,
+        nav_spread_bps: i16,
+    ) -> Result<()> {
+        nav_oracle::set_offer_nav_pricing(ctx, nav_spread_bps)
+    }
+
+    /// Creates a redemption offer.
+    ///
+    /// Delegates to `redemption_offer::make_redemption_offer`.
+    /// The boss escrows payout tokens that users claim by tendering redeem tokens, at a scheduled
+    /// or NAV based price and within a per-epoch cap. Emits a `RedemptionOfferMade` event.
+    ///
+    /// # Arguments
+    /// - `ctx`: Context for `MakeRedemptionOffer`.
+    /// - `offer_id`: Unique ID for the redemption offer.
+    /// - `args`: Payout amount, price schedule, NAV spread and epochs of the redemption offer.
+    pub fn make_redemption_offer(
+        ctx: Context<MakeRedemptionOffer>,
+        offer_id: u64,
+        args: MakeRedemptionOfferArgs,
+    ) -> Result<()> {
+        redemption_offer::make_redemption_offer(ctx, offer_id, args)
+    }
+
+    /// Redeems tokens through a redemption offer.
+    ///
+    /// Delegates to `redemption_offer::take_redemption_offer`.
+    /// Tendered tokens are burned if the program holds the mint authority, or escrowed for the boss
+    /// otherwise. Emits a `RedemptionOfferTaken` event.
+    pub fn take_redemption_offer(
+        ctx: Context<TakeRedemptionOffer>,
+        redeem_token_amount: u64,
+    ) -> Result<()> {
+        redemption_offer::take_redemption_offer(ctx, redeem_token_amount)
+    }
+
+    /// Closes a redemption offer.
+    ///
+    /// Delegates to `redemption_offer::close_redemption_offer` to return the remaining liquidity
+    /// and escrowed tokens to the boss. Emits a `RedemptionOfferClosed` event.
+    pub fn close_redemption_offer(ctx: Context<CloseRedemptionOffer>) -> Result<()> {
+        redemption_offer::close_redemption_offer(ctx)
+    }
+
+    /// Creates a redemption queue.
+    ///
+    /// Delegates to `redemption_queue::initialize_redemption_queue`.
+    /// Opens the first epoch of the queue. Emits a `RedemptionQueueInitialized` event.
+    pub fn initialize_redemption_queue(
+        ctx: Context<InitializeRedemptionQueue>,
+        queue_id: u64,
+    ) -> Result<()> {
+        redemption_queue::initialize_redemption_queue(ctx, queue_id)
+    }
+
+    /// Locks redeem tokens into the current epoch of a redemption queue.
+    ///
+    /// Delegates to `redemption_queue::request_redemption`.
+    /// Emits a `RedemptionRequested` event.
+    pub fn request_redemption(ctx: Context<RequestRedemption>, amount: u64) -> Result<()> {
+        redemption_queue::request_redemption(ctx, amount)
+    }
+
+    /// Cancels a redemption request of an unsettled epoch.
+    ///
+    /// Delegates to `redemption_queue::cancel_redemption`.
+    /// Emits a `RedemptionCancelled` event.
+    pub fn cancel_redemption(ctx: Context<CancelRedemption>) -> Result<()> {
+        redemption_queue::cancel_redemption(ctx)
+    }
+
+    /// Settles the current epoch of a redemption queue.
+    ///
+    /// Delegates to `redemption_queue::settle_redemption_epoch`.
+    /// Requests of the epoch are filled pro-rata to what `payout_token_amount` buys at `price`.
+    /// Emits a `RedemptionEpochSettled` event.
+    pub fn settle_redemption_epoch(
+        ctx: Context<SettleRedemptionEpoch>,
+        payout_token_amount: u64,
+        price: u64,
+    ) -> Result<()> {
+        redemption_queue::settle_redemption_epoch(ctx, payout_token_amount, price)
+    }
+
+    /// Claims a settled redemption request.
+    ///
+    /// Delegates to `redemption_queue::claim_redemption`.
+    /// Pays out the filled part and returns the unfilled part. Emits a `RedemptionClaimed` event.
+    pub fn claim_redemption(ctx: Context<ClaimRedemption>) -> Result<()> {
+        redemption_queue::claim_redemption(ctx)
+    }
+
+    /// Migrates the program state to the current layout.
+    ///
+    /// Delegates to `migrate_state::migrate_state`. Resizes the state account and sets defaults
+    /// for fields added since it was created.
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        migrate_state::migrate_state(ctx)
+    }
+
+    /// Migrates an offer to the current layout.
+    ///
+    /// Delegates to `migrate_state::migrate_offer`. Resizes the offer account and sets defaults
+    /// for fields added since it was made.
+    pub fn migrate_offer(ctx: Context<MigrateOffer>) -> Result<()> {
+        migrate_state::migrate_offer(ctx)
+    }
+
+    /// Creates or updates the KYC records of a batch of wallets.
+    ///
+    /// Delegates to `kyc::approve_kyc`. The records are passed as remaining accounts.
+    /// Emits a `KycApproved` event per wallet.
+    pub fn approve_kyc<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveKyc<'info>>,
+        wallets: Vec<Pubkey>,
+        level: u8,
+        expiry: u64,
+        jurisdiction: u16,
+    ) -> Result<()> {
+        kyc::approve_kyc(ctx, wallets, level, expiry, jurisdiction)
+    }
+
+    /// Revokes the KYC records of a batch of wallets.
+    ///
+    /// Delegates to `kyc::revoke_kyc`. The records are passed as remaining accounts.
+    /// Emits a `KycRevoked` event per wallet.
+    pub fn revoke_kyc<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevokeKyc<'info>>,
+        wallets: Vec<Pubkey>,
+    ) -> Result<()> {
+        kyc::revoke_kyc(ctx, wallets)
+    }
+
+    /// Sets the minimum KYC level required to take an offer.
+    ///
+    /// Delegates to `kyc::set_offer_min_kyc_level`.
+    /// Emits an `OfferMinKycLevelSet` event.
+    pub fn set_offer_min_kyc_level(
+        ctx: Context<SetOfferMinKycLevel>,
+        min_kyc_level: u8,
+    ) -> Result<()> {
+        kyc::set_offer_min_kyc_level(ctx, min_kyc_level)
+    }
+
+    /// Sets the purchase limits of an offer.
+    ///
+    /// Delegates to `offer_limits::set_offer_limits`. Limits are expressed in sell token amounts
+    /// and `0` disables a limit. Emits an `OfferLimitsSet` event.
+    pub fn set_offer_limits(
+        ctx: Context<SetOfferLimits>,
+        max_per_user: u64,
+        max_per_tx: u64,
+        min_per_tx: u64,
+        raise_cap: u64,
+    ) -> Result<()> {
+        offer_limits::set_offer_limits(ctx, max_per_user, max_per_tx, min_per_tx, raise_cap)
+    }
+
+    /// Pauses or unpauses taking all offers.
+    ///
+    /// Delegates to `pause::set_paused`.
+    /// Emits a `PausedSet` event.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        pause::set_paused(ctx, paused)
+    }
+
+    /// Pauses or unpauses taking a single offer.
+    ///
+    /// Delegates to `pause::set_offer_paused`.
+    /// Emits an `OfferPausedSet` event.
+    pub fn set_offer_paused(ctx: Context<SetOfferPaused>, paused: bool) -> Result<()> {
+        pause::set_offer_paused(ctx, paused)
+    }
+
+    /// Grants a role to a key.
+    ///
+    /// Delegates to `roles::grant_role`.
+    /// Emits a `RoleGranted` event.
+    pub fn grant_role(ctx: Context<UpdateRole>, role: Role, holder: Pubkey) -> Result<()> {
+        roles::grant_role(ctx, role, holder)
+    }
+
+    /// Revokes a role from a key.
+    ///
+    /// Delegates to `roles::revoke_role`.
+    /// Emits a `RoleRevoked` event.
+    pub fn revoke_role(ctx: Context<UpdateRole>, role: Role, holder: Pubkey) -> Result<()> {
+        roles::revoke_role(ctx, role, holder)
+    }
+
+    /// Creates the m-of-n multisig.
+    ///
+    /// Delegates to `multisig::create_multisig`.
+    /// Emits a `MultisigCreated` event.
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::create_multisig(ctx, signers, threshold)
+    }
+
+    /// Changes the multisig's signers and threshold, through an executed proposal.
+    ///
+    /// Delegates to `multisig::change_multisig`.
+    /// Emits a `MultisigChanged` event.
+    pub fn change_multisig(
+        ctx: Context<ChangeMultisig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::change_multisig(ctx, signers, threshold)
+    }
+
+    /// Creates a proposal to execute an onreapp instruction as the multisig authority.
+    ///
+    /// Delegates to `multisig::create_proposal`.
+    /// Emits a `ProposalCreated` event.
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        multisig::create_proposal(ctx, accounts, data)
+    }
+
+    /// Approves a proposal.
+    ///
+    /// Delegates to `multisig::approve_proposal`.
+    /// Emits a `ProposalApproved` event.
+    pub fn approve_proposal(ctx: Context<VoteProposal>) -> Result<()> {
+        multisig::approve_proposal(ctx)
+    }
+
+    /// Rejects a proposal.
+    ///
+    /// Delegates to `multisig::reject_proposal`.
+    /// Emits a `ProposalRejected` event.
+    pub fn reject_proposal(ctx: Context<VoteProposal>) -> Result<()> {
+        multisig::reject_proposal(ctx)
+    }
+
+    /// Executes a proposal approved by the threshold of signers.
+    ///
+    /// Delegates to `multisig::execute_proposal`. The proposed instruction's accounts are passed
+    /// as remaining accounts. Emits a `ProposalExecuted` event.
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        multisig::execute_proposal(ctx)
+    }
+
+    /// Closes an executed or rejected proposal, refunding its rent to the proposer.
+    ///
+    /// Delegates to `multisig::close_proposal`.
+    /// Emits a `ProposalClosed` event.
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        multisig::close_proposal(ctx)
+    }
+
+    /// Sets the protocol fee deducted from the sell tokens of every take.
+    ///
+    /// Delegates to `protocol_fee::set_protocol_fee`.
+    /// Emits a `ProtocolFeeSet` event.
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, fee_bps: u16) -> Result<()> {
+        protocol_fee::set_protocol_fee(ctx, fee_bps)
+    }
+
+    /// Overrides the protocol fee of an offer.
+    ///
+    /// Delegates to `protocol_fee::set_offer_fee`.
+    /// Emits an `OfferFeeSet` event.
+    pub fn set_offer_fee(ctx: Context<SetOfferFee>, fee_bps: Option<u16>) -> Result<()> {
+        protocol_fee::set_offer_fee(ctx, fee_bps)
+    }
+
+    /// Withdraws accumulated fees from a fee vault.
+    ///
+    /// Delegates to `protocol_fee::withdraw_fees`.
+    /// Emits a `FeesWithdrawn` event.
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        protocol_fee::withdraw_fees(ctx, amount)
+    }
+
+    /// Registers a distribution partner as referrer for a sell token.
+    ///
+    /// Delegates to `referral::register_referrer`.
+    /// Emits a `ReferrerRegistered` event.
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        referral::register_referrer(ctx)
+    }
+
+    /// Sets the referral fee of an offer.
+    ///
+    /// Delegates to `referral::set_offer_referral_fee`.
+    /// Emits an `OfferReferralFeeSet` event.
+    pub fn set_offer_referral_fee(
+        ctx: Context<SetOfferReferralFee>,
+        referral_fee_bps: u16,
+    ) -> Result<()> {
+        referral::set_offer_referral_fee(ctx, referral_fee_bps)
+    }
+
+    /// Claims the referral fees accrued by the signer.
+    ///
+    /// Delegates to `referral::claim_referral_fees`.
+    /// Emits a `ReferralFeesClaimed` event.
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        referral::claim_referral_fees(ctx)
     }
 }
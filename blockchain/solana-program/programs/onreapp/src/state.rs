@@ -16,6 +16,35 @@ use anchor_lang::prelude::*;
 /// - `price_fix_duration`: Duration in seconds for each fixed price interval.
 /// - `offer_start_time`: Unix timestamp when the offer becomes active.
 /// - `offer_end_time`: Unix timestamp when the offer expires.
+/// - `nav_oracle`: NAV oracle the offer is priced against, `Pubkey::default()` for the interval schedule.
+/// - `nav_spread_bps`: Signed spread in basis points applied on top of the NAV when `nav_oracle` is set.
+/// - `min_kyc_level`: Minimum KYC level required to take the offer, `0` if no KYC is required.
+/// - `max_per_user`: Maximum cumulative sell token amount a single user can pay, `0` if unlimited.
+/// - `max_per_tx`: Maximum sell token amount per take, `0` if unlimited.
+/// - `min_per_tx`: Minimum sell token amount per take, `0` if unlimited.
+/// - `raise_cap`: Maximum cumulative sell token amount the offer accepts, `0` if unlimited.
+/// - `total_raised`: Cumulative sell token amount paid into the offer.
+/// - `status`: Whether the offer can be taken or is paused, set via `set_offer_paused`.
+/// - `fee_bps`: Protocol fee in basis points overriding `State::fee_bps`, `None` to use the global fee.
+/// - `referral_fee_bps`: Referral fee in basis points credited to the referrer of a take, `0` if disabled.
+/// - `boss`: The boss when the offer was made, whose ATAs receive the remaining tokens of `close_expired_offer`.
+/// - `uniform_price`: Whether every filler pays the clearing price, with the difference rebated after `settle_auction`.
+/// - `clearing_sell_token_amount`: Sell token amount for `buy_token_1.amount` at the last take, the clearing price of a uniform price offer.
+/// - `auction_settled`: Whether `settle_auction` fixed the clearing price of a uniform price offer.
+/// - `auction_sell_token_total`: Cumulative sell token amount paid into a uniform price offer, net of fees.
+/// - `auction_buy_token_1_total`: Cumulative buy token 1 amount sold by a uniform price offer.
+/// - `subscription`: Whether the offer collects subscriptions filled pro-rata after it ends instead of being taken.
+/// - `total_subscribed`: Cumulative sell token amount deposited into the subscriptions of the offer.
+/// - `open_subscriptions`: Number of subscriptions not claimed yet.
+/// - `min_raise`: Minimum `total_raised` for the takes to settle, `0` if takes settle instantly.
+/// - `escrowed_buy_token_1_total`: Buy token 1 amount owed to takers of a `min_raise` offer and not claimed yet.
+/// - `escrowed_buy_token_2_total`: Buy token 2 amount owed to takers of a `min_raise` offer and not claimed yet.
+/// - `open_receipts`: Number of positions holding escrowed takes of a `min_raise` offer.
+/// - `vesting_start_time`: Unix timestamp the bought buy tokens start vesting from.
+/// - `vesting_cliff_duration`: Seconds after `vesting_start_time` before any buy tokens vest.
+/// - `vesting_duration`: Seconds after `vesting_start_time` until all buy tokens vested, `0` if takes are not vested.
+/// - `schedule_start_time`: Unix timestamp the price intervals are counted from after `update_offer`, `0` for `offer_start_time`.
+/// - `carried_sell_token_amount`: Sell token amount of the interval `update_offer` was called in, applied until `schedule_start_time`.
 #[account]
 #[derive(InitSpace)]
 pub struct Offer {
@@ -29,6 +58,35 @@ pub struct Offer {
     pub price_fix_duration: u64,
     pub offer_start_time: u64,
     pub offer_end_time: u64,
+    pub nav_oracle: Pubkey,
+    pub nav_spread_bps: i16,
+    pub min_kyc_level: u8,
+    pub max_per_user: u64,
+    pub max_per_tx: u64,
+    pub min_per_tx: u64,
+    pub raise_cap: u64,
+    pub total_raised: u64,
+    pub status: OfferStatus,
+    pub fee_bps: Option<u16>,
+    pub referral_fee_bps: u16,
+    pub boss: Pubkey,
+    pub uniform_price: bool,
+    pub clearing_sell_token_amount: u64,
+    pub auction_settled: bool,
+    pub auction_sell_token_total: u64,
+    pub auction_buy_token_1_total: u64,
+    pub subscription: bool,
+    pub total_subscribed: u64,
+    pub open_subscriptions: u64,
+    pub min_raise: u64,
+    pub escrowed_buy_token_1_total: u64,
+    pub escrowed_buy_token_2_total: u64,
+    pub open_receipts: u64,
+    pub vesting_start_time: u64,
+    pub vesting_cliff_duration: u64,
+    pub vesting_duration: u64,
+    pub schedule_start_time: u64,
+    pub carried_sell_token_amount: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -37,21 +95,480 @@ pub struct OfferToken {
     pub amount: u64,
 }
 
+/// Maximum number of role grants stored in `State::roles`.
+pub const MAX_ROLE_GRANTS: usize = 16;
+
+/// Roles that can be granted to keys other than the boss.
+///
+/// The boss implicitly holds every role.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Role {
+    /// Makes, updates and closes offers.
+    OfferManager,
+    /// Pauses and unpauses the program and offers.
+    Pauser,
+    /// Withdraws proceeds.
+    Treasurer,
+    /// Approves and revokes KYC records.
+    Compliance,
+    /// Grants and revokes roles.
+    Admin,
+}
+
+/// A role held by a key, stored in `State::roles`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct RoleGrant {
+    pub role: Role,
+    pub holder: Pubkey,
+}
+
+/// Status of an offer, stored in `Offer::status`.
+///
+/// Encoded as a single byte in place of the former `paused` flag, so offers paused before read as
+/// [`OfferStatus::Paused`].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OfferStatus {
+    /// The offer can be taken within its time window.
+    Active,
+    /// Taking the offer is halted via `set_offer_paused`.
+    Paused,
+}
+
 /// Represents the program state in the Onre App program.
 ///
 /// Stores the current boss's public key, used for authorization across instructions.
 ///
 /// # Fields
-/// - `boss`: Public key of the current boss, set via `initialize` and updated via `set_boss`.
+/// - `boss`: Public key of the current boss, set via `initialize` and handed over via `propose_boss` and `accept_boss`.
+/// - `paused`: Whether taking any offer is halted, set via `set_paused`.
+/// - `pending_boss`: Public key proposed as boss and yet to accept, `Pubkey::default()` if none.
+/// - `roles`: Roles granted to keys other than the boss, managed via `grant_role` and `revoke_role`.
+/// - `fee_bps`: Protocol fee in basis points deducted from the sell tokens of every take, set via `set_protocol_fee`.
+/// - `treasury`: Owner of the token accounts receiving withdrawn proceeds, set via `set_treasury`.
+/// - `crank_bounty_lamports`: Lamports of the offer rent paid to the caller of `close_expired_offer`, set via `set_crank_bounty`.
+/// - `next_offer_id`: ID given to the next offer made, incremented by `make_offer_one` and `make_offer_two`.
 #[account]
 #[derive(InitSpace)]
 pub struct State {
     pub boss: Pubkey,
+    pub paused: bool,
+    pub pending_boss: Pubkey,
+    #[max_len(MAX_ROLE_GRANTS)]
+    pub roles: Vec<RoleGrant>,
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    pub crank_bounty_lamports: u64,
+    pub next_offer_id: u64,
+}
+
+impl State {
+    /// Returns whether `key` holds `role`, either as the boss or through a grant.
+    pub fn has_role(&self, role: Role, key: &Pubkey) -> bool {
+        *key == self.boss
+            || self
+                .roles
+                .iter()
+                .any(|grant| grant.role == role && grant.holder == *key)
+    }
 }
 
+/// Index of the open offers, so clients can list them without scanning the offer IDs.
+///
+/// Derived from `["offer_registry"]`. The account grows by one ID as offers are made and shrinks
+/// as they are closed, each offer records its own `status`.
+///
+/// # Fields
+/// - `offer_ids`: IDs of the offers made and not closed yet, in the order they were made.
+#[account]
+pub struct OfferRegistry {
+    pub offer_ids: Vec<u64>,
+}
+
+impl OfferRegistry {
+    /// Returns the account size holding `offer_count` IDs, including the discriminator.
+    pub fn space(offer_count: usize) -> usize {
+        8 + 4 + 8 * offer_count
+    }
+
+    /// Returns the number of IDs left once `offer_id` is closed, offers made before the
+    /// registry existed are not listed.
+    pub fn offer_count_after_close(&self, offer_id: u64) -> usize {
+        self.offer_ids.len() - usize::from(self.offer_ids.contains(&offer_id))
+    }
+}
+
+/// Program-controlled intermediary routing permissionless takes, e.g. one per distribution partner.
+///
+/// Derived from `["permissionless", index]`. The PDA is the authority of the intermediary token
+/// accounts the takes route through.
+///
+/// # Fields
+/// - `name`: Display name of the routing channel.
+/// - `index`: Index the PDA is derived from.
+/// - `enabled`: Whether takes may route through the intermediary.
+/// - `bump`: Bump seed of the PDA.
 #[account]
 #[derive(InitSpace)]
 pub struct PermissionlessAccount {
     #[max_len(50)]
     pub name: String,
+    pub index: u64,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+/// Precision of `NavOracle::nav`: the NAV is the amount of sell token base units paid for
+/// `NAV_PRECISION` base units of the pool token.
+pub const NAV_PRECISION: u64 = 1_000_000_000;
+
+/// Denominator used for all basis point values.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum protocol fee in basis points.
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Represents the on-chain NAV (net asset value) feed of an insurance pool.
+///
+/// Published by the oracle authority and read by offers that are priced at NAV instead of
+/// the interval schedule.
+///
+/// # Fields
+/// - `pool_id`: Identifier of the pool, used in PDA derivation.
+/// - `oracle_authority`: Key allowed to publish NAV updates.
+/// - `nav`: Latest NAV, in sell token base units per `NAV_PRECISION` pool token base units.
+/// - `last_update_time`: Unix timestamp of the latest NAV update.
+/// - `max_staleness`: Maximum age in seconds of the NAV before it is rejected by offers.
+/// - `is_active`: Whether the pool is active; offers reject trades against a deactivated pool.
+/// - `bump`: Bump seed of the oracle PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct NavOracle {
+    pub pool_id: u64,
+    pub oracle_authority: Pubkey,
+    pub nav: u64,
+    pub last_update_time: u64,
+    pub max_staleness: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+/// Represents a redemption offer in the Onre App program.
+///
+/// The mirror image of `Offer`: the boss escrows payout tokens (e.g. USDC) and users tender
+/// redeem tokens (e.g. ONyc) in exchange. Shares the `offer_authority` PDA pattern of `Offer`,
+/// under the `"redemption_offer"` and `"redemption_offer_authority"` seeds.
+///
+/// # Fields
+/// - `offer_id`: Unique identifier for the redemption offer.
+/// - `redeem_token_mint`: Mint of the token users tender.
+/// - `payout_token_mint`: Mint of the token users receive.
+/// - `price_start`: Payout token base units per `NAV_PRECISION` redeem token base units in the first interval.
+/// - `price_end`: Payout token base units per `NAV_PRECISION` redeem token base units in the last interval.
+/// - `price_fix_duration`: Duration in seconds for each fixed price interval.
+/// - `offer_start_time`: Unix timestamp when the redemption offer becomes active.
+/// - `offer_end_time`: Unix timestamp when the redemption offer expires.
+/// - `nav_oracle`: NAV oracle the payout is priced against, `Pubkey::default()` for the interval schedule.
+/// - `nav_spread_bps`: Signed spread in basis points applied on top of the NAV when `nav_oracle` is set.
+/// - `epoch_duration`: Duration in seconds of a redemption epoch, counted from `offer_start_time`.
+/// - `epoch_cap`: Maximum amount of redeem tokens accepted per epoch.
+/// - `current_epoch`: Index of the epoch `epoch_redeemed` refers to.
+/// - `epoch_redeemed`: Amount of redeem tokens tendered during `current_epoch`.
+/// - `authority_bump`: Bump seed for the redemption offer's token authority PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionOffer {
+    pub offer_id: u64,
+    pub redeem_token_mint: Pubkey,
+    pub payout_token_mint: Pubkey,
+    pub price_start: u64,
+    pub price_end: u64,
+    pub price_fix_duration: u64,
+    pub offer_start_time: u64,
+    pub offer_end_time: u64,
+    pub nav_oracle: Pubkey,
+    pub nav_spread_bps: i16,
+    pub epoch_duration: u64,
+    pub epoch_cap: u64,
+    pub current_epoch: u64,
+    pub epoch_redeemed: u64,
+    pub authority_bump: u8,
+}
+
+/// Represents a redemption queue in the Onre App program.
+///
+/// Users lock redeem tokens into per-user `RedemptionRequest` accounts; at the end of each epoch
+/// the boss funds a settlement that fills the epoch's requests pro-rata.
+///
+/// # Fields
+/// - `queue_id`: Unique identifier for the queue, used in PDA derivation.
+/// - `redeem_token_mint`: Mint of the token users lock.
+/// - `payout_token_mint`: Mint of the token settlements are paid in.
+/// - `current_epoch`: Epoch currently accepting requests.
+/// - `authority_bump`: Bump seed for the queue's token authority PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionQueue {
+    pub queue_id: u64,
+    pub redeem_token_mint: Pubkey,
+    pub payout_token_mint: Pubkey,
+    pub current_epoch: u64,
+    pub authority_bump: u8,
+}
+
+/// Represents one epoch of a redemption queue.
+///
+/// # Fields
+/// - `queue`: The redemption queue the epoch belongs to.
+/// - `epoch`: Index of the epoch.
+/// - `total_requested`: Redeem tokens locked by the epoch's requests.
+/// - `filled_amount`: Redeem tokens filled by the settlement.
+/// - `price`: Payout token base units per `NAV_PRECISION` redeem token base units used by the settlement.
+/// - `is_settled`: Whether the epoch has been settled.
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionEpoch {
+    pub queue: Pubkey,
+    pub epoch: u64,
+    pub total_requested: u64,
+    pub filled_amount: u64,
+    pub price: u64,
+    pub is_settled: bool,
+}
+
+/// Represents the pending redemption request of a user in a redemption queue.
+///
+/// # Fields
+/// - `queue`: The redemption queue the request belongs to.
+/// - `user`: Owner of the request.
+/// - `epoch`: Epoch the request takes part in.
+/// - `amount`: Redeem tokens locked by the request.
+#[account]
+#[derive(InitSpace)]
+pub struct RedemptionRequest {
+    pub queue: Pubkey,
+    pub user: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+/// KYC status of a wallet, managed by holders of the compliance role.
+///
+/// Derived from `["kyc", wallet]`. Offers with a non-zero `min_kyc_level` can only be taken
+/// by wallets holding a record with at least that level which has not expired.
+///
+/// # Fields
+/// - `wallet`: Wallet the record belongs to.
+/// - `level`: KYC level of the wallet, `0` once revoked.
+/// - `expiry`: Unix timestamp after which the record is no longer valid, `0` if it never expires.
+/// - `jurisdiction`: ISO 3166-1 numeric code of the wallet owner's jurisdiction.
+/// - `bump`: Bump seed of the record PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct KycRecord {
+    pub wallet: Pubkey,
+    pub level: u8,
+    pub expiry: u64,
+    pub jurisdiction: u16,
+    pub bump: u8,
+}
+
+/// Cumulative purchases of a user from an offer.
+///
+/// Derived from `["position", offer, user]` and created on the user's first take. For `min_raise`
+/// offers it is also the user's receipt of the takes escrowed until the offer ends.
+///
+/// # Fields
+/// - `offer`: The offer the position belongs to.
+/// - `user`: The user the position belongs to.
+/// - `sell_token_amount`: Cumulative sell token amount the user paid into the offer.
+/// - `bump`: Bump seed of the position PDA.
+/// - `auction_sell_token_amount`: Cumulative sell token amount the user paid into a uniform price offer, net of fees.
+/// - `auction_buy_token_1_amount`: Cumulative buy token 1 amount the user bought from a uniform price offer.
+/// - `rebate_claimed`: Whether the user claimed their rebate via `claim_rebate`.
+/// - `escrowed_sell_token_amount`: Sell token amount paid into a `min_raise` offer, refunded in full if the raise fails.
+/// - `escrowed_buy_token_1_amount`: Buy token 1 amount bought from a `min_raise` offer and not claimed yet.
+/// - `escrowed_buy_token_2_amount`: Buy token 2 amount bought from a `min_raise` offer and not claimed yet.
+#[account]
+#[derive(InitSpace)]
+pub struct Position {
+    pub offer: Pubkey,
+    pub user: Pubkey,
+    pub sell_token_amount: u64,
+    pub bump: u8,
+    pub auction_sell_token_amount: u64,
+    pub auction_buy_token_1_amount: u64,
+    pub rebate_claimed: bool,
+    pub escrowed_sell_token_amount: u64,
+    pub escrowed_buy_token_1_amount: u64,
+    pub escrowed_buy_token_2_amount: u64,
+}
+
+/// Sell tokens deposited by a user into a subscription offer.
+///
+/// Derived from `["subscription", offer, user]`, created on the user's first deposit and closed
+/// when the fill is claimed after the offer ends.
+///
+/// # Fields
+/// - `offer`: The offer the subscription belongs to.
+/// - `user`: The user the subscription belongs to.
+/// - `sell_token_amount`: Cumulative sell token amount the user deposited.
+/// - `bump`: Bump seed of the subscription PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub offer: Pubkey,
+    pub user: Pubkey,
+    pub sell_token_amount: u64,
+    pub bump: u8,
+}
+
+/// Buy tokens bought by a user from a vesting offer, released over the offer's vesting schedule.
+///
+/// Derived from `["vesting_escrow", offer, user]` and opened by the user before their first take.
+/// The locked tokens are held in the associated token accounts of this PDA.
+///
+/// # Fields
+/// - `offer`: The offer the tokens were bought from.
+/// - `user`: The user the escrow belongs to.
+/// - `buy_token_1_mint`: Mint of the offer's buy token 1.
+/// - `buy_token_2_mint`: Mint of the offer's buy token 2, `Pubkey::default()` for offers with one buy token.
+/// - `start_time`: Unix timestamp the tokens start vesting from.
+/// - `cliff_duration`: Seconds after `start_time` before any tokens vest.
+/// - `duration`: Seconds after `start_time` until all tokens vested.
+/// - `buy_token_1_locked`: Cumulative buy token 1 amount locked.
+/// - `buy_token_1_claimed`: Cumulative buy token 1 amount claimed.
+/// - `buy_token_2_locked`: Cumulative buy token 2 amount locked.
+/// - `buy_token_2_claimed`: Cumulative buy token 2 amount claimed.
+/// - `bump`: Bump seed of the vesting escrow PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+    pub offer: Pubkey,
+    pub user: Pubkey,
+    pub buy_token_1_mint: Pubkey,
+    pub buy_token_2_mint: Pubkey,
+    pub start_time: u64,
+    pub cliff_duration: u64,
+    pub duration: u64,
+    pub buy_token_1_locked: u64,
+    pub buy_token_1_claimed: u64,
+    pub buy_token_2_locked: u64,
+    pub buy_token_2_claimed: u64,
+    pub bump: u8,
+}
+
+/// Clearing price of a settled uniform price offer.
+///
+/// Derived from `["auction_settlement", offer]` and created by `settle_auction`. The rebates owed
+/// to the fillers are moved to the rebate vault, the `sell_token_mint` associated token account
+/// of this PDA, so they stay claimable after the offer is closed. If the offer couldn't cover
+/// every rebate, each filler is paid their share of the reserve pro-rata to what they are owed.
+///
+/// # Fields
+/// - `offer`: The settled offer.
+/// - `sell_token_mint`: Mint the rebates are paid in.
+/// - `clearing_sell_token_amount`: Sell token amount for `buy_token_1_amount` every filler pays.
+/// - `buy_token_1_amount`: `Offer::buy_token_1.amount` at settlement.
+/// - `rebate_reserve`: Rebates moved to the rebate vault and not claimed yet.
+/// - `rebates_owed`: Rebates owed at the clearing price to the fillers that haven't claimed yet.
+/// - `bump`: Bump seed of the settlement PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionSettlement {
+    pub offer: Pubkey,
+    pub sell_token_mint: Pubkey,
+    pub clearing_sell_token_amount: u64,
+    pub buy_token_1_amount: u64,
+    pub rebate_reserve: u64,
+    pub rebates_owed: u64,
+    pub bump: u8,
+}
+
+/// Referral fees accrued by a referrer in one sell token.
+///
+/// Derived from `["referrer", referrer, mint]` and registered by an admin. The accrued tokens
+/// are held in the referral vault, the `mint` associated token account of the
+/// `["referral_vault_authority"]` PDA, until claimed.
+///
+/// # Fields
+/// - `referrer`: The referrer the account belongs to.
+/// - `mint`: Sell token mint the fees are accrued in.
+/// - `balance`: Accrued fees not claimed yet.
+/// - `total_earned`: Cumulative fees accrued by the referrer.
+/// - `bump`: Bump seed of the referrer account PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerAccount {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub total_earned: u64,
+    pub bump: u8,
+}
+
+/// Maximum number of signers of the multisig.
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Maximum length of the instruction data held by a proposal.
+pub const MAX_PROPOSAL_DATA_LEN: usize = 512;
+
+/// Maximum number of accounts of the instruction held by a proposal.
+pub const MAX_PROPOSAL_ACCOUNTS: usize = 24;
+
+/// Represents the m-of-n multisig able to act as the boss.
+///
+/// Derived from `["multisig"]`. Executed proposals sign as the `["multisig_authority"]` PDA, which
+/// is meant to be handed the boss key via `propose_boss` and an executed `accept_boss` proposal.
+///
+/// # Fields
+/// - `signers`: Keys allowed to create, approve and reject proposals.
+/// - `threshold`: Number of approvals needed to execute a proposal.
+/// - `proposal_count`: Number of proposals created so far, used as the next proposal's ID.
+/// - `authority_bump`: Bump seed of the multisig authority PDA.
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64,
+    pub authority_bump: u8,
+}
+
+/// Account of the instruction held by a proposal.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// Represents a proposal to execute a privileged onreapp instruction as the multisig authority.
+///
+/// Derived from `["proposal", multisig, proposal_id]`.
+///
+/// # Fields
+/// - `multisig`: The multisig the proposal belongs to.
+/// - `proposal_id`: Index of the proposal within the multisig.
+/// - `proposer`: Signer that created the proposal.
+/// - `accounts`: Accounts of the onreapp instruction.
+/// - `data`: Serialized onreapp instruction, including its discriminator.
+/// - `approvals`: Signers that approved the proposal.
+/// - `rejections`: Signers that rejected the proposal.
+/// - `executed`: Whether the proposal has been executed.
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    #[max_len(MAX_PROPOSAL_ACCOUNTS)]
+    pub accounts: Vec<ProposalAccount>,
+    #[max_len(MAX_PROPOSAL_DATA_LEN)]
+    pub data: Vec<u8>,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub approvals: Vec<Pubkey>,
+    #[max_len(MAX_MULTISIG_SIGNERS)]
+    pub rejections: Vec<Pubkey>,
+    pub executed: bool,
 }
@@ -0,0 +1,53 @@
+// acceptBoss.ts
+// Accepts the boss proposal made with set-boss.ts. Must be signed by NEW_BOSS.
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import bs58 from 'bs58';
+
+import { initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
+
+const NEW_BOSS = new PublicKey('9tTUg7r9ftofzoPXKeUPB35oN4Lm8KkrVDVQbbM7Xzxx'); // Replace with the proposed boss
+
+async function createAcceptBossTransaction() {
+    const program = await initProgram();
+    const connection = new anchor.web3.Connection(RPC_URL);
+
+    const [statePda, _bump] = PublicKey.findProgramAddressSync([Buffer.from('state')], PROGRAM_ID);
+
+    try {
+        const tx = await program.methods
+            .acceptBoss()
+            .accountsPartial({
+                state: statePda,
+                newBoss: NEW_BOSS,
+            })
+            .transaction();
+
+        tx.feePayer = NEW_BOSS;
+        tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+        const serializedTx = tx.serialize({
+            requireAllSignatures: false,
+            verifySignatures: false,
+        });
+
+        const base58Tx = bs58.encode(serializedTx);
+        console.log('Accept Boss Transaction (Base58):');
+        console.log(base58Tx);
+
+        return base58Tx;
+    } catch (error) {
+        console.error('Error creating transaction:', error);
+        throw error;
+    }
+}
+
+async function main() {
+    try {
+        await createAcceptBossTransaction();
+    } catch (error) {
+        console.error('Failed to create accept boss transaction:', error);
+    }
+}
+
+await main();
@@ -41,6 +41,7 @@ async function closeOffer() {
                 state: statePda,
                 offerTokenAuthority: offerAuthority,
                 boss: BOSS,
+                authority: BOSS,
             })
             .transaction();
 
@@ -10,7 +10,8 @@ import { initProgram, PROGRAM_ID, RPC_URL } from "./script-commons";
 // TEST & local
 const BOSS = new PublicKey("7rzEKejyAXJXMkGfRhMV9Vg1k7tFznBBEFu3sfLNz8LC"); // DEV Squad
 
-// Configure the permissionless account name here
+// Configure the permissionless account index and name here
+const PERMISSIONLESS_ACCOUNT_INDEX = new anchor.BN(0);
 const PERMISSIONLESS_ACCOUNT_NAME = "ON Technologies Corporation";
 
 async function createInitializePermissionlessTransaction() {
@@ -19,13 +20,13 @@ async function createInitializePermissionlessTransaction() {
 
     const [statePda] = PublicKey.findProgramAddressSync([Buffer.from("state")], PROGRAM_ID);
     const [permissionlessAccountPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('permissionless-1')],
+        [Buffer.from('permissionless'), PERMISSIONLESS_ACCOUNT_INDEX.toArrayLike(Buffer, 'le', 8)],
         PROGRAM_ID
     );
 
     try {
         const tx = await program.methods
-            .initializePermissionlessAccount(PERMISSIONLESS_ACCOUNT_NAME)
+            .initializePermissionlessAccount(PERMISSIONLESS_ACCOUNT_INDEX, PERMISSIONLESS_ACCOUNT_NAME)
             .accountsPartial({
                 permissionlessAccount: permissionlessAccountPda,
                 state: statePda,
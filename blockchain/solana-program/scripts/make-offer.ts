@@ -8,7 +8,7 @@ import {
 import bs58 from 'bs58';
 import { BN } from 'bn.js';
 
-import { getBossAccount, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
+import { getBossAccount, getNextOfferId, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
 
 // PROD
 const SELL_TOKEN_MINT = new PublicKey('EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v'); // USDC Mint Address
@@ -22,7 +22,7 @@ async function createMakeOfferOneTransaction() {
     const program = await initProgram();
     const connection = new anchor.web3.Connection(RPC_URL);
 
-    const offerId = 1;
+    const offerId = await getNextOfferId(program);
     const buyTokenAmount =       '20000000000000000';   // 9 decimals for ONe
     const sellTokenStartAmount = '20160514420000';      // 6 decimals for USDC
     const sellTokenEndAmount =   '20340430000000';      // 6 decimals for USDC
@@ -31,11 +31,12 @@ async function createMakeOfferOneTransaction() {
     const priceFixDuration = 60 * 60 * 24; // 1 day
 
     const [offerAuthority] = PublicKey.findProgramAddressSync(
-        [Buffer.from('offer_authority'), new BN(offerId).toArrayLike(Buffer, 'le', 8)],
+        [Buffer.from('offer_authority'), offerId.toArrayLike(Buffer, 'le', 8)],
         program.programId,
     );
 
     console.log('programId:', program.programId.toBase58());
+    console.log('offerId:', offerId.toString());
     console.log('offerAuthority:', offerAuthority.toBase58());
 
     const BOSS = await getBossAccount(program);
@@ -60,13 +61,12 @@ async function createMakeOfferOneTransaction() {
     const [statePda] = PublicKey.findProgramAddressSync([Buffer.from('state')], PROGRAM_ID);
 
     const [offerPda] = PublicKey.findProgramAddressSync(
-        [Buffer.from('offer'), new BN(offerId).toArrayLike(Buffer, 'le', 8)],
+        [Buffer.from('offer'), offerId.toArrayLike(Buffer, 'le', 8)],
         PROGRAM_ID,
     );
     try {
         const tx = await program.methods
             .makeOfferOne(
-              new BN(offerId), 
               new BN(buyTokenAmount),
               new BN(sellTokenStartAmount),
               new BN(sellTokenEndAmount),
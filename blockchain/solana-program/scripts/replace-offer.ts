@@ -7,12 +7,11 @@ import {
 import bs58 from 'bs58';
 import { BN } from 'bn.js';
 
-import { getBossAccount, getOffer, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
+import { getBossAccount, getNextOfferId, getOffer, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
 import { PublicKey } from '@solana/web3.js';
 
 async function createMakeOfferOneTransaction() {
     const oldOfferId = new BN(1);
-    const offerId = new BN(1);
 
     const buyTokenAmount =       new BN('20000000000000000');                   // 9 decimals for ONyc
     const sellTokenStartAmount = new BN('20988976000000');                      // 6 decimals for USDC
@@ -25,6 +24,8 @@ async function createMakeOfferOneTransaction() {
     const connection = new anchor.web3.Connection(RPC_URL);
 
     const BOSS = await getBossAccount(program);
+    const offerId = await getNextOfferId(program);
+    console.log('offerId:', offerId.toString());
     const offer = await getOffer(oldOfferId, program);
 
     const [oldOfferAuthority] = PublicKey.findProgramAddressSync(
@@ -73,12 +74,12 @@ async function createMakeOfferOneTransaction() {
                 state: statePda,
                 offerTokenAuthority: oldOfferAuthority,
                 boss: BOSS,
+                authority: BOSS,
             })
             .instruction();
 
         const tx = await program.methods
             .makeOfferOne(
-              offerId,
               buyTokenAmount,
               sellTokenStartAmount,
               sellTokenEndAmount,
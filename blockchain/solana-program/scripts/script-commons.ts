@@ -30,6 +30,14 @@ export async function getBossAccount(program: Program<OnreApp>) {
     return stateAccount.boss;
 }
 
+export async function getNextOfferId(program: Program<OnreApp>) {
+    const [statePda] = PublicKey.findProgramAddressSync([Buffer.from("state")], PROGRAM_ID);
+
+    const stateAccount = await program.account.state.fetch(statePda);
+
+    return stateAccount.nextOfferId;
+}
+
 export async function getOffer(offerId: BN, program: Program<OnreApp>) {
     const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], PROGRAM_ID);
 
@@ -1,6 +1,7 @@
 // setBoss.ts
+// Proposes NEW_BOSS as boss. The handover completes once NEW_BOSS signs the transaction from accept-boss.ts.
 import * as anchor from '@coral-xyz/anchor';
-import { PublicKey, SystemProgram } from '@solana/web3.js';
+import { PublicKey } from '@solana/web3.js';
 import bs58 from 'bs58';
 
 import { getBossAccount, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
@@ -17,11 +18,10 @@ async function createSetBossTransaction() {
 
     try {
         const tx = await program.methods
-            .setBoss(NEW_BOSS)
+            .proposeBoss(NEW_BOSS)
             .accountsPartial({
                 state: statePda,
                 boss: BOSS,
-                systemProgram: SystemProgram.programId,
             })
             .transaction();
 
@@ -34,7 +34,7 @@ async function createSetBossTransaction() {
         });
 
         const base58Tx = bs58.encode(serializedTx);
-        console.log('Set Boss Transaction (Base58):');
+        console.log('Propose Boss Transaction (Base58):');
         console.log(base58Tx);
 
         return base58Tx;
@@ -0,0 +1,66 @@
+import { PublicKey } from '@solana/web3.js';
+import { BN } from 'bn.js';
+
+import bs58 from 'bs58';
+import { getBossAccount, initProgram, PROGRAM_ID, RPC_URL } from './script-commons';
+import { web3 } from '@coral-xyz/anchor';
+
+async function createUpdateOfferTransaction() {
+    const offerId = new BN(1);
+
+    const sellTokenStartAmount = new BN('20988976000000');                      // 6 decimals for USDC
+    const sellTokenEndAmount =   new BN('21191718880000');                      // 6 decimals for USDC
+    const offerEndTime = Math.floor(new Date(2025, 11, 19).getTime() / 1000);   // December 19, 2025
+    const priceFixDuration = new BN(60 * 60 * 24); // 1 day
+
+    const connection = new web3.Connection(RPC_URL);
+
+    const program = await initProgram();
+    const BOSS = await getBossAccount(program);
+
+    // Derive the state PDA
+    const [statePda] = PublicKey.findProgramAddressSync([Buffer.from('state')], PROGRAM_ID);
+
+    const [offerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from('offer'), offerId.toArrayLike(Buffer, 'le', 8)],
+        PROGRAM_ID,
+    );
+
+    try {
+        const tx = await program.methods
+            .updateOffer(sellTokenStartAmount, sellTokenEndAmount, new BN(offerEndTime), priceFixDuration)
+            .accountsPartial({
+                offer: offerPda,
+                state: statePda,
+                authority: BOSS,
+            })
+            .transaction();
+
+        tx.feePayer = BOSS;
+        tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+        const serializedTx = tx.serialize({
+            requireAllSignatures: false,
+            verifySignatures: false,
+        });
+
+        const base58Tx = bs58.encode(serializedTx);
+        console.log('Update Offer Transaction (Base58):');
+        console.log(base58Tx);
+
+        return base58Tx;
+    } catch (error) {
+        console.error('Error creating transaction:', error);
+        throw error;
+    }
+}
+
+async function main() {
+    try {
+        await createUpdateOfferTransaction();
+    } catch (error) {
+        console.error('Failed to create update offer transaction:', error);
+    }
+}
+
+await main();
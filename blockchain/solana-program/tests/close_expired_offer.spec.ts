@@ -0,0 +1,140 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("close expired offer", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+    let buyToken2Mint: PublicKey;
+
+    let boss: PublicKey;
+    let bossSellTokenAccount: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken2Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        bossSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda, offerSellTokenPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        await testHelper.takeOfferOne({ sellTokenAmount: 40e9, offerPda, user });
+
+        return { offerPda, offerSellTokenPda, bossBuyTokenAccount };
+    }
+
+    test("Close expired offer before its end time should fail", async () => {
+        // given
+        const cranker = testHelper.createUserAccount();
+        const { offerPda } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.closeExpiredOffer(offerPda, cranker))
+            .rejects.toThrow(RegExp(".*OfferNotExpired.*"));
+    });
+
+    test("Anyone should close an expired offer, returning the tokens to the boss for a bounty", async () => {
+        // given
+        const cranker = testHelper.createUserAccount();
+        const { offerPda, offerSellTokenPda, bossBuyTokenAccount } = await makeOffer();
+        await testHelper.setCrankBounty(1_000_000);
+        const bossSellTokenBalance = (await testHelper.program.provider.connection.getTokenAccountBalance(bossSellTokenAccount)).value.amount;
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.closeExpiredOffer(offerPda, cranker);
+
+        // then
+        expect(await testHelper.context.banksClient.getAccount(offerPda)).toBeNull();
+        expect(await testHelper.context.banksClient.getAccount(offerSellTokenPda)).toBeNull();
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(60e9));
+        await testHelper.expectTokenAccountAmountToBe(bossSellTokenAccount, BigInt(bossSellTokenBalance) + BigInt(40e9));
+        expect(await testHelper.context.banksClient.getBalance(cranker.publicKey)).toBe(BigInt(1_000_000_000 + 1_000_000));
+        await testHelper.setCrankBounty(0);
+    });
+
+    test("Close expired two buy token offer without its buy token 2 accounts should fail", async () => {
+        // given
+        const cranker = testHelper.createUserAccount();
+        const { offerId, offerPda, offerBuyToken2Pda, bossBuyTokenAccount2 } = await testHelper.createTwoTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            buyToken2Mint, BigInt(0),
+            boss, BigInt(100e9), BigInt(50e9)
+        );
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferTwo({
+            offerId,
+            buyToken1TotalAmount: 100e9,
+            buyToken2TotalAmount: 50e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyToken1Mint,
+            buyToken2Mint,
+        });
+        await testHelper.advanceClockBy(3600);
+
+        // when / then
+        await expect(testHelper.closeExpiredOffer(offerPda, cranker))
+            .rejects.toThrow(RegExp(".*MissingBuyToken2Accounts.*"));
+
+        // when
+        await testHelper.closeExpiredOffer(offerPda, cranker, { offerBuyToken2Account: offerBuyToken2Pda, bossBuyToken2Account: bossBuyTokenAccount2 });
+
+        // then
+        expect(await testHelper.context.banksClient.getAccount(offerPda)).toBeNull();
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount2, BigInt(50e9));
+    });
+});
@@ -51,7 +51,7 @@ describe("close offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda, bossBuyTokenAccount } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(10e9)
@@ -95,7 +95,7 @@ describe("close offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyToken1Pda, offerBuyToken2Pda, bossBuyTokenAccount1, bossBuyTokenAccount2 } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyToken1Pda, offerBuyToken2Pda, bossBuyTokenAccount1, bossBuyTokenAccount2 } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0),
@@ -143,7 +143,7 @@ describe("close offer", () => {
         const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
         
         // create offer accounts
-        const { offerId, offerPda, offerAuthority } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerAuthority } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(10e9)
@@ -180,7 +180,7 @@ describe("close offer", () => {
         const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
         
         // create offer accounts
-        const { offerId, offerPda, offerAuthority } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId, offerPda, offerAuthority } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0),
@@ -0,0 +1,153 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("dutch auction", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeDescendingOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 150e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda };
+    }
+
+    function createUser() {
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userSellTokenAccount, userBuyTokenAccount };
+    }
+
+    test("Price of a descending offer should decay towards the end amount", async () => {
+        // given
+        const { offerPda } = await makeDescendingOffer();
+        const first = createUser();
+        const second = createUser();
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 15e9, offerPda, user: first.user });
+        await testHelper.advanceClockBy(3600);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user: second.user });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(second.userBuyTokenAccount, BigInt(10e9));
+    });
+
+    test("Fillers of a settled uniform price offer should claim the difference to the clearing price", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const { offerPda } = await makeDescendingOffer();
+        await testHelper.setOfferUniformPrice(offerPda, true);
+        const first = createUser();
+        const second = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 15e9, offerPda, user: first.user });
+        await testHelper.advanceClockBy(3600);
+        await testHelper.takeOfferOne({ sellTokenAmount: 20e9, offerPda, user: second.user });
+        await expect(testHelper.settleAuction(offerPda, outsider))
+            .rejects.toThrow(RegExp(".*AuctionNotEnded.*"));
+        await expect(testHelper.closeOfferOne(offerPda))
+            .rejects.toThrow(RegExp(".*AuctionNotSettled.*"));
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.settleAuction(offerPda, outsider);
+        await testHelper.claimRebate(offerPda, first.user);
+
+        // then
+        const settlementPda = testHelper.getAuctionSettlementPda(offerPda);
+        const settlement = await testHelper.program.account.auctionSettlement.fetch(settlementPda);
+        expect(settlement.clearingSellTokenAmount.toNumber()).toEqual(100e9);
+        expect(settlement.rebateReserve.toNumber()).toEqual(0);
+        expect(settlement.rebatesOwed.toNumber()).toEqual(0);
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(90e9));
+        await testHelper.expectTokenAccountAmountToBe(getAssociatedTokenAddressSync(sellTokenMint, settlementPda, true), BigInt(0));
+        await expect(testHelper.claimRebate(offerPda, first.user))
+            .rejects.toThrow(RegExp(".*RebateAlreadyClaimed.*"));
+        await expect(testHelper.claimRebate(offerPda, second.user))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+        await testHelper.closeOfferOne(offerPda);
+        expect(await testHelper.context.banksClient.getAccount(offerPda)).toBeNull();
+    });
+
+    test("Uniform price offer settled early should no longer be taken", async () => {
+        // given
+        const { offerPda } = await makeDescendingOffer();
+        await testHelper.setOfferUniformPrice(offerPda, true);
+        const first = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 15e9, offerPda, user: first.user });
+
+        // when
+        await testHelper.settleAuction(offerPda);
+
+        // then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 15e9, offerPda, user: first.user }))
+            .rejects.toThrow(RegExp(".*AuctionAlreadySettled.*"));
+        await expect(testHelper.claimRebate(offerPda, first.user))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+    });
+
+    test("Changing the uniform price mode of a taken offer should fail", async () => {
+        // given
+        const { offerPda } = await makeDescendingOffer();
+        const first = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 15e9, offerPda, user: first.user });
+
+        // when / then
+        await expect(testHelper.setOfferUniformPrice(offerPda, true))
+            .rejects.toThrow(RegExp(".*AuctionStarted.*"));
+    });
+});
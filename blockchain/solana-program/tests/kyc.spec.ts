@@ -0,0 +1,183 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("kyc", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    const JURISDICTION_CH = 756;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeKycGatedOffer(minKycLevel: number) {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(600e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        await testHelper.setOfferMinKycLevel(offerPda, minKycLevel);
+
+        return offerPda;
+    }
+
+    function createUser(): Keypair {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return user;
+    }
+
+    test("Approve KYC in batch should allow all approved wallets to take a gated offer", async () => {
+        // given
+        const user1 = createUser();
+        const user2 = createUser();
+        const offerPda = await makeKycGatedOffer(2);
+
+        // when
+        await testHelper.approveKyc([user1.publicKey, user2.publicKey], 2, 0, JURISDICTION_CH);
+
+        // then
+        const kycRecord = await testHelper.program.account.kycRecord.fetch(testHelper.getKycRecordPda(user1.publicKey));
+        expect(kycRecord.wallet).toEqual(user1.publicKey);
+        expect(kycRecord.level).toBe(2);
+        expect(kycRecord.jurisdiction).toBe(JURISDICTION_CH);
+
+        for (const user of [user1, user2]) {
+            await testHelper.takeOfferOne({
+                sellTokenAmount: 10e9,
+                offerPda,
+                user,
+                kycRecord: testHelper.getKycRecordPda(user.publicKey),
+            });
+        }
+    });
+
+    test("Take gated offer without a KYC record should fail", async () => {
+        // given
+        const user = createUser();
+        const offerPda = await makeKycGatedOffer(1);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*MissingKycRecord.*"));
+    });
+
+    test("Take gated offer with another wallet's KYC record should fail", async () => {
+        // given
+        const approvedUser = createUser();
+        const user = createUser();
+        const offerPda = await makeKycGatedOffer(1);
+        await testHelper.approveKyc([approvedUser.publicKey], 1, 0, JURISDICTION_CH);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user,
+            kycRecord: testHelper.getKycRecordPda(approvedUser.publicKey),
+        })).rejects.toThrow(RegExp(".*InvalidKycRecord.*"));
+    });
+
+    test("Take gated offer with insufficient, expired or revoked KYC should fail", async () => {
+        // given
+        const user = createUser();
+        const kycRecord = testHelper.getKycRecordPda(user.publicKey);
+        const offerPda = await makeKycGatedOffer(2);
+
+        // when / then
+        await testHelper.approveKyc([user.publicKey], 1, 0, JURISDICTION_CH);
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, kycRecord }))
+            .rejects.toThrow(RegExp(".*InsufficientKycLevel.*"));
+
+        const expiry = await testHelper.getCurrentClockTime() + 100;
+        await testHelper.approveKyc([user.publicKey], 2, expiry, JURISDICTION_CH);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, kycRecord });
+        await testHelper.advanceClockBy(100);
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, kycRecord }))
+            .rejects.toThrow(RegExp(".*KycExpired.*"));
+
+        await testHelper.approveKyc([user.publicKey], 2, 0, JURISDICTION_CH);
+        await testHelper.revokeKyc([user.publicKey]);
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, kycRecord }))
+            .rejects.toThrow(RegExp(".*InsufficientKycLevel.*"));
+    });
+
+    test("Approve KYC by a key without the compliance role should fail", async () => {
+        // given
+        const compliance = testHelper.createUserAccount();
+        const user = createUser();
+
+        // when / then
+        await expect(testHelper.approveKyc([user.publicKey], 1, 0, JURISDICTION_CH, compliance))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+
+        await testHelper.grantRole({ compliance: {} }, compliance.publicKey);
+        await testHelper.approveKyc([user.publicKey], 1, 0, JURISDICTION_CH, compliance);
+
+        await testHelper.revokeRole({ compliance: {} }, compliance.publicKey);
+        await expect(testHelper.approveKyc([user.publicKey], 1, 0, JURISDICTION_CH, compliance))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+
+    test("Migrate state should resize a legacy state account with no role grants", async () => {
+        // given
+        const stateAccount = await testHelper.context.banksClient.getAccount(testHelper.statePda);
+        const legacyData = Buffer.from(stateAccount.data).subarray(0, 8 + 32);
+        testHelper.context.setAccount(testHelper.statePda, { ...stateAccount, data: legacyData });
+
+        // when
+        await testHelper.program.methods.migrateState().accounts({ boss }).rpc();
+
+        // then
+        const state = await testHelper.program.account.state.fetch(testHelper.statePda);
+        expect(state.boss).toEqual(boss);
+        expect(state.paused).toEqual(false);
+        expect(state.roles).toEqual([]);
+        expect(state.nextOfferId.toNumber()).toEqual(0);
+    });
+});
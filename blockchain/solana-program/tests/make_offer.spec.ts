@@ -45,7 +45,7 @@ describe("make offer", () => {
 
     test("Make an offer with one buy token", async () => {
         // given
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda, bossBuyTokenAccount } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -96,7 +96,7 @@ describe("make offer", () => {
 
     test("Make offer with one buy token with price fix duration greater than offer duration should fail", async () => {
         // given
-        const { offerId } = testHelper.createOneTokenOfferAccounts(
+        const { offerId } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -123,7 +123,7 @@ describe("make offer", () => {
 
     test("Make offer with one buy token with zero price fix duration should fail", async () => {
         // given
-        const { offerId } = testHelper.createOneTokenOfferAccounts(
+        const { offerId } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -150,7 +150,7 @@ describe("make offer", () => {
 
     test("Make offer with two buy tokens with price fix duration greater than offer duration should fail", async () => {
         // given
-        const { offerId } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0), 
@@ -180,7 +180,7 @@ describe("make offer", () => {
 
     test("Make offer with two buy token with zero price fix duration should fail", async () => {
         // given
-        const { offerId } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0), 
@@ -210,7 +210,7 @@ describe("make offer", () => {
 
     test("Make an offer with same sell_token_start_amount and sell_token_end_amount should succeed", async () => {        
         // given
-        const { offerId, offerPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(1000e9)
@@ -240,7 +240,7 @@ describe("make offer", () => {
 
     test("Make an offer with two buy tokens should succeed", async () => {
         // given
-        const { offerId, offerPda, bossBuyTokenAccount1, bossBuyTokenAccount2, offerBuyToken1Pda, offerBuyToken2Pda, offerSellTokenPda } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId, offerPda, bossBuyTokenAccount1, bossBuyTokenAccount2, offerBuyToken1Pda, offerBuyToken2Pda, offerSellTokenPda } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0), 
@@ -300,7 +300,7 @@ describe("make offer", () => {
 
     test("Make offer with one buy token with end time before start time should fail", async () => {
         // given
-        const { offerId } = testHelper.createOneTokenOfferAccounts(
+        const { offerId } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -327,7 +327,7 @@ describe("make offer", () => {
 
     test("Make offer with two buy tokens with end time before start time should fail", async () => {
         // given
-        const { offerId } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0), 
@@ -357,7 +357,7 @@ describe("make offer", () => {
 
     test("Make offer with existing offer_id should fail", async () => {
         // given
-        const { offerId } = testHelper.createOneTokenOfferAccounts(
+        const { offerId } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -403,7 +403,7 @@ describe("make offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(10e9)
@@ -425,4 +425,81 @@ describe("make offer", () => {
             buyTokenMint: buyToken1Mint,
         })).rejects.toThrow(RegExp(".*InvalidOfferTime.*"));
     })
+
+    test("Migrate offer should resize a legacy offer and record the boss", async () => {
+        // given
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(10e9)
+        );
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 10e9,
+            sellTokenStartAmount: 10e9,
+            sellTokenEndAmount: 20e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        const offerAccount = await testHelper.context.banksClient.getAccount(offerPda);
+        const legacyOfferSize = 8 + 8 * 3 + 32 + 40 * 2 + 1 + 8 * 3;
+        const legacyData = Buffer.from(offerAccount.data).subarray(0, legacyOfferSize);
+        testHelper.context.setAccount(offerPda, { ...offerAccount, data: legacyData });
+        await expect(testHelper.program.account.offer.fetch(offerPda)).rejects.toThrow();
+
+        // when
+        await testHelper.migrateOffer(offerPda);
+
+        // then
+        const offer = await testHelper.program.account.offer.fetch(offerPda);
+        expect(offer.offerId.eq(offerId)).toBe(true);
+        expect(offer.sellTokenEndAmount.eq(new BN(20e9))).toBe(true);
+        expect(offer.boss).toEqual(boss);
+        expect(offer.status).toEqual({ active: {} });
+        expect(offer.feeBps).toBeNull();
+        expect(offer.navOracle).toEqual(PublicKey.default);
+    });
+
+    test("Migrate offer should keep a legacy offer paused", async () => {
+        // given
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(10e9)
+        );
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 10e9,
+            sellTokenStartAmount: 10e9,
+            sellTokenEndAmount: 20e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        await testHelper.setOfferPaused(offerPda, true);
+        const offerAccount = await testHelper.context.banksClient.getAccount(offerPda);
+        const legacyOfferSize = 8 + 8 * 3 + 32 + 40 * 2 + 1 + 8 * 3 + 32 + 2 + 1 + 8 * 5 + 1;
+        const legacyData = Buffer.from(offerAccount.data).subarray(0, legacyOfferSize);
+        testHelper.context.setAccount(offerPda, { ...offerAccount, data: legacyData });
+
+        // when
+        await testHelper.migrateOffer(offerPda);
+
+        // then
+        const offer = await testHelper.program.account.offer.fetch(offerPda);
+        expect(offer.status).toEqual({ paused: {} });
+    });
+
+    test("Migrate offer on an account that is not an offer should fail", async () => {
+        // when / then
+        await expect(testHelper.migrateOffer(testHelper.statePda))
+            .rejects.toThrow(RegExp(".*InvalidOffer.*"));
+    });
 });
\ No newline at end of file
@@ -0,0 +1,149 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("min raise", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer(minRaise: number) {
+        const { offerId, offerPda, offerBuyTokenPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        await testHelper.setOfferMinRaise(offerPda, minRaise);
+
+        return { offerPda, offerBuyTokenPda, bossBuyTokenAccount };
+    }
+
+    function createUser() {
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userSellTokenAccount, userBuyTokenAccount };
+    }
+
+    test("Takes of an offer meeting its minimum raise should be delivered once it ends", async () => {
+        // given
+        const { offerPda } = await makeOffer(50e9);
+        const first = createUser();
+        const second = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 30e9, offerPda, user: first.user });
+        await testHelper.takeOfferOne({ sellTokenAmount: 30e9, offerPda, user: second.user });
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(0));
+        await expect(testHelper.claimReceipt(offerPda, first.user.publicKey))
+            .rejects.toThrow(RegExp(".*OfferNotEnded.*"));
+        await testHelper.advanceClockBy(3600);
+        await expect(testHelper.closeOfferOne(offerPda))
+            .rejects.toThrow(RegExp(".*RaiseNotSettled.*"));
+
+        // when
+        await testHelper.claimReceipt(offerPda, first.user.publicKey);
+        await testHelper.claimReceipt(offerPda, second.user.publicKey, second.user);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(30e9));
+        await testHelper.expectTokenAccountAmountToBe(second.userBuyTokenAccount, BigInt(30e9));
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(70e9));
+        await expect(testHelper.claimReceipt(offerPda, first.user.publicKey))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+        await testHelper.closeOfferOne(offerPda);
+    });
+
+    test("Takes of an offer missing its minimum raise should be refunded and the inventory returned", async () => {
+        // given
+        const { offerPda, bossBuyTokenAccount } = await makeOffer(80e9);
+        const first = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 30e9, offerPda, user: first.user });
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.claimReceipt(offerPda, first.user.publicKey);
+        await testHelper.closeOfferOne(offerPda);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(100e9));
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(0));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(100e9));
+    });
+
+    test("Takes of an offer missing its minimum raise should be refunded in full despite the protocol fee", async () => {
+        // given
+        const feeVault = testHelper.createTokenAccount(sellTokenMint, testHelper.getFeeVaultAuthorityPda(), BigInt(0), true);
+        await testHelper.setProtocolFee(100);
+        const { offerPda } = await makeOffer(80e9);
+        const first = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 30e9, offerPda, user: first.user, feeVault });
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.claimReceipt(offerPda, first.user.publicKey);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(100e9));
+        await testHelper.expectTokenAccountAmountToBe(feeVault, BigInt(0));
+        await testHelper.setProtocolFee(0);
+    });
+
+    test("Escrowed buy tokens should not be sold twice", async () => {
+        // given
+        const { offerPda } = await makeOffer(200e9);
+        const first = createUser();
+        const second = createUser();
+        await testHelper.takeOfferOne({ sellTokenAmount: 80e9, offerPda, user: first.user });
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 30e9, offerPda, user: second.user }))
+            .rejects.toThrow(RegExp(".*InsufficientOfferTokenOneBalance.*"));
+        await expect(testHelper.setOfferMinRaise(offerPda, 0))
+            .rejects.toThrow(RegExp(".*OfferAlreadyTaken.*"));
+    });
+});
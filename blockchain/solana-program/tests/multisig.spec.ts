@@ -0,0 +1,205 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("multisig", () => {
+    let testHelper: TestHelper;
+    let program: Program<OnreApp>;
+
+    let boss: PublicKey;
+    let multisigAuthority: PublicKey;
+    let signers: Keypair[];
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+        multisigAuthority = testHelper.getMultisigAuthorityPda();
+        signers = [testHelper.createUserAccount(), testHelper.createUserAccount(), testHelper.createUserAccount()];
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+        await testHelper.createMultisig(signers.map(signer => signer.publicKey), 2);
+    });
+
+    async function setPausedInstruction(paused: boolean) {
+        return await program.methods
+            .setPaused(paused)
+            .accounts({ authority: multisigAuthority })
+            .instruction();
+    }
+
+    test("Multisig should take over as boss and execute approved proposals", async () => {
+        // given
+        await program.methods.proposeBoss(multisigAuthority).accounts({ state: testHelper.statePda }).rpc();
+        const acceptBossInstruction = await program.methods
+            .acceptBoss()
+            .accountsPartial({ state: testHelper.statePda, newBoss: multisigAuthority })
+            .instruction();
+
+        // when
+        const acceptBossProposal = await testHelper.createProposal(acceptBossInstruction, signers[0]);
+        await expect(testHelper.executeProposal(acceptBossProposal))
+            .rejects.toThrow(RegExp(".*ThresholdNotMet.*"));
+        await testHelper.approveProposal(acceptBossProposal, signers[1]);
+        await testHelper.executeProposal(acceptBossProposal);
+
+        // then
+        let state = await program.account.state.fetch(testHelper.statePda);
+        expect(state.boss).toEqual(multisigAuthority);
+        await expect(testHelper.executeProposal(acceptBossProposal))
+            .rejects.toThrow(RegExp(".*ProposalClosed.*"));
+
+        // when
+        const pauseProposal = await testHelper.createProposal(await setPausedInstruction(true), signers[1]);
+        await testHelper.approveProposal(pauseProposal, signers[2]);
+        await testHelper.executeProposal(pauseProposal);
+
+        // then
+        state = await program.account.state.fetch(testHelper.statePda);
+        expect(state.paused).toEqual(true);
+    });
+
+    test("Proposal rejected by enough signers should no longer be approved", async () => {
+        // given
+        const proposal = await testHelper.createProposal(await setPausedInstruction(false), signers[0]);
+
+        // when
+        await testHelper.rejectProposal(proposal, signers[1]);
+        await testHelper.rejectProposal(proposal, signers[2]);
+
+        // then
+        await expect(testHelper.approveProposal(proposal, signers[1]))
+            .rejects.toThrow(RegExp(".*ProposalClosed.*"));
+        await expect(testHelper.executeProposal(proposal))
+            .rejects.toThrow(RegExp(".*ProposalClosed.*"));
+    });
+
+    test("Executed or rejected proposals should be closed with their rent refunded to the proposer", async () => {
+        // given
+        const executedProposal = await testHelper.createProposal(await setPausedInstruction(false), signers[0]);
+        const rejectedProposal = await testHelper.createProposal(await setPausedInstruction(false), signers[0]);
+        await expect(testHelper.closeProposal(executedProposal))
+            .rejects.toThrow(RegExp(".*ProposalOpen.*"));
+        await testHelper.approveProposal(executedProposal, signers[1]);
+        await testHelper.executeProposal(executedProposal);
+        await testHelper.rejectProposal(rejectedProposal, signers[1]);
+        await testHelper.rejectProposal(rejectedProposal, signers[2]);
+        const proposerLamports = (await testHelper.context.banksClient.getAccount(signers[0].publicKey)).lamports;
+        const proposalLamports = (await testHelper.context.banksClient.getAccount(executedProposal)).lamports;
+
+        // when
+        await testHelper.closeProposal(executedProposal);
+        await testHelper.closeProposal(rejectedProposal);
+
+        // then
+        expect(await testHelper.context.banksClient.getAccount(executedProposal)).toBeNull();
+        expect(await testHelper.context.banksClient.getAccount(rejectedProposal)).toBeNull();
+        expect((await testHelper.context.banksClient.getAccount(signers[0].publicKey)).lamports)
+            .toEqual(proposerLamports + 2 * proposalLamports);
+    });
+
+    test("Votes by non signers or signers that already voted should fail", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const instruction = await setPausedInstruction(false);
+        const proposal = await testHelper.createProposal(instruction, signers[0]);
+
+        // when / then
+        await expect(testHelper.createProposal(instruction, outsider))
+            .rejects.toThrow(RegExp(".*NotASigner.*"));
+        await expect(testHelper.approveProposal(proposal, outsider))
+            .rejects.toThrow(RegExp(".*NotASigner.*"));
+        await expect(testHelper.approveProposal(proposal, signers[0]))
+            .rejects.toThrow(RegExp(".*AlreadyVoted.*"));
+        await testHelper.rejectProposal(proposal, signers[1]);
+        await expect(testHelper.approveProposal(proposal, signers[1]))
+            .rejects.toThrow(RegExp(".*AlreadyVoted.*"));
+    });
+
+    test("Proposal requiring a signer other than the multisig authority should fail", async () => {
+        // given
+        const pauser = testHelper.createUserAccount();
+        const instruction = await program.methods
+            .setPaused(false)
+            .accounts({ authority: pauser.publicKey })
+            .instruction();
+
+        // when / then
+        await expect(testHelper.createProposal(instruction, signers[0]))
+            .rejects.toThrow(RegExp(".*InvalidInstruction.*"));
+    });
+
+    test("Changing the signers through a proposal should drop the votes of removed signers", async () => {
+        // given
+        const newSigner = testHelper.createUserAccount();
+        const pendingProposal = await testHelper.createProposal(await setPausedInstruction(false), signers[2]);
+        const changeInstruction = await program.methods
+            .changeMultisig([signers[0].publicKey, signers[1].publicKey, newSigner.publicKey], 2)
+            .accountsPartial({ multisigAuthority })
+            .instruction();
+
+        // when
+        const changeProposal = await testHelper.createProposal(changeInstruction, signers[0]);
+        await testHelper.approveProposal(changeProposal, signers[1]);
+        await testHelper.executeProposal(changeProposal);
+
+        // then
+        const multisig = await program.account.multisig.fetch(testHelper.getMultisigPda());
+        expect(multisig.signers).toEqual([signers[0].publicKey, signers[1].publicKey, newSigner.publicKey]);
+        await expect(testHelper.executeProposal(pendingProposal))
+            .rejects.toThrow(RegExp(".*ThresholdNotMet.*"));
+        await testHelper.approveProposal(pendingProposal, newSigner);
+        await expect(testHelper.executeProposal(pendingProposal))
+            .rejects.toThrow(RegExp(".*ThresholdNotMet.*"));
+        await testHelper.approveProposal(pendingProposal, signers[0]);
+        await testHelper.executeProposal(pendingProposal);
+        const state = await program.account.state.fetch(testHelper.statePda);
+        expect(state.paused).toEqual(false);
+    });
+
+    test("Proposal reentering the multisig should fail", async () => {
+        // given
+        const proposal = await testHelper.createProposal(await setPausedInstruction(false), signers[0]);
+        const executeInstruction = await program.methods
+            .executeProposal()
+            .accounts({ proposal, executor: multisigAuthority })
+            .instruction();
+        const multisig = await program.account.multisig.fetch(testHelper.getMultisigPda());
+        const nextProposal = testHelper.getProposalPda(multisig.proposalCount);
+        const withMultisig = await program.methods
+            .setPaused(false)
+            .accounts({ authority: multisigAuthority })
+            .remainingAccounts([{ pubkey: testHelper.getMultisigPda(), isSigner: false, isWritable: true }])
+            .instruction();
+        const withProposal = await program.methods
+            .setPaused(false)
+            .accounts({ authority: multisigAuthority })
+            .remainingAccounts([{ pubkey: nextProposal, isSigner: false, isWritable: true }])
+            .instruction();
+
+        // when / then
+        await expect(testHelper.createProposal(executeInstruction, signers[0]))
+            .rejects.toThrow(RegExp(".*InvalidInstruction.*"));
+        await expect(testHelper.createProposal(withMultisig, signers[0]))
+            .rejects.toThrow(RegExp(".*InvalidInstruction.*"));
+        await expect(testHelper.createProposal(withProposal, signers[0]))
+            .rejects.toThrow(RegExp(".*InvalidInstruction.*"));
+    });
+});
@@ -0,0 +1,234 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("nav oracle", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let oracleAuthority: Keypair;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+        oracleAuthority = testHelper.createUserAccount();
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeNavPricedOffer(navOracle: PublicKey | null, navSpreadBps: number) {
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(600e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        const offerEndTime = offerStartTime + 7200;
+
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerStartTime,
+            offerEndTime,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        await testHelper.setOfferNavPricing(offerPda, navOracle, navSpreadBps);
+
+        return { offerPda, offerSellTokenPda, offerBuyTokenPda };
+    }
+
+    test("Initialize and update NAV oracle should succeed", async () => {
+        // given
+        const poolId = new BN(1);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+
+        // when
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        await testHelper.updateNav(navOracle, 1.05e9, oracleAuthority);
+
+        // then
+        const oracle = await testHelper.getNavOracleAccount(navOracle);
+        expect(oracle.poolId.toNumber()).toBe(1);
+        expect(oracle.oracleAuthority).toEqual(oracleAuthority.publicKey);
+        expect(oracle.nav.toNumber()).toBe(1.05e9);
+        expect(oracle.lastUpdateTime.toNumber()).toBe(await testHelper.getCurrentClockTime());
+        expect(oracle.maxStaleness.toNumber()).toBe(3600);
+        expect(oracle.isActive).toBe(true);
+    });
+
+    test("Update NAV by a key other than the oracle authority should fail", async () => {
+        // given
+        const poolId = new BN(2);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        const attacker = testHelper.createUserAccount();
+
+        // when / then
+        await expect(testHelper.updateNav(navOracle, 1e9, attacker))
+            .rejects.toThrow(RegExp(".*InvalidOracleAuthority.*"));
+    });
+
+    test("Take NAV priced offer should price at NAV plus spread", async () => {
+        // given
+        const poolId = new BN(3);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        await testHelper.updateNav(navOracle, 1.05e9, oracleAuthority);
+
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+
+        const { offerPda, offerSellTokenPda, offerBuyTokenPda } = await makeNavPricedOffer(navOracle, 100);
+
+        // when
+        // price is 1.05 * 1.01 = 1.0605 sell tokens per buy token
+        await testHelper.takeOfferOne({
+            sellTokenAmount: 10.605e9,
+            offerPda,
+            user,
+            navOracle,
+        });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userSellTokenAccount, BigInt(89.395e9));
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(10.605e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyToken1Account, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(offerBuyTokenPda, BigInt(90e9));
+    });
+
+    test("Take NAV priced offer without the oracle account should fail", async () => {
+        // given
+        const poolId = new BN(4);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        await testHelper.updateNav(navOracle, 1e9, oracleAuthority);
+
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+
+        const { offerPda } = await makeNavPricedOffer(navOracle, 0);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user,
+        })).rejects.toThrow(RegExp(".*MissingNavOracle.*"));
+    });
+
+    test("Take NAV priced offer with stale NAV should fail", async () => {
+        // given
+        const poolId = new BN(5);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 600);
+        await testHelper.updateNav(navOracle, 1e9, oracleAuthority);
+
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+
+        const { offerPda } = await makeNavPricedOffer(navOracle, 0);
+
+        // time travel past the staleness window
+        await testHelper.advanceClockBy(601);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user,
+            navOracle,
+        })).rejects.toThrow(RegExp(".*StaleNav.*"));
+        await testHelper.expectTokenAccountAmountToBe(userSellTokenAccount, BigInt(100e9));
+    });
+
+    test("Take NAV priced offer of a deactivated pool should fail", async () => {
+        // given
+        const poolId = new BN(6);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        await testHelper.updateNav(navOracle, 1e9, oracleAuthority);
+
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+
+        const { offerPda } = await makeNavPricedOffer(navOracle, 0);
+        await testHelper.configureNavOracle(navOracle, oracleAuthority.publicKey, 3600, false);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user,
+            navOracle,
+        })).rejects.toThrow(RegExp(".*PoolDeactivated.*"));
+    });
+
+    test("Set offer NAV pricing with out of bounds spread should fail", async () => {
+        // given
+        const poolId = new BN(7);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+
+        // when / then
+        await expect(makeNavPricedOffer(navOracle, 5001))
+            .rejects.toThrow(RegExp(".*InvalidSpread.*"));
+    });
+
+    test("NAV pricing should not be combined with uniform pricing, subscriptions or a minimum raise", async () => {
+        // given
+        const poolId = new BN(8);
+        const navOracle = testHelper.getNavOraclePda(poolId);
+        await testHelper.initializeNavOracle(poolId, oracleAuthority.publicKey, 3600);
+        const { offerPda: uniformPriceOfferPda } = await makeNavPricedOffer(null, 0);
+        await testHelper.setOfferUniformPrice(uniformPriceOfferPda, true);
+        const { offerPda: subscriptionOfferPda } = await makeNavPricedOffer(null, 0);
+        await testHelper.setOfferSubscription(subscriptionOfferPda, true);
+        const { offerPda: minRaiseOfferPda } = await makeNavPricedOffer(null, 0);
+        await testHelper.setOfferMinRaise(minRaiseOfferPda, 50e9);
+        const { offerPda: navPricedOfferPda } = await makeNavPricedOffer(navOracle, 0);
+
+        // when / then
+        await expect(testHelper.setOfferNavPricing(uniformPriceOfferPda, navOracle, 0))
+            .rejects.toThrow(RegExp(".*UnsupportedOffer.*"));
+        await expect(testHelper.setOfferNavPricing(subscriptionOfferPda, navOracle, 0))
+            .rejects.toThrow(RegExp(".*UnsupportedOffer.*"));
+        await expect(testHelper.setOfferNavPricing(minRaiseOfferPda, navOracle, 0))
+            .rejects.toThrow(RegExp(".*MinRaiseOffer.*"));
+        await expect(testHelper.setOfferMinRaise(navPricedOfferPda, 50e9))
+            .rejects.toThrow(RegExp(".*MinRaiseOffer.*"));
+    });
+});
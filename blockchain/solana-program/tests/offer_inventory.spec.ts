@@ -0,0 +1,179 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("offer inventory", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+    let buyToken2Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken2Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOfferOne() {
+        const { offerId, offerPda, offerBuyTokenPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(200e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, offerBuyTokenPda, bossBuyTokenAccount };
+    }
+
+    function createUser(): { user: Keypair, userBuyTokenAccount: PublicKey } {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userBuyTokenAccount };
+    }
+
+    test("Top up should add buy tokens without changing the price", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerBuyTokenPda, bossBuyTokenAccount } = await makeOfferOne();
+
+        // when
+        await testHelper.topUpOffer(offerPda, 50e9);
+
+        // then
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.buyToken1.amount.toNumber()).toEqual(150e9);
+        expect(offer.sellTokenStartAmount.toNumber()).toEqual(150e9);
+        expect(offer.sellTokenEndAmount.toNumber()).toEqual(150e9);
+        await testHelper.expectTokenAccountAmountToBe(offerBuyTokenPda, BigInt(150e9));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(50e9));
+
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+    });
+
+    test("Withdraw unsold should return buy tokens without changing the price", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerBuyTokenPda, bossBuyTokenAccount } = await makeOfferOne();
+        await testHelper.takeOfferOne({ sellTokenAmount: 20e9, offerPda, user });
+
+        // when
+        await testHelper.withdrawUnsold(offerPda, 60e9);
+
+        // then
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.buyToken1.amount.toNumber()).toEqual(40e9);
+        expect(offer.sellTokenStartAmount.toNumber()).toEqual(40e9);
+        await testHelper.expectTokenAccountAmountToBe(offerBuyTokenPda, BigInt(20e9));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(160e9));
+
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(30e9));
+    });
+
+    test("Withdraw unsold beyond the inventory or the whole offer should fail", async () => {
+        // given
+        const { user } = createUser();
+        const { offerPda } = await makeOfferOne();
+        await testHelper.takeOfferOne({ sellTokenAmount: 50e9, offerPda, user });
+
+        // when / then
+        await expect(testHelper.withdrawUnsold(offerPda, 60e9))
+            .rejects.toThrow(RegExp(".*InsufficientInventory.*"));
+        await expect(testHelper.withdrawUnsold(offerPda, 100e9))
+            .rejects.toThrow(RegExp(".*InvalidAmount.*"));
+        await expect(testHelper.topUpOffer(offerPda, 0))
+            .rejects.toThrow(RegExp(".*InvalidAmount.*"));
+    });
+
+    test("Top up and withdraw of a two buy token offer should scale both buy tokens", async () => {
+        // given
+        const { offerId, offerPda, offerBuyToken1Pda, offerBuyToken2Pda, bossBuyTokenAccount2 } = await testHelper.createTwoTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            buyToken2Mint, BigInt(0),
+            boss, BigInt(200e9), BigInt(100e9)
+        );
+        const buyToken2Accounts = { offerBuyToken2Account: offerBuyToken2Pda, bossBuyToken2Account: bossBuyTokenAccount2 };
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferTwo({
+            offerId,
+            buyToken1TotalAmount: 100e9,
+            buyToken2TotalAmount: 50e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyToken1Mint,
+            buyToken2Mint,
+        });
+
+        // when / then
+        await expect(testHelper.topUpOffer(offerPda, 100e9))
+            .rejects.toThrow(RegExp(".*MissingBuyToken2Accounts.*"));
+
+        // when
+        await testHelper.topUpOffer(offerPda, 100e9, buyToken2Accounts);
+
+        // then
+        let offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.buyToken2.amount.toNumber()).toEqual(100e9);
+        expect(offer.sellTokenStartAmount.toNumber()).toEqual(200e9);
+        await testHelper.expectTokenAccountAmountToBe(offerBuyToken1Pda, BigInt(200e9));
+        await testHelper.expectTokenAccountAmountToBe(offerBuyToken2Pda, BigInt(100e9));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount2, BigInt(0));
+
+        // when
+        await testHelper.withdrawUnsold(offerPda, 150e9, buyToken2Accounts);
+
+        // then
+        offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.buyToken1.amount.toNumber()).toEqual(50e9);
+        expect(offer.buyToken2.amount.toNumber()).toEqual(25e9);
+        expect(offer.sellTokenEndAmount.toNumber()).toEqual(50e9);
+        await testHelper.expectTokenAccountAmountToBe(offerBuyToken2Pda, BigInt(25e9));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount2, BigInt(75e9));
+    });
+});
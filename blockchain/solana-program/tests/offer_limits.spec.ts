@@ -0,0 +1,140 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("offer limits", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(600e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 500e9,
+            sellTokenStartAmount: 500e9,
+            sellTokenEndAmount: 500e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return offerPda;
+    }
+
+    function createUser(): Keypair {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return user;
+    }
+
+    test("Take offer should track the user's cumulative purchases in a position", async () => {
+        // given
+        const user = createUser();
+        const offerPda = await makeOffer();
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.takeOfferOne({ sellTokenAmount: 5e9, offerPda, user });
+
+        // then
+        const position = await testHelper.program.account.position.fetch(testHelper.getPositionPda(offerPda, user.publicKey));
+        expect(position.offer).toEqual(offerPda);
+        expect(position.user).toEqual(user.publicKey);
+        expect(position.sellTokenAmount.toNumber()).toBe(15e9);
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.totalRaised.toNumber()).toBe(15e9);
+    });
+
+    test("Take offer outside the per transaction limits should fail", async () => {
+        // given
+        const user = createUser();
+        const offerPda = await makeOffer();
+        await testHelper.setOfferLimits(offerPda, { minPerTx: 5e9, maxPerTx: 20e9 });
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 4e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*BelowMinPerTx.*"));
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 21e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*AboveMaxPerTx.*"));
+        await testHelper.takeOfferOne({ sellTokenAmount: 20e9, offerPda, user });
+    });
+
+    test("Take offer above the per user cap should fail while other users can still buy", async () => {
+        // given
+        const user1 = createUser();
+        const user2 = createUser();
+        const offerPda = await makeOffer();
+        await testHelper.setOfferLimits(offerPda, { maxPerUser: 25e9 });
+        await testHelper.takeOfferOne({ sellTokenAmount: 20e9, offerPda, user: user1 });
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 6e9, offerPda, user: user1 }))
+            .rejects.toThrow(RegExp(".*UserCapExceeded.*"));
+        await testHelper.takeOfferOne({ sellTokenAmount: 25e9, offerPda, user: user2 });
+    });
+
+    test("Take offer above the raise cap should fail", async () => {
+        // given
+        const user1 = createUser();
+        const user2 = createUser();
+        const offerPda = await makeOffer();
+        await testHelper.setOfferLimits(offerPda, { raiseCap: 50e9 });
+        await testHelper.takeOfferOne({ sellTokenAmount: 40e9, offerPda, user: user1 });
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 11e9, offerPda, user: user2 }))
+            .rejects.toThrow(RegExp(".*RaiseCapExceeded.*"));
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user: user2 });
+    });
+
+    test("Set offer limits with a minimum above a maximum should fail", async () => {
+        // given
+        const offerPda = await makeOffer();
+
+        // when / then
+        await expect(testHelper.setOfferLimits(offerPda, { minPerTx: 10e9, maxPerTx: 5e9 }))
+            .rejects.toThrow(RegExp(".*InvalidLimits.*"));
+    });
+});
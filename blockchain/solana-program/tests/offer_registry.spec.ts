@@ -0,0 +1,155 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("offer registry", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0), true);
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerId, offerPda };
+    }
+
+    async function getOfferStatus(offerPda: PublicKey) {
+        const offerAccount = await testHelper.program.account.offer.fetch(offerPda);
+        return offerAccount.status;
+    }
+
+    test("Making offers should hand out increasing offer IDs and list them as active", async () => {
+        // given
+        const nextOfferId = await testHelper.getNextOfferId();
+
+        // when
+        const first = await makeOffer();
+        const second = await makeOffer();
+
+        // then
+        expect(first.offerId.eq(nextOfferId)).toBe(true);
+        expect(second.offerId.eq(nextOfferId.addn(1))).toBe(true);
+        expect((await testHelper.getNextOfferId()).eq(nextOfferId.addn(2))).toBe(true);
+
+        const offerAccount = await testHelper.program.account.offer.fetch(second.offerPda);
+        expect(offerAccount.offerId.eq(second.offerId)).toBe(true);
+        expect(await getOfferStatus(first.offerPda)).toEqual({ active: {} });
+        expect(await getOfferStatus(second.offerPda)).toEqual({ active: {} });
+        expect(await testHelper.getListedOfferIds()).toEqual(expect.arrayContaining([first.offerId.toNumber(), second.offerId.toNumber()]));
+    });
+
+    test("Pausing an offer should update its status", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+
+        // when
+        await testHelper.setOfferPaused(offerPda, true);
+
+        // then
+        expect(await getOfferStatus(offerPda)).toEqual({ paused: {} });
+
+        // when
+        await testHelper.setOfferPaused(offerPda, false);
+
+        // then
+        expect(await getOfferStatus(offerPda)).toEqual({ active: {} });
+    });
+
+    test("Closing an offer should remove it from the registry without reusing its ID", async () => {
+        // given
+        const { offerId, offerPda } = await makeOffer();
+
+        // when
+        await testHelper.closeOfferOne(offerPda);
+
+        // then
+        expect(await testHelper.context.banksClient.getAccount(offerPda)).toBeNull();
+        expect(await testHelper.getListedOfferIds()).not.toContain(offerId.toNumber());
+        const next = await makeOffer();
+        expect(next.offerId.eq(offerId.addn(1))).toBe(true);
+    });
+
+    test("Setting the next offer ID should skip IDs", async () => {
+        // given
+        const nextOfferId = (await testHelper.getNextOfferId()).addn(100);
+
+        // when
+        await testHelper.setNextOfferId(nextOfferId);
+        const { offerId, offerPda } = await makeOffer();
+
+        // then
+        expect(offerId.eq(nextOfferId)).toBe(true);
+        expect(await getOfferStatus(offerPda)).toEqual({ active: {} });
+    });
+
+    test("Setting the next offer ID backwards should fail", async () => {
+        // given
+        const nextOfferId = await testHelper.getNextOfferId();
+
+        // when / then
+        await expect(testHelper.setNextOfferId(nextOfferId))
+            .rejects.toThrow(RegExp(".*InvalidNextOfferId.*"));
+        await expect(testHelper.setNextOfferId(nextOfferId.subn(1)))
+            .rejects.toThrow(RegExp(".*InvalidNextOfferId.*"));
+    });
+
+    test("Setting the next offer ID without the admin role should fail", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        const nextOfferId = (await testHelper.getNextOfferId()).addn(1);
+
+        // when / then
+        await expect(testHelper.setNextOfferId(nextOfferId, user))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+});
@@ -52,7 +52,7 @@ async function createAndSendTransaction(provider: anchor.AnchorProvider, payer:
 }
 
 async function createIntermediaryAccountsIfNeeded(provider: anchor.AnchorProvider, payer: anchor.Wallet, buyTokenMint: PublicKey, sellTokenMint: PublicKey, programId: PublicKey) {
-    const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless-1")], programId);
+    const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)], programId);
     const intermediaryBuyTokenAccount = await getAssociatedTokenAddress(buyTokenMint, intermediaryAuthority, true);
     const intermediarySellTokenAccount = await getAssociatedTokenAddress(sellTokenMint, intermediaryAuthority, true);
 
@@ -90,7 +90,7 @@ describe("onreapp", () => {
     let bossBuyTokenAccount2: PublicKey;
     let offerPda: PublicKey;
     let offerBuyToken1Pda: PublicKey;
-    let offerId = new anchor.BN(123123123);
+    let offerId: anchor.BN;
     let statePda: PublicKey;
     let offerAuthority: PublicKey;
 
@@ -109,12 +109,7 @@ describe("onreapp", () => {
         await mintToAddress(provider, initialBoss.payer, buyToken1Mint, bossBuyTokenAccount1, initialBoss.publicKey, 10000e9);
         await mintToAddress(provider, initialBoss.payer, buyToken2Mint, bossBuyTokenAccount2, initialBoss.publicKey, 10000e9);
 
-        [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         [statePda] = PublicKey.findProgramAddressSync([Buffer.from("state")], program.programId);
-        [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
-        await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
-        offerBuyToken1Pda = await getAssociatedTokenAddress(buyToken1Mint, offerAuthority, true);
-        await getAssociatedTokenAddress(buyToken2Mint, offerAuthority, true);
     });
 
     it("Initialize onre with right boss account", async () => {
@@ -123,21 +118,46 @@ describe("onreapp", () => {
         expect(currentBoss.boss).toEqual(initialBoss.publicKey);
     });
 
-    it("Set boss account sets a new boss account", async () => {
+    it("Propose and accept boss hands over to a new boss account", async () => {
         const newBoss = new anchor.Wallet(Keypair.generate());
-        await program.methods.setBoss(newBoss.publicKey).accounts({ state: statePda }).rpc();
+        await program.methods.proposeBoss(newBoss.publicKey).accounts({ state: statePda }).rpc();
+        const proposedState = await program.account.state.fetch(statePda);
+        expect(proposedState.boss).toEqual(initialBoss.publicKey);
+        expect(proposedState.pendingBoss).toEqual(newBoss.publicKey);
+
+        await airdropLamports(provider, newBoss.publicKey, anchor.web3.LAMPORTS_PER_SOL * 200);
+        const acceptBossInstruction = await program.methods.acceptBoss().accountsPartial({ state: statePda, newBoss: newBoss.publicKey }).instruction();
+        await createAndSendTransaction(provider, newBoss, [acceptBossInstruction]);
         const currentBoss = await program.account.state.fetch(statePda);
         expect(currentBoss.boss).toEqual(newBoss.publicKey);
+        expect(currentBoss.pendingBoss).toEqual(PublicKey.default);
 
-        await airdropLamports(provider, newBoss.publicKey, anchor.web3.LAMPORTS_PER_SOL * 200);
-        const setBossInstruction = await program.methods.setBoss(initialBoss.publicKey).accountsPartial({ state: statePda, boss: newBoss.publicKey }).instruction();
-        await createAndSendTransaction(provider, newBoss, [setBossInstruction]);
+        const proposeBossInstruction = await program.methods.proposeBoss(initialBoss.publicKey).accountsPartial({ state: statePda, boss: newBoss.publicKey }).instruction();
+        await createAndSendTransaction(provider, newBoss, [proposeBossInstruction]);
+        await program.methods.acceptBoss().accountsPartial({ state: statePda, newBoss: initialBoss.publicKey }).rpc();
 
         const finalBoss = await program.account.state.fetch(statePda);
         expect(finalBoss.boss).toEqual(initialBoss.publicKey);
     });
 
+    it("Cancel boss proposal prevents the proposed boss from accepting", async () => {
+        const newBoss = new anchor.Wallet(Keypair.generate());
+        await airdropLamports(provider, newBoss.publicKey, anchor.web3.LAMPORTS_PER_SOL * 20);
+        await program.methods.proposeBoss(newBoss.publicKey).accounts({ state: statePda }).rpc();
+        await program.methods.cancelBossProposal().accounts({ state: statePda }).rpc();
+
+        const acceptBossInstruction = await program.methods.acceptBoss().accountsPartial({ state: statePda, newBoss: newBoss.publicKey }).instruction();
+        await expect(createAndSendTransaction(provider, newBoss, [acceptBossInstruction])).rejects.toThrow(/NoPendingBoss/);
+
+        const currentBoss = await program.account.state.fetch(statePda);
+        expect(currentBoss.boss).toEqual(initialBoss.publicKey);
+    });
+
     it("Makes an offer", async () => {
+        offerId = (await program.account.state.fetch(statePda)).nextOfferId;
+        [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
+        [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
+        offerBuyToken1Pda = await getAssociatedTokenAddress(buyToken1Mint, offerAuthority, true);
         const offerSellTokenAccountInstruction = createAssociatedTokenAccountInstruction(
             initialBoss.payer.publicKey,
             getAssociatedTokenAddressSync(sellTokenMint, offerAuthority, true),
@@ -153,7 +173,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(500e9),
                 new anchor.BN(200e9),
                 new anchor.BN(400e9),
@@ -161,7 +180,7 @@ describe("onreapp", () => {
                 new anchor.BN(Date.now() + 7200),
                 new anchor.BN(3600),
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([buyToken1AccountInstruction, offerSellTokenAccountInstruction])
             .rpc();
 
@@ -185,7 +204,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(500e9),
                     new anchor.BN(200e9),
                     new anchor.BN(400e9),
@@ -193,7 +211,7 @@ describe("onreapp", () => {
                     new anchor.BN(Date.now() + 1),
                     new anchor.BN(3600),
                 )
-                .accountsPartial({ bossBuyToken1Account: bossBuyTokenAccount1, sellTokenMint, buyToken1Mint, state: statePda })
+                .accountsPartial({ bossBuyToken1Account: bossBuyTokenAccount1, sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
                 .signers([newBoss.payer])
                 .rpc(),
         ).rejects.toThrow();
@@ -204,7 +222,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(500e9),
                     new anchor.BN(200e9),
                     new anchor.BN(400e9),
@@ -229,7 +246,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(500e9),
                     new anchor.BN(200e9),
                     new anchor.BN(400e9),
@@ -250,16 +266,17 @@ describe("onreapp", () => {
     });
 
     it("Replace an offer", async () => {
-        const newOfferId = new anchor.BN(123123124);
+        const newOfferId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [newOfferAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), newOfferId.toArrayLike(Buffer, "le", 8)], program.programId);
         const newOfferSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, newOfferAuthorityPda, true);
         const newOfferBuyTokenPda = await getAssociatedTokenAddress(buyToken1Mint, newOfferAuthorityPda, true);
 
         const closeInstruction = await program.methods
             .closeOfferOne()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .instruction();
         const offerSellTokenAccountInstruction = createAssociatedTokenAccountInstruction(
@@ -276,7 +293,6 @@ describe("onreapp", () => {
         );
         const makeOfferInstruction = await program.methods
             .makeOfferOne(
-                newOfferId,
                 new anchor.BN(500e9),
                 new anchor.BN(200e9),
                 new anchor.BN(400e9),
@@ -284,7 +300,7 @@ describe("onreapp", () => {
                 new anchor.BN(Date.now() + 7200),
                 new anchor.BN(3600),
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .instruction();
 
         await createAndSendTransaction(provider, initialBoss, [closeInstruction, offerSellTokenAccountInstruction, buyToken1AccountInstruction, makeOfferInstruction]);
@@ -307,7 +323,7 @@ describe("onreapp", () => {
     });
 
     it("Create and take offer", async () => {
-        const offerId = new anchor.BN(1);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -322,7 +338,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferTwo(
-                offerId,
                 new anchor.BN(100e9), // buy token 1
                 new anchor.BN(20e9), // buy token 2
                 new anchor.BN(240e9), // sell token start
@@ -331,7 +346,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, buyToken2Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, buyToken2Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
             .rpc();
 
@@ -399,7 +414,7 @@ describe("onreapp", () => {
     });
 
     it("Takes an offer with one buy token successfully", async () => {
-        const offerId = new anchor.BN(2);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -412,7 +427,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(200e9), // sell token start
                 new anchor.BN(200e9), // sell token end
@@ -420,7 +434,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
             .rpc();
 
@@ -461,7 +475,7 @@ describe("onreapp", () => {
     });
 
     it("Fails to take offer with one buy token due to exceeding sell limit", async () => {
-        const offerId = new anchor.BN(3);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -471,7 +485,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(50e9), // sell token start
                 new anchor.BN(50e9), // sell token end
@@ -479,7 +492,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerSellTokenPda, offerAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerBuyToken1Pda, offerAuthority, buyToken1Mint),
@@ -526,7 +539,7 @@ describe("onreapp", () => {
     });
 
     it("Fails to take offer with one buy token due to invalid buy token mint", async () => {
-        const offerId = new anchor.BN(5);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -536,7 +549,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(200e9), // sell token start
                 new anchor.BN(300e9), // sell token end
@@ -544,7 +556,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerSellTokenPda, offerAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerBuyToken1Pda, offerAuthority, buyToken1Mint),
@@ -585,7 +597,7 @@ describe("onreapp", () => {
 
     it("Closes an offer with two buy tokens", async () => {
         // Create an offer with two buy tokens
-        const offerId = new anchor.BN(1000);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -600,7 +612,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferTwo(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(200e9), // buy token 2 amount
                 new anchor.BN(200e9), // sell token start
@@ -609,11 +620,12 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({
+            .accountsPartial({
                 sellTokenMint: sellTokenMint,
                 buyToken1Mint: buyToken1Mint,
                 buyToken2Mint: buyToken2Mint,
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
             .rpc();
@@ -640,9 +652,10 @@ describe("onreapp", () => {
         // Close the offer
         await program.methods
             .closeOfferTwo()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
 
@@ -662,7 +675,7 @@ describe("onreapp", () => {
 
     it("Fails to close offer with one buy token using close_offer_two", async () => {
         // Create an offer with one buy token
-        const offerId = new anchor.BN(1001);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -675,7 +688,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(50e9), // buy token 1 amount
                 new anchor.BN(200e9), // sell token start
                 new anchor.BN(400e9), // sell token end
@@ -683,10 +695,11 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({
+            .accountsPartial({
                 sellTokenMint: sellTokenMint,
                 buyToken1Mint: buyToken1Mint,
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
             .rpc();
@@ -701,9 +714,10 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .closeOfferTwo()
-                .accounts({
+                .accountsPartial({
                     offer: offerPda,
                     state: statePda,
+                    authority: initialBoss.publicKey,
                 })
                 .rpc(),
         ).rejects.toThrow();
@@ -711,15 +725,16 @@ describe("onreapp", () => {
         // Clean up - close the offer properly
         await program.methods
             .closeOfferOne()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
     });
 
     it("Fails to make offer with zero buy token amount in make_offer_two", async () => {
-        const offerId = new anchor.BN(1002);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
         const offerBuyToken1Pda = await getAssociatedTokenAddress(buyToken1Mint, offerAuthority, true);
@@ -735,7 +750,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferTwo(
-                    offerId,
                     new anchor.BN(0), // buy token 1 amount
                     new anchor.BN(100e9), // buy token 2 amount
                     new anchor.BN(200e9), // sell token start
@@ -744,11 +758,12 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({
+                .accountsPartial({
                     sellTokenMint: sellTokenMint,
                     buyToken1Mint: buyToken1Mint,
                     buyToken2Mint: buyToken2Mint,
                     state: statePda,
+                    boss: initialBoss.publicKey,
                 })
                 .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
                 .rpc(),
@@ -758,7 +773,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferTwo(
-                    offerId,
                     new anchor.BN(100e9), // buy token 1 amount
                     new anchor.BN(0), // buy token 2 amount
                     new anchor.BN(200e9), // sell token start
@@ -767,11 +781,12 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({
+                .accountsPartial({
                     sellTokenMint: sellTokenMint,
                     buyToken1Mint: buyToken1Mint,
                     buyToken2Mint: buyToken2Mint,
                     state: statePda,
+                    boss: initialBoss.publicKey,
                 })
                 .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
                 .rpc(),
@@ -781,7 +796,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferTwo(
-                    offerId,
                     new anchor.BN(100e9), // buy token 1 amount
                     new anchor.BN(100e9), // buy token 2 amount
                     new anchor.BN(0), // sell token start
@@ -790,11 +804,12 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({
+                .accountsPartial({
                     sellTokenMint: sellTokenMint,
                     buyToken1Mint: buyToken1Mint,
                     buyToken2Mint: buyToken2Mint,
                     state: statePda,
+                    boss: initialBoss.publicKey,
                 })
                 .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
                 .rpc(),
@@ -802,7 +817,7 @@ describe("onreapp", () => {
     });
 
     it("Fails to make offer with zero amounts in make_offer_one", async () => {
-        const offerId = new anchor.BN(1003);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
         const offerBuyToken1Pda = await getAssociatedTokenAddress(buyToken1Mint, offerAuthority, true);
@@ -815,7 +830,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(0), // buy token 1 amount
                     new anchor.BN(200e9), // sell token start
                     new anchor.BN(400e9), // sell token end
@@ -823,10 +837,11 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({
+                .accountsPartial({
                     sellTokenMint: sellTokenMint,
                     buyToken1Mint: buyToken1Mint,
                     state: statePda,
+                    boss: initialBoss.publicKey,
                 })
                 .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
                 .rpc(),
@@ -836,7 +851,6 @@ describe("onreapp", () => {
         await expect(
             program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(100e9), // buy token 1 amount
                     new anchor.BN(0), // sell token start
                     new anchor.BN(0), // sell token end
@@ -844,10 +858,11 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({
+                .accountsPartial({
                     sellTokenMint: sellTokenMint,
                     buyToken1Mint: buyToken1Mint,
                     state: statePda,
+                    boss: initialBoss.publicKey,
                 })
                 .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
                 .rpc(),
@@ -855,7 +870,7 @@ describe("onreapp", () => {
     });
 
     it("Fails to take offer with two buy tokens due to invalid token mints", async () => {
-        const offerId = new anchor.BN(1005);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -871,7 +886,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferTwo(
-                offerId,
                 new anchor.BN(50e9), // buy token 1 amount
                 new anchor.BN(50e9), // buy token 2 amount
                 new anchor.BN(100e9), // sell token start
@@ -880,11 +894,12 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({
+            .accountsPartial({
                 sellTokenMint: sellTokenMint,
                 buyToken1Mint: buyToken1Mint,
                 buyToken2Mint: buyToken2Mint,
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
             .rpc();
@@ -935,15 +950,16 @@ describe("onreapp", () => {
         // Clean up - close the offer
         await program.methods
             .closeOfferTwo()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
     });
 
     it("Fails to take offer with two buy tokens due to exceeding sell limit", async () => {
-        const offerId = new anchor.BN(1004);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -959,7 +975,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferTwo(
-                offerId,
                 new anchor.BN(50e9), // buy token 1 amount
                 new anchor.BN(50e9), // buy token 2 amount
                 new anchor.BN(100e9), // sell token start
@@ -968,11 +983,12 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({
+            .accountsPartial({
                 sellTokenMint: sellTokenMint,
                 buyToken1Mint: buyToken1Mint,
                 buyToken2Mint: buyToken2Mint,
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction, offerBuyToken2AccountInstruction])
             .rpc();
@@ -1028,9 +1044,10 @@ describe("onreapp", () => {
         // Clean up - close the offer
         await program.methods
             .closeOfferTwo()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
     });
@@ -1041,15 +1058,15 @@ describe("onreapp", () => {
         await expect(program.methods.initialize().accounts({ boss: initialBoss.publicKey }).rpc()).rejects.toThrow(/already in use/);
     });
 
-    it("Fails to set boss from unauthorized account", async () => {
+    it("Fails to propose boss from unauthorized account", async () => {
         // Create a new wallet that is not the current boss
         const unauthorizedWallet = new anchor.Wallet(Keypair.generate());
         await airdropLamports(provider, unauthorizedWallet.publicKey, anchor.web3.LAMPORTS_PER_SOL * 20);
 
-        // Attempt to set a new boss from an unauthorized account
+        // Attempt to propose a new boss from an unauthorized account
         const newBoss = Keypair.generate().publicKey;
 
-        const setBossInstruction = await program.methods.setBoss(newBoss).accountsPartial({ state: statePda, boss: unauthorizedWallet.publicKey }).instruction();
+        const setBossInstruction = await program.methods.proposeBoss(newBoss).accountsPartial({ state: statePda, boss: unauthorizedWallet.publicKey }).instruction();
 
         const tx = new VersionedTransaction(
             new TransactionMessage({
@@ -1066,7 +1083,7 @@ describe("onreapp", () => {
 
     it("Handles very small token amounts correctly", async () => {
         // Create an offer with very small amounts to test edge cases in calculations
-        const offerId = new anchor.BN(1006);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -1084,7 +1101,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(buyTokenAmount), // buy token 1 amount
                 new anchor.BN(sellTokenAmount), // sell token start
                 new anchor.BN(sellTokenAmount), // sell token end
@@ -1092,10 +1108,11 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({
+            .accountsPartial({
                 sellTokenMint: sellTokenMint,
                 buyToken1Mint: buyToken1Mint,
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
             .rpc();
@@ -1144,16 +1161,17 @@ describe("onreapp", () => {
         // Clean up - close the offer
         await program.methods
             .closeOfferOne()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
     });
 
     it("Takes an offer completely with two users and fails on third attempt", async () => {
         // Create a new offer
-        const offerId = new anchor.BN(9999);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -1168,7 +1186,6 @@ describe("onreapp", () => {
         // Make an offer with 100e9 buy tokens for 100e9 sell tokens
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(100e9), // sell token start
                 new anchor.BN(100e9), // sell token end
@@ -1176,7 +1193,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
             .rpc();
 
@@ -1297,15 +1314,16 @@ describe("onreapp", () => {
         // Clean up - close the offer
         await program.methods
             .closeOfferOne()
-            .accounts({
+            .accountsPartial({
                 offer: offerPda,
                 state: statePda,
+                authority: initialBoss.publicKey,
             })
             .rpc();
     }, 10000);
 
     it("Takes an offer with one buy token successfully via permissionless route", async () => {
-        const offerId = new anchor.BN(5000);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -1320,7 +1338,6 @@ describe("onreapp", () => {
         // Create an offer with fixed pricing (start = end)
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(200e9), // sell token start
                 new anchor.BN(200e9), // sell token end
@@ -1328,7 +1345,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([offerSellTokenAccountInstruction, offerBuyToken1AccountInstruction])
             .rpc();
 
@@ -1340,15 +1357,16 @@ describe("onreapp", () => {
         await mintToAddress(provider, initialBoss.payer, sellTokenMint, userSellTokenAccount, initialBoss.publicKey, 100e9);
 
         // Derive intermediary authority PDA
-        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless-1")], program.programId);
+        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)], program.programId);
         const intermediaryBuyTokenAccount = await getAssociatedTokenAddress(buyToken1Mint, intermediaryAuthority, true);
         const intermediarySellTokenAccount = await getAssociatedTokenAddress(sellTokenMint, intermediaryAuthority, true);
 
         // Initialize permissionless account
         await program.methods
-            .initializePermissionlessAccount("permissionless-1")
-            .accounts({
+            .initializePermissionlessAccount(new anchor.BN(0), "permissionless-1")
+            .accountsPartial({
                 state: statePda,
+                boss: initialBoss.publicKey,
             })
             .signers([initialBoss.payer])
             .rpc();
@@ -1365,7 +1383,7 @@ describe("onreapp", () => {
 
         // Take the offer using permissionless route
         await program.methods
-            .takeOfferOnePermissionless(new anchor.BN(50e9))
+            .takeOfferOnePermissionless(new anchor.BN(0), new anchor.BN(50e9))
             .accounts({
                 offer: offerPda,
                 buyToken1Mint,
@@ -1399,11 +1417,11 @@ describe("onreapp", () => {
         expect(+intermediaryAccountInfo.value.amount).toEqual(0);
 
         // Clean up - close the offer
-        await program.methods.closeOfferOne().accounts({ offer: offerPda, state: statePda }).rpc();
+        await program.methods.closeOfferOne().accountsPartial({ offer: offerPda, state: statePda, authority: initialBoss.publicKey }).rpc();
     });
 
     it("Verifies intermediary account is properly created and persists", async () => {
-        const offerId = new anchor.BN(5001);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -1414,7 +1432,6 @@ describe("onreapp", () => {
         // Create offer
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(50e9), // buy token 1 amount
                 new anchor.BN(100e9), // sell token start
                 new anchor.BN(100e9), // sell token end
@@ -1422,7 +1439,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerSellTokenPda, offerAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerBuyToken1Pda, offerAuthority, buyToken1Mint),
@@ -1436,7 +1453,7 @@ describe("onreapp", () => {
         await mintToAddress(provider, initialBoss.payer, sellTokenMint, userSellTokenAccount, initialBoss.publicKey, 50e9);
 
         // Derive intermediary account for verification
-        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless-1")], program.programId);
+        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)], program.programId);
         const intermediaryBuyTokenAccount = await getAssociatedTokenAddress(buyToken1Mint, intermediaryAuthority, true);
         const intermediarySellTokenAccount = await getAssociatedTokenAddress(sellTokenMint, intermediaryAuthority, true);
 
@@ -1445,7 +1462,7 @@ describe("onreapp", () => {
 
         // Create a partial transaction to observe intermediary account creation
         const takeOfferIx = await program.methods
-            .takeOfferOnePermissionless(new anchor.BN(50e9))
+            .takeOfferOnePermissionless(new anchor.BN(0), new anchor.BN(50e9))
             .accounts({
                 offer: offerPda,
                 buyToken1Mint,
@@ -1462,11 +1479,11 @@ describe("onreapp", () => {
         expect(+intermediaryTokenAccountInfo.value.amount).toEqual(0);
 
         // Clean up
-        await program.methods.closeOfferOne().accounts({ offer: offerPda, state: statePda }).rpc();
+        await program.methods.closeOfferOne().accountsPartial({ offer: offerPda, state: statePda, authority: initialBoss.publicKey }).rpc();
     });
 
     it("Fails to take offer via permissionless route with invalid buy token mint", async () => {
-        const offerId = new anchor.BN(5002);
+        const offerId = (await program.account.state.fetch(statePda)).nextOfferId;
         const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
         const offerSellTokenPda = await getAssociatedTokenAddress(sellTokenMint, offerAuthority, true);
@@ -1477,7 +1494,6 @@ describe("onreapp", () => {
         // Create offer
         await program.methods
             .makeOfferOne(
-                offerId,
                 new anchor.BN(100e9), // buy token 1 amount
                 new anchor.BN(200e9), // sell token start
                 new anchor.BN(200e9), // sell token end
@@ -1485,7 +1501,7 @@ describe("onreapp", () => {
                 new anchor.BN(currentTime + 7200), // offer end
                 new anchor.BN(3600), // offer interval
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerSellTokenPda, offerAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerBuyToken1Pda, offerAuthority, buyToken1Mint),
@@ -1499,7 +1515,7 @@ describe("onreapp", () => {
         await mintToAddress(provider, initialBoss.payer, sellTokenMint, userSellTokenAccount, initialBoss.publicKey, 100e9);
 
         // Derive intermediary authority
-        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless-1")], program.programId);
+        const [intermediaryAuthority] = PublicKey.findProgramAddressSync([Buffer.from("permissionless"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)], program.programId);
 
         // Pre-create intermediary token accounts with CORRECT mints (not the wrong one)
         await createIntermediaryAccountsIfNeeded(provider, user, buyToken1Mint, sellTokenMint, program.programId);
@@ -1509,7 +1525,7 @@ describe("onreapp", () => {
         // (we created it with buyToken1Mint above)
         await expect(
             program.methods
-                .takeOfferOnePermissionless(new anchor.BN(50e9))
+                .takeOfferOnePermissionless(new anchor.BN(0), new anchor.BN(50e9))
                 .accounts({
                     offer: offerPda,
                     buyToken1Mint: buyToken2Mint, // Wrong mint!
@@ -1521,13 +1537,13 @@ describe("onreapp", () => {
         ).rejects.toThrow(/AccountNotInitialized|InvalidBuyTokenMint/);
 
         // Clean up
-        await program.methods.closeOfferOne().accounts({ offer: offerPda, state: statePda }).rpc();
+        await program.methods.closeOfferOne().accountsPartial({ offer: offerPda, state: statePda, authority: initialBoss.publicKey }).rpc();
     });
 
     it("Compares permissionless route with regular route - same economic outcome", async () => {
         // Create two identical offers for comparison
-        const regularOfferId = new anchor.BN(5004);
-        const permissionlessOfferId = new anchor.BN(5005);
+        const regularOfferId = (await program.account.state.fetch(statePda)).nextOfferId;
+        const permissionlessOfferId = regularOfferId.addn(1);
 
         // Setup for regular offer
         const [regularOfferAuthority] = PublicKey.findProgramAddressSync([Buffer.from("offer_authority"), regularOfferId.toArrayLike(Buffer, "le", 8)], program.programId);
@@ -1558,7 +1574,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                regularOfferId,
                 offerParams.buyTokenAmount,
                 offerParams.sellTokenStart,
                 offerParams.sellTokenEnd,
@@ -1566,7 +1581,7 @@ describe("onreapp", () => {
                 offerParams.offerEnd,
                 offerParams.priceInterval,
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, regularOfferSellTokenPda, regularOfferAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, regularOfferBuyToken1Pda, regularOfferAuthority, buyToken1Mint),
@@ -1575,7 +1590,6 @@ describe("onreapp", () => {
 
         await program.methods
             .makeOfferOne(
-                permissionlessOfferId,
                 offerParams.buyTokenAmount,
                 offerParams.sellTokenStart,
                 offerParams.sellTokenEnd,
@@ -1583,7 +1597,7 @@ describe("onreapp", () => {
                 offerParams.offerEnd,
                 offerParams.priceInterval,
             )
-            .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+            .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
             .preInstructions([
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, permissionlessOfferSellTokenPda, permissionlessOfferAuthority, sellTokenMint),
                 createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, permissionlessOfferBuyToken1Pda, permissionlessOfferAuthority, buyToken1Mint),
@@ -1624,7 +1638,7 @@ describe("onreapp", () => {
 
         // Take permissionless offer
         await program.methods
-            .takeOfferOnePermissionless(sellAmount)
+            .takeOfferOnePermissionless(new anchor.BN(0), sellAmount)
             .accounts({
                 offer: permissionlessOfferPda,
                 buyToken1Mint,
@@ -1650,13 +1664,13 @@ describe("onreapp", () => {
         expect(+permissionlessUserSellBalance.value.amount).toEqual(40e9);
 
         // Clean up
-        await program.methods.closeOfferOne().accounts({ offer: regularOfferPda, state: statePda }).rpc();
-        await program.methods.closeOfferOne().accounts({ offer: permissionlessOfferPda, state: statePda }).rpc();
+        await program.methods.closeOfferOne().accountsPartial({ offer: regularOfferPda, state: statePda, authority: initialBoss.publicKey }).rpc();
+        await program.methods.closeOfferOne().accountsPartial({ offer: permissionlessOfferPda, state: statePda, authority: initialBoss.publicKey }).rpc();
     });
 
     it("Handles multiple permissionless transactions from same user with different offer IDs", async () => {
-        const offerId1 = new anchor.BN(5006);
-        const offerId2 = new anchor.BN(5007);
+        const offerId1 = (await program.account.state.fetch(statePda)).nextOfferId;
+        const offerId2 = offerId1.addn(1);
 
         // Create both offers
         for (const offerId of [offerId1, offerId2]) {
@@ -1668,7 +1682,6 @@ describe("onreapp", () => {
 
             await program.methods
                 .makeOfferOne(
-                    offerId,
                     new anchor.BN(50e9), // buy token amount
                     new anchor.BN(100e9), // sell token start
                     new anchor.BN(100e9), // sell token end
@@ -1676,7 +1689,7 @@ describe("onreapp", () => {
                     new anchor.BN(currentTime + 7200), // offer end
                     new anchor.BN(3600), // offer interval
                 )
-                .accounts({ sellTokenMint, buyToken1Mint, state: statePda })
+                .accountsPartial({ sellTokenMint, buyToken1Mint, state: statePda, boss: initialBoss.publicKey })
                 .preInstructions([
                     createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerSellTokenPda, offerAuthority, sellTokenMint),
                     createAssociatedTokenAccountInstruction(initialBoss.payer.publicKey, offerBuyToken1Pda, offerAuthority, buyToken1Mint),
@@ -1698,7 +1711,7 @@ describe("onreapp", () => {
             const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
 
             await program.methods
-                .takeOfferOnePermissionless(new anchor.BN(50e9))
+                .takeOfferOnePermissionless(new anchor.BN(0), new anchor.BN(50e9))
                 .accounts({
                     offer: offerPda,
                     buyToken1Mint,
@@ -1719,7 +1732,7 @@ describe("onreapp", () => {
         // Clean up both offers
         for (const offerId of [offerId1, offerId2]) {
             const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from("offer"), offerId.toArrayLike(Buffer, "le", 8)], program.programId);
-            await program.methods.closeOfferOne().accounts({ offer: offerPda, state: statePda }).rpc();
+            await program.methods.closeOfferOne().accountsPartial({ offer: offerPda, state: statePda, authority: initialBoss.publicKey }).rpc();
         }
     });
 
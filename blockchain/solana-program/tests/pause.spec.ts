@@ -0,0 +1,120 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("pause", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let pauser: Keypair;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+        pauser = testHelper.createUserAccount();
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+        await testHelper.grantRole({ pauser: {} }, pauser.publicKey);
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(600e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return offerPda;
+    }
+
+    function createUser(): Keypair {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return user;
+    }
+
+    test("Take offer while the program is paused by the pauser should fail until unpaused", async () => {
+        // given
+        const user = createUser();
+        const offerPda = await makeOffer();
+
+        // when
+        await testHelper.setPaused(true, pauser);
+
+        // then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*ProgramPaused.*"));
+
+        await testHelper.setPaused(false);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+    });
+
+    test("Take a paused offer should fail while other offers stay open", async () => {
+        // given
+        const user = createUser();
+        const pausedOfferPda = await makeOffer();
+        const openOfferPda = await makeOffer();
+
+        // when
+        await testHelper.setOfferPaused(pausedOfferPda, true);
+
+        // then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda: pausedOfferPda, user }))
+            .rejects.toThrow(RegExp(".*OfferPaused.*"));
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda: openOfferPda, user });
+
+        await testHelper.setOfferPaused(pausedOfferPda, false, pauser);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda: pausedOfferPda, user });
+    });
+
+    test("Pause by a key without the pauser role should fail", async () => {
+        // given
+        const attacker = testHelper.createUserAccount();
+        const offerPda = await makeOffer();
+
+        // when / then
+        await expect(testHelper.setPaused(true, attacker))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+        await expect(testHelper.setOfferPaused(offerPda, true, attacker))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+});
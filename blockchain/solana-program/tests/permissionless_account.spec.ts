@@ -0,0 +1,192 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("permissionless accounts", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda };
+    }
+
+    async function createPermissionlessAccount(index: number, name: string) {
+        await testHelper.initializePermissionlessAccount(index, name);
+        const permissionlessAccount = testHelper.getPermissionlessAccountPda(index);
+        const sellTokenAccount = testHelper.createTokenAccount(sellTokenMint, permissionlessAccount, BigInt(0), true);
+        const buyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, permissionlessAccount, BigInt(0), true);
+        return { permissionlessAccount, sellTokenAccount, buyTokenAccount };
+    }
+
+    function createUser() {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userBuyTokenAccount };
+    }
+
+    test("Takes should route through any enabled permissionless account", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+        const { permissionlessAccount: firstAccount } = await createPermissionlessAccount(1, "First partner");
+        await createPermissionlessAccount(2, "Second partner");
+        const { user, userBuyTokenAccount } = createUser();
+
+        // when
+        await testHelper.takeOfferOnePermissionless({ intermediaryIndex: 1, sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.takeOfferOnePermissionless({ intermediaryIndex: 2, sellTokenAmount: 20e9, offerPda, user });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(30e9));
+        const account = await testHelper.program.account.permissionlessAccount.fetch(firstAccount);
+        expect(account.name).toEqual("First partner");
+        expect(account.index.toNumber()).toEqual(1);
+        expect(account.enabled).toBe(true);
+    });
+
+    test("Takes through a disabled permissionless account should fail until it is enabled again", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+        await createPermissionlessAccount(3, "Third partner");
+        const { user, userBuyTokenAccount } = createUser();
+        await testHelper.setPermissionlessAccountEnabled(3, false);
+
+        // when / then
+        await expect(testHelper.takeOfferOnePermissionless({ intermediaryIndex: 3, sellTokenAmount: 10e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*PermissionlessAccountDisabled.*"));
+
+        await testHelper.setPermissionlessAccountEnabled(3, true);
+        await testHelper.takeOfferOnePermissionless({ intermediaryIndex: 3, sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+    });
+
+    test("Managing permissionless accounts should require the admin role", async () => {
+        // given
+        await createPermissionlessAccount(4, "Fourth partner");
+        const stranger = testHelper.createUserAccount();
+
+        // when / then
+        await expect(testHelper.program.methods
+            .setPermissionlessAccountEnabled(false)
+            .accountsPartial({ permissionlessAccount: testHelper.getPermissionlessAccountPda(4), state: testHelper.statePda, authority: stranger.publicKey })
+            .signers([stranger])
+            .rpc()).rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+
+    test("Sweeping an intermediary account should move its stray balance to the boss", async () => {
+        // given
+        const { buyTokenAccount } = await createPermissionlessAccount(5, "Fifth partner");
+        testHelper.createTokenAccount(buyToken1Mint, testHelper.getPermissionlessAccountPda(5), BigInt(7e9), true);
+        const bossBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, boss, BigInt(0), true);
+
+        // when
+        await testHelper.sweepIntermediary(5, buyToken1Mint);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(buyTokenAccount, BigInt(0));
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(7e9));
+    });
+
+    test("Sweeping an intermediary account should require the boss", async () => {
+        // given
+        await createPermissionlessAccount(6, "Sixth partner");
+        const stranger = testHelper.createUserAccount();
+
+        // when / then
+        await expect(testHelper.program.methods
+            .sweepIntermediary()
+            .accountsPartial({ permissionlessAccount: testHelper.getPermissionlessAccountPda(6), mint: buyToken1Mint, state: testHelper.statePda, boss: stranger.publicKey })
+            .signers([stranger])
+            .rpc()).rejects.toThrow(RegExp(".*ConstraintHasOne.*"));
+    });
+
+    test("Closing an intermediary should close its token accounts and the permissionless account", async () => {
+        // given
+        const { permissionlessAccount, sellTokenAccount, buyTokenAccount } = await createPermissionlessAccount(7, "Seventh partner");
+
+        // when
+        await testHelper.closeIntermediary(7, [sellTokenAccount, buyTokenAccount]);
+
+        // then
+        for (const account of [permissionlessAccount, sellTokenAccount, buyTokenAccount]) {
+            expect(await testHelper.context.banksClient.getAccount(account)).toBeNull();
+        }
+    });
+
+    test("Closing an intermediary with a stray balance should fail until it is swept", async () => {
+        // given
+        const { sellTokenAccount, buyTokenAccount } = await createPermissionlessAccount(8, "Eighth partner");
+        testHelper.createTokenAccount(sellTokenMint, testHelper.getPermissionlessAccountPda(8), BigInt(3e9), true);
+
+        // when / then
+        await expect(testHelper.closeIntermediary(8, [sellTokenAccount, buyTokenAccount]))
+            .rejects.toThrow(RegExp(".*IntermediaryNotEmpty.*"));
+
+        await testHelper.sweepIntermediary(8, sellTokenMint);
+        await testHelper.closeIntermediary(8, [sellTokenAccount, buyTokenAccount]);
+        expect(await testHelper.context.banksClient.getAccount(sellTokenAccount)).toBeNull();
+    });
+
+    test("Closing an intermediary should reject token accounts of other owners", async () => {
+        // given
+        await createPermissionlessAccount(9, "Ninth partner");
+        const { userBuyTokenAccount } = createUser();
+
+        // when / then
+        await expect(testHelper.closeIntermediary(9, [userBuyTokenAccount]))
+            .rejects.toThrow(RegExp(".*InvalidIntermediaryAccount.*"));
+    });
+});
@@ -0,0 +1,122 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("proceeds", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let treasury: PublicKey;
+    let treasurySellTokenAccount: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+        treasury = testHelper.createUserAccount().publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        treasurySellTokenAccount = testHelper.createTokenAccount(sellTokenMint, treasury, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeTakenOffer() {
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        await testHelper.takeOfferOne({ sellTokenAmount: 40e9, offerPda, user });
+
+        return { offerPda, offerSellTokenPda, offerBuyTokenPda };
+    }
+
+    test("Withdraw proceeds before a treasury is set should fail", async () => {
+        // given
+        const { offerPda, offerSellTokenPda } = await makeTakenOffer();
+
+        // when / then
+        await expect(testHelper.withdrawProceeds(offerPda, offerSellTokenPda, 10e9))
+            .rejects.toThrow(RegExp(".*TreasuryNotSet.*"));
+    });
+
+    test("Treasurer should withdraw part of the proceeds to the treasury", async () => {
+        // given
+        const treasurer = testHelper.createUserAccount();
+        const { offerPda, offerSellTokenPda, offerBuyTokenPda } = await makeTakenOffer();
+        await testHelper.setTreasury(treasury);
+        await testHelper.grantRole({ treasurer: {} }, treasurer.publicKey);
+
+        // when
+        await testHelper.withdrawProceeds(offerPda, treasurySellTokenAccount, 30e9, treasurer);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(treasurySellTokenAccount, BigInt(30e9));
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(offerBuyTokenPda, BigInt(60e9));
+        await expect(testHelper.withdrawProceeds(offerPda, treasurySellTokenAccount, 20e9, treasurer))
+            .rejects.toThrow(RegExp(".*InvalidAmount.*"));
+        await testHelper.revokeRole({ treasurer: {} }, treasurer.publicKey);
+    });
+
+    test("Withdraw proceeds to an account not owned by the treasury should fail", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const outsiderSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, outsider.publicKey, BigInt(0));
+        const { offerPda } = await makeTakenOffer();
+
+        // when / then
+        await expect(testHelper.withdrawProceeds(offerPda, outsiderSellTokenAccount, 10e9))
+            .rejects.toThrow(RegExp(".*InvalidTreasuryAccount.*"));
+    });
+
+    test("Withdraw proceeds by a key without the treasurer role should fail", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const { offerPda } = await makeTakenOffer();
+
+        // when / then
+        await expect(testHelper.withdrawProceeds(offerPda, treasurySellTokenAccount, 10e9, outsider))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+});
@@ -0,0 +1,161 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("protocol fee", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let feeVault: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        feeVault = testHelper.createTokenAccount(sellTokenMint, testHelper.getFeeVaultAuthorityPda(), BigInt(0), true);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, offerSellTokenPda, offerBuyTokenPda };
+    }
+
+    function createUser(): { user: Keypair, userBuyTokenAccount: PublicKey } {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userBuyTokenAccount };
+    }
+
+    test("Take offer should deduct the protocol fee and send it to the fee vault", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerSellTokenPda } = await makeOffer();
+        await testHelper.setProtocolFee(100);
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, feeVault });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(feeVault, BigInt(0.1e9));
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(9.9e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(9.9e9));
+        await testHelper.setProtocolFee(0);
+    });
+
+    test("Offer fee should override the protocol fee", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerSellTokenPda } = await makeOffer();
+        await testHelper.setProtocolFee(100);
+        await testHelper.setOfferFee(offerPda, 0);
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+
+        // then
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.feeBps).toEqual(0);
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+        await testHelper.setProtocolFee(0);
+    });
+
+    test("Take offer with a fee due and no or a wrong fee vault should fail", async () => {
+        // given
+        const { user } = createUser();
+        const { offerPda, offerSellTokenPda } = await makeOffer();
+        await testHelper.setOfferFee(offerPda, 50);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*MissingFeeVault.*"));
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, feeVault: offerSellTokenPda }))
+            .rejects.toThrow(RegExp(".*InvalidFeeVault.*"));
+    });
+
+    test("Treasurer should withdraw accumulated fees", async () => {
+        // given
+        const { user } = createUser();
+        const treasurer = testHelper.createUserAccount();
+        const treasury = testHelper.createTokenAccount(sellTokenMint, treasurer.publicKey, BigInt(0));
+        const { offerPda } = await makeOffer();
+        await testHelper.setOfferFee(offerPda, 1_000);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, feeVault });
+        await testHelper.grantRole({ treasurer: {} }, treasurer.publicKey);
+
+        // when
+        await testHelper.withdrawFees(sellTokenMint, treasury, 1e9, treasurer);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(treasury, BigInt(1e9));
+        await expect(testHelper.withdrawFees(sellTokenMint, treasury, 100e9, treasurer))
+            .rejects.toThrow(RegExp(".*InvalidAmount.*"));
+        await testHelper.revokeRole({ treasurer: {} }, treasurer.publicKey);
+    });
+
+    test("Withdraw fees by a key without the treasurer role should fail", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const destination = testHelper.createTokenAccount(sellTokenMint, outsider.publicKey, BigInt(0));
+
+        // when / then
+        await expect(testHelper.withdrawFees(sellTokenMint, destination, 1, outsider))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+
+    test("Fee above the maximum should fail", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.setProtocolFee(1_001))
+            .rejects.toThrow(RegExp(".*InvalidFeeBps.*"));
+        await expect(testHelper.setOfferFee(offerPda, 1_001))
+            .rejects.toThrow(RegExp(".*InvalidFeeBps.*"));
+    });
+});
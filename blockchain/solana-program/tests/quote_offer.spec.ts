@@ -0,0 +1,164 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("quote offer", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+    let buyToken2Mint: PublicKey;
+    let feeVault: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken2Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        feeVault = testHelper.createTokenAccount(sellTokenMint, testHelper.getFeeVaultAuthorityPda(), BigInt(0), true);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 10800,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, offerStartTime };
+    }
+
+    test("Quote should return the current interval, price and next price change", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        const quote = await testHelper.quoteOffer(offerPda, { sellTokenAmount: 30e9 });
+
+        // then
+        expect(quote.interval.toNumber()).toEqual(1);
+        expect(quote.numberOfIntervals.toNumber()).toEqual(3);
+        expect(quote.currentSellTokenAmount.toNumber()).toEqual(150e9);
+        expect(quote.sellTokenAmount.toNumber()).toEqual(30e9);
+        expect(quote.feeAmount.toNumber()).toEqual(0);
+        expect(quote.buyToken1Amount.toNumber()).toEqual(20e9);
+        expect(quote.buyToken2Amount.toNumber()).toEqual(0);
+        expect(quote.nextPriceChangeTime.toNumber()).toEqual(offerStartTime + 7200);
+    });
+
+    test("Quote in the last interval should report no further price change", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+        await testHelper.advanceClockBy(7200);
+
+        // when
+        const quote = await testHelper.quoteOffer(offerPda, { sellTokenAmount: 20e9 });
+
+        // then
+        expect(quote.currentSellTokenAmount.toNumber()).toEqual(200e9);
+        expect(quote.buyToken1Amount.toNumber()).toEqual(10e9);
+        expect(quote.nextPriceChangeTime.toNumber()).toEqual(0);
+    });
+
+    test("Exact buy quote should match what a take of the quoted sell amount receives", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+        await testHelper.setProtocolFee(100);
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+
+        // when
+        const quote = await testHelper.quoteOffer(offerPda, { buyToken1Amount: 7e9 });
+        await testHelper.takeOfferOne({ sellTokenAmount: quote.sellTokenAmount.toNumber(), offerPda, user, feeVault });
+
+        // then
+        expect(quote.buyToken1Amount.toNumber()).toBeGreaterThanOrEqual(7e9);
+        expect(quote.feeAmount.toNumber()).toEqual(Math.floor(quote.sellTokenAmount.toNumber() / 100));
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(quote.buyToken1Amount.toNumber()));
+        await testHelper.setProtocolFee(0);
+    });
+
+    test("Quote of a two token offer should return both buy amounts", async () => {
+        // given
+        const { offerId, offerPda } = await testHelper.createTwoTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            buyToken2Mint, BigInt(0),
+            boss, BigInt(100e9), BigInt(50e9)
+        );
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferTwo({
+            offerId,
+            buyToken1TotalAmount: 100e9,
+            buyToken2TotalAmount: 50e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyToken1Mint,
+            buyToken2Mint,
+        });
+
+        // when
+        const quote = await testHelper.quoteOffer(offerPda, { sellTokenAmount: 40e9 });
+
+        // then
+        expect(quote.buyToken1Amount.toNumber()).toEqual(40e9);
+        expect(quote.buyToken2Amount.toNumber()).toEqual(20e9);
+        expect(quote.nextPriceChangeTime.toNumber()).toEqual(0);
+    });
+
+    test("Quote of a zero amount or an inactive offer should fail", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.quoteOffer(offerPda, { buyToken1Amount: 0 }))
+            .rejects.toThrow(RegExp(".*InvalidQuoteAmount.*"));
+        await testHelper.advanceClockBy(10800);
+        await expect(testHelper.quoteOffer(offerPda, { sellTokenAmount: 10e9 }))
+            .rejects.toThrow(RegExp(".*InvalidCurrentTime.*"));
+    });
+});
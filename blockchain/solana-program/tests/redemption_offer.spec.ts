@@ -0,0 +1,266 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("redemption offer", () => {
+    let testHelper: TestHelper;
+
+    let redeemTokenMint: PublicKey;
+    let payoutTokenMint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        redeemTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        payoutTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    test("Take redemption offer should escrow tendered tokens and pay out at the scheduled price", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        const userRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        const userPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda, offerPayoutTokenPda, offerRedeemTokenPda } = testHelper.createRedemptionOfferAccounts(
+            redeemTokenMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1.02e9,
+            priceEnd: 1.02e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 3600,
+            epochCap: 500e9,
+            redeemTokenMint,
+            payoutTokenMint,
+        });
+
+        // when
+        await testHelper.takeRedemptionOffer({
+            redeemTokenAmount: 10e9,
+            offerPda,
+            redeemTokenMint,
+            user,
+        });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userRedeemTokenAccount, BigInt(90e9));
+        await testHelper.expectTokenAccountAmountToBe(offerRedeemTokenPda, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(userPayoutTokenAccount, BigInt(10.2e9));
+        await testHelper.expectTokenAccountAmountToBe(offerPayoutTokenPda, BigInt(989.8e9));
+    });
+
+    test("Take redemption offer should burn tendered tokens when the program holds the mint authority", async () => {
+        // given
+        const [mintAuthority] = PublicKey.findProgramAddressSync([Buffer.from("mint_authority")], ONREAPP_PROGRAM_ID);
+        const burnableMint = testHelper.createMintWithAuthority(mintAuthority, BigInt(100e9), 9);
+
+        const user = testHelper.createUserAccount();
+        const userRedeemTokenAccount = testHelper.createTokenAccount(burnableMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda, offerRedeemTokenPda } = testHelper.createRedemptionOfferAccounts(
+            burnableMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1e9,
+            priceEnd: 1e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 3600,
+            epochCap: 500e9,
+            redeemTokenMint: burnableMint,
+            payoutTokenMint,
+        });
+
+        // when
+        await testHelper.takeRedemptionOffer({
+            redeemTokenAmount: 10e9,
+            offerPda,
+            redeemTokenMint: burnableMint,
+            user,
+        });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userRedeemTokenAccount, BigInt(90e9));
+        await testHelper.expectTokenAccountAmountToBe(offerRedeemTokenPda, BigInt(0));
+        expect(await testHelper.getMintSupply(burnableMint)).toBe(BigInt(90e9));
+    });
+
+    test("Take redemption offer above the epoch cap should fail until the next epoch", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        const userRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda } = testHelper.createRedemptionOfferAccounts(
+            redeemTokenMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1e9,
+            priceEnd: 1e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 3600,
+            epochCap: 15e9,
+            redeemTokenMint,
+            payoutTokenMint,
+        });
+        await testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user });
+
+        // when / then
+        await expect(testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user }))
+            .rejects.toThrow(RegExp(".*EpochCapExceeded.*"));
+
+        await testHelper.advanceClockBy(3600);
+        await testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user });
+        await testHelper.expectTokenAccountAmountToBe(userRedeemTokenAccount, BigInt(80e9));
+    });
+
+    test("Take redemption offer with descending schedule should use the last interval price", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        const userPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda } = testHelper.createRedemptionOfferAccounts(
+            redeemTokenMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1e9,
+            priceEnd: 0.9e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 7200,
+            epochCap: 500e9,
+            redeemTokenMint,
+            payoutTokenMint,
+        });
+
+        // time travel to the last interval
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userPayoutTokenAccount, BigInt(9e9));
+    });
+
+    test("Close redemption offer should return liquidity and escrowed tokens to the boss", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda, bossPayoutTokenAccount } = testHelper.createRedemptionOfferAccounts(
+            redeemTokenMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+        const bossRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, boss, BigInt(0));
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1e9,
+            priceEnd: 1e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 3600,
+            epochCap: 500e9,
+            redeemTokenMint,
+            payoutTokenMint,
+        });
+        await testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user });
+
+        // when
+        await testHelper.closeRedemptionOffer(offerPda);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(bossPayoutTokenAccount, BigInt(990e9));
+        await testHelper.expectTokenAccountAmountToBe(bossRedeemTokenAccount, BigInt(10e9));
+        expect(await testHelper.context.banksClient.getAccount(offerPda)).toBeNull();
+    });
+
+    test("Take redemption offer while the program is paused should fail until unpaused", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        const userPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+
+        const { offerId, offerPda } = testHelper.createRedemptionOfferAccounts(
+            redeemTokenMint, payoutTokenMint, boss, BigInt(1000e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeRedemptionOffer({
+            offerId,
+            payoutTokenTotalAmount: 1000e9,
+            priceStart: 1e9,
+            priceEnd: 1e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            epochDuration: 3600,
+            epochCap: 500e9,
+            redeemTokenMint,
+            payoutTokenMint,
+        });
+
+        // when
+        await testHelper.setPaused(true);
+
+        // then
+        await expect(testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user }))
+            .rejects.toThrow(RegExp(".*ProgramPaused.*"));
+
+        await testHelper.setPaused(false);
+        await testHelper.takeRedemptionOffer({ redeemTokenAmount: 10e9, offerPda, redeemTokenMint, user });
+        await testHelper.expectTokenAccountAmountToBe(userPayoutTokenAccount, BigInt(10e9));
+    });
+});
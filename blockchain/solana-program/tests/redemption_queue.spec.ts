@@ -0,0 +1,160 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("redemption queue", () => {
+    let testHelper: TestHelper;
+
+    let redeemTokenMint: PublicKey;
+    let payoutTokenMint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        redeemTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        payoutTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    test("Settle redemption epoch with partial funding should fill all requests pro-rata", async () => {
+        // given
+        const user1 = testHelper.createUserAccount();
+        const user1RedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user1.publicKey, BigInt(100e9), true);
+        const user1PayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user1.publicKey, BigInt(0), true);
+        const user2 = testHelper.createUserAccount();
+        const user2RedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user2.publicKey, BigInt(100e9), true);
+        const user2PayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user2.publicKey, BigInt(0), true);
+        const bossPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, boss, BigInt(100e9));
+        const bossRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, boss, BigInt(0));
+
+        const { queueId, queuePda, queueRedeemTokenPda, queuePayoutTokenPda } = testHelper.createRedemptionQueueAccounts(redeemTokenMint, payoutTokenMint);
+        await testHelper.initializeRedemptionQueue(queueId, redeemTokenMint, payoutTokenMint);
+
+        await testHelper.requestRedemption(queuePda, 30e9, user1);
+        await testHelper.requestRedemption(queuePda, 10e9, user2);
+
+        // when
+        // 21 payout tokens at a price of 1.05 fill 20 of the 40 requested tokens
+        await testHelper.settleRedemptionEpoch(queuePda, 21e9, 1.05e9);
+        await testHelper.claimRedemption(queuePda, redeemTokenMint, user1);
+        await testHelper.claimRedemption(queuePda, redeemTokenMint, user2);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(bossPayoutTokenAccount, BigInt(79e9));
+        await testHelper.expectTokenAccountAmountToBe(user1PayoutTokenAccount, BigInt(15.75e9));
+        await testHelper.expectTokenAccountAmountToBe(user1RedeemTokenAccount, BigInt(85e9));
+        await testHelper.expectTokenAccountAmountToBe(user2PayoutTokenAccount, BigInt(5.25e9));
+        await testHelper.expectTokenAccountAmountToBe(user2RedeemTokenAccount, BigInt(95e9));
+        await testHelper.expectTokenAccountAmountToBe(bossRedeemTokenAccount, BigInt(20e9));
+        await testHelper.expectTokenAccountAmountToBe(queueRedeemTokenPda, BigInt(0));
+        await testHelper.expectTokenAccountAmountToBe(queuePayoutTokenPda, BigInt(0));
+        expect(await testHelper.context.banksClient.getAccount(testHelper.getRedemptionRequestPda(queuePda, user1.publicKey))).toBeNull();
+    });
+
+    test("Settle redemption epoch with excess funding should only take the payout needed", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        const userPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+        const bossPayoutTokenAccount = testHelper.createTokenAccount(payoutTokenMint, boss, BigInt(100e9));
+        testHelper.createTokenAccount(redeemTokenMint, boss, BigInt(0));
+
+        const { queueId, queuePda } = testHelper.createRedemptionQueueAccounts(redeemTokenMint, payoutTokenMint);
+        await testHelper.initializeRedemptionQueue(queueId, redeemTokenMint, payoutTokenMint);
+        await testHelper.requestRedemption(queuePda, 10e9, user);
+
+        // when
+        await testHelper.settleRedemptionEpoch(queuePda, 50e9, 1e9);
+        await testHelper.claimRedemption(queuePda, redeemTokenMint, user);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(bossPayoutTokenAccount, BigInt(90e9));
+        await testHelper.expectTokenAccountAmountToBe(userPayoutTokenAccount, BigInt(10e9));
+    });
+
+    test("Cancel redemption before settlement should return the locked tokens", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        const userRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+
+        const { queueId, queuePda, queueRedeemTokenPda } = testHelper.createRedemptionQueueAccounts(redeemTokenMint, payoutTokenMint);
+        await testHelper.initializeRedemptionQueue(queueId, redeemTokenMint, payoutTokenMint);
+        await testHelper.requestRedemption(queuePda, 10e9, user);
+        await testHelper.requestRedemption(queuePda, 5e9, user);
+
+        // when
+        await testHelper.cancelRedemption(queuePda, user);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userRedeemTokenAccount, BigInt(100e9));
+        await testHelper.expectTokenAccountAmountToBe(queueRedeemTokenPda, BigInt(0));
+        const epoch = await testHelper.program.account.redemptionEpoch.fetch(testHelper.getRedemptionEpochPda(queuePda, 0));
+        expect(epoch.totalRequested.toNumber()).toBe(0);
+    });
+
+    test("Claim redemption before settlement and cancel after settlement should fail", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+        testHelper.createTokenAccount(payoutTokenMint, user.publicKey, BigInt(0), true);
+        testHelper.createTokenAccount(payoutTokenMint, boss, BigInt(100e9));
+        testHelper.createTokenAccount(redeemTokenMint, boss, BigInt(0));
+
+        const { queueId, queuePda } = testHelper.createRedemptionQueueAccounts(redeemTokenMint, payoutTokenMint);
+        await testHelper.initializeRedemptionQueue(queueId, redeemTokenMint, payoutTokenMint);
+        await testHelper.requestRedemption(queuePda, 10e9, user);
+
+        // when / then
+        await expect(testHelper.claimRedemption(queuePda, redeemTokenMint, user))
+            .rejects.toThrow(RegExp(".*EpochNotSettled.*"));
+
+        await testHelper.settleRedemptionEpoch(queuePda, 5e9, 1e9);
+        await expect(testHelper.cancelRedemption(queuePda, user))
+            .rejects.toThrow(RegExp(".*EpochAlreadySettled.*"));
+        await expect(testHelper.requestRedemption(queuePda, 10e9, user))
+            .rejects.toThrow(RegExp(".*UnclaimedRequest.*"));
+    });
+
+    test("Request redemption while the program is paused should fail until unpaused", async () => {
+        // given
+        const user = testHelper.createUserAccount();
+        const userRedeemTokenAccount = testHelper.createTokenAccount(redeemTokenMint, user.publicKey, BigInt(100e9), true);
+
+        const { queueId, queuePda } = testHelper.createRedemptionQueueAccounts(redeemTokenMint, payoutTokenMint);
+        await testHelper.initializeRedemptionQueue(queueId, redeemTokenMint, payoutTokenMint);
+
+        // when
+        await testHelper.setPaused(true);
+
+        // then
+        await expect(testHelper.requestRedemption(queuePda, 10e9, user))
+            .rejects.toThrow(RegExp(".*ProgramPaused.*"));
+
+        await testHelper.setPaused(false);
+        await testHelper.requestRedemption(queuePda, 10e9, user);
+        await testHelper.expectTokenAccountAmountToBe(userRedeemTokenAccount, BigInt(90e9));
+    });
+});
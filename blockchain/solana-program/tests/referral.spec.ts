@@ -0,0 +1,160 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("referral", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let referralVault: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        referralVault = testHelper.createTokenAccount(sellTokenMint, testHelper.getReferralVaultAuthorityPda(), BigInt(0), true);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer(referralFeeBps: number) {
+        const { offerId, offerPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        await testHelper.setOfferReferralFee(offerPda, referralFeeBps);
+
+        return { offerPda, offerSellTokenPda };
+    }
+
+    function createUser(): { user: Keypair, userBuyTokenAccount: PublicKey } {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userBuyTokenAccount };
+    }
+
+    test("Referred take should pay the referral fee out of the offer proceeds to the referrer, who claims it", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const referrer = testHelper.createUserAccount();
+        const referrerTokenAccount = testHelper.createTokenAccount(sellTokenMint, referrer.publicKey, BigInt(0));
+        const referrerAccount = await testHelper.registerReferrer(sellTokenMint, referrer);
+        const { offerPda, offerSellTokenPda } = await makeOffer(50);
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, referrerAccount, referralVault });
+
+        // then
+        let account = await testHelper.program.account.referrerAccount.fetch(referrerAccount);
+        expect(account.balance.toNumber()).toEqual(0.05e9);
+        expect(account.totalEarned.toNumber()).toEqual(0.05e9);
+        await testHelper.expectTokenAccountAmountToBe(referralVault, BigInt(0.05e9));
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(9.95e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+
+        // when
+        await testHelper.claimReferralFees(sellTokenMint, referrerTokenAccount, referrer);
+
+        // then
+        account = await testHelper.program.account.referrerAccount.fetch(referrerAccount);
+        expect(account.balance.toNumber()).toEqual(0);
+        expect(account.totalEarned.toNumber()).toEqual(0.05e9);
+        await testHelper.expectTokenAccountAmountToBe(referrerTokenAccount, BigInt(0.05e9));
+        await expect(testHelper.claimReferralFees(sellTokenMint, referrerTokenAccount, referrer))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+    });
+
+    test("Take without a referrer should not charge the referral fee", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerSellTokenPda } = await makeOffer(50);
+
+        // when
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(10e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+    });
+
+    test("Self referral or a referral without the referral vault should fail", async () => {
+        // given
+        const { user } = createUser();
+        const referrer = testHelper.createUserAccount();
+        const userReferrerAccount = await testHelper.registerReferrer(sellTokenMint, user);
+        const referrerAccount = await testHelper.registerReferrer(sellTokenMint, referrer);
+        const { offerPda } = await makeOffer(50);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, referrerAccount: userReferrerAccount, referralVault }))
+            .rejects.toThrow(RegExp(".*SelfReferral.*"));
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, referrerAccount }))
+            .rejects.toThrow(RegExp(".*MissingReferralVault.*"));
+    });
+
+    test("Referrer account for another sell token should fail", async () => {
+        // given
+        const { user } = createUser();
+        const referrer = testHelper.createUserAccount();
+        const referrerAccount = await testHelper.registerReferrer(buyToken1Mint, referrer);
+        const { offerPda } = await makeOffer(50);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user, referrerAccount, referralVault }))
+            .rejects.toThrow(RegExp(".*InvalidReferrerAccount.*"));
+    });
+
+    test("Registering a referrer without the admin role should fail", async () => {
+        // given
+        const { user } = createUser();
+
+        // when / then
+        await expect(testHelper.registerReferrer(sellTokenMint, user, user))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+
+    test("Referral fee above the maximum should fail", async () => {
+        // when / then
+        await expect(makeOffer(1_001))
+            .rejects.toThrow(RegExp(".*InvalidReferralFeeBps.*"));
+    });
+});
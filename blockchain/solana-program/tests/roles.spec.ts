@@ -0,0 +1,149 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("roles", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+    let bossSellTokenAccount: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        bossSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0), true);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda, bossBuyTokenAccount } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, bossBuyTokenAccount };
+    }
+
+    test("Offer manager should close an offer and refund the boss", async () => {
+        // given
+        const offerManager = testHelper.createUserAccount();
+        const { offerPda, bossBuyTokenAccount } = await makeOffer();
+
+        // when
+        await testHelper.grantRole({ offerManager: {} }, offerManager.publicKey);
+        await testHelper.closeOfferOne(offerPda, offerManager);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(bossBuyTokenAccount, BigInt(100e9));
+        await testHelper.expectTokenAccountAmountToBe(bossSellTokenAccount, BigInt(0));
+        await testHelper.revokeRole({ offerManager: {} }, offerManager.publicKey);
+    });
+
+    test("Close offer by a key without the offer manager role should fail", async () => {
+        // given
+        const offerManager = testHelper.createUserAccount();
+        const pauser = testHelper.createUserAccount();
+        const { offerPda } = await makeOffer();
+        await testHelper.grantRole({ offerManager: {} }, offerManager.publicKey);
+        await testHelper.grantRole({ pauser: {} }, pauser.publicKey);
+        await testHelper.revokeRole({ offerManager: {} }, offerManager.publicKey);
+
+        // when / then
+        await expect(testHelper.closeOfferOne(offerPda, offerManager))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+        await expect(testHelper.closeOfferOne(offerPda, pauser))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+        await testHelper.revokeRole({ pauser: {} }, pauser.publicKey);
+    });
+
+    test("Admin should grant and revoke roles", async () => {
+        // given
+        const admin = testHelper.createUserAccount();
+        const treasurer = testHelper.createUserAccount();
+
+        // when
+        await testHelper.grantRole({ admin: {} }, admin.publicKey);
+        await testHelper.grantRole({ treasurer: {} }, treasurer.publicKey, admin);
+
+        // then
+        let state = await testHelper.program.account.state.fetch(testHelper.statePda);
+        expect(state.roles).toEqual([
+            { role: { admin: {} }, holder: admin.publicKey },
+            { role: { treasurer: {} }, holder: treasurer.publicKey },
+        ]);
+
+        await testHelper.revokeRole({ treasurer: {} }, treasurer.publicKey, admin);
+        await testHelper.revokeRole({ admin: {} }, admin.publicKey);
+        state = await testHelper.program.account.state.fetch(testHelper.statePda);
+        expect(state.roles).toEqual([]);
+    });
+
+    test("Grant role by a key without the admin role should fail", async () => {
+        // given
+        const offerManager = testHelper.createUserAccount();
+        const holder = testHelper.createUserAccount();
+        await testHelper.grantRole({ offerManager: {} }, offerManager.publicKey);
+
+        // when / then
+        await expect(testHelper.grantRole({ offerManager: {} }, holder.publicKey, offerManager))
+            .rejects.toThrow(RegExp(".*MissingRole.*"));
+        await testHelper.revokeRole({ offerManager: {} }, offerManager.publicKey);
+    });
+
+    test("Invalid role grants and revocations should fail", async () => {
+        // given
+        const pauser = testHelper.createUserAccount();
+        await testHelper.grantRole({ pauser: {} }, pauser.publicKey);
+
+        // when / then
+        await expect(testHelper.grantRole({ pauser: {} }, pauser.publicKey))
+            .rejects.toThrow(RegExp(".*RoleAlreadyGranted.*"));
+        await expect(testHelper.grantRole({ pauser: {} }, boss))
+            .rejects.toThrow(RegExp(".*InvalidHolder.*"));
+        await expect(testHelper.grantRole({ pauser: {} }, PublicKey.default))
+            .rejects.toThrow(RegExp(".*InvalidHolder.*"));
+        await expect(testHelper.revokeRole({ compliance: {} }, pauser.publicKey))
+            .rejects.toThrow(RegExp(".*RoleNotGranted.*"));
+        await testHelper.revokeRole({ pauser: {} }, pauser.publicKey);
+    });
+});
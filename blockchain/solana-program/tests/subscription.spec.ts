@@ -0,0 +1,148 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("subscription", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer(subscription: boolean) {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+        if (subscription) {
+            await testHelper.setOfferSubscription(offerPda, true);
+        }
+
+        return { offerPda };
+    }
+
+    function createUser() {
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userSellTokenAccount, userBuyTokenAccount };
+    }
+
+    test("Subscribing to a regular offer or taking a subscription offer should fail", async () => {
+        // given
+        const { user } = createUser();
+        const { offerPda: regularOfferPda } = await makeOffer(false);
+        const { offerPda: subscriptionOfferPda } = await makeOffer(true);
+
+        // when / then
+        await expect(testHelper.subscribe(regularOfferPda, 10e9, user))
+            .rejects.toThrow(RegExp(".*NotSubscriptionOffer.*"));
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda: subscriptionOfferPda, user }))
+            .rejects.toThrow(RegExp(".*SubscriptionOffer.*"));
+    });
+
+    test("Purchase limits and subscriptions should not be combined", async () => {
+        // given
+        const { offerPda: limitedOfferPda } = await makeOffer(false);
+        await testHelper.setOfferLimits(limitedOfferPda, { maxPerUser: 10e9 });
+        const { offerPda: subscriptionOfferPda } = await makeOffer(true);
+
+        // when / then
+        await expect(testHelper.setOfferSubscription(limitedOfferPda, true))
+            .rejects.toThrow(RegExp(".*LimitedOffer.*"));
+        await expect(testHelper.setOfferLimits(subscriptionOfferPda, { raiseCap: 50e9 }))
+            .rejects.toThrow(RegExp(".*SubscriptionOffer.*"));
+    });
+
+    test("Oversubscribed offer should fill subscriptions pro-rata and refund the rest", async () => {
+        // given
+        const { offerPda } = await makeOffer(true);
+        const first = createUser();
+        const second = createUser();
+        await testHelper.subscribe(offerPda, 60e9, first.user);
+        await testHelper.subscribe(offerPda, 40e9, first.user);
+        await testHelper.subscribe(offerPda, 50e9, second.user);
+        await expect(testHelper.claimSubscription(offerPda, first.user.publicKey))
+            .rejects.toThrow(RegExp(".*OfferNotEnded.*"));
+        await testHelper.advanceClockBy(3600);
+        await expect(testHelper.closeOfferOne(offerPda))
+            .rejects.toThrow(RegExp(".*OpenSubscriptions.*"));
+
+        // when
+        await testHelper.claimSubscription(offerPda, first.user.publicKey);
+        await testHelper.claimSubscription(offerPda, second.user.publicKey, second.user);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(66_666_666_666));
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(33_333_333_334));
+        await testHelper.expectTokenAccountAmountToBe(second.userBuyTokenAccount, BigInt(33_333_333_333));
+        await testHelper.expectTokenAccountAmountToBe(second.userSellTokenAccount, BigInt(66_666_666_667));
+        expect(await testHelper.context.banksClient.getAccount(testHelper.getSubscriptionPda(offerPda, first.user.publicKey))).toBeNull();
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.openSubscriptions.toNumber()).toEqual(0);
+        expect(offer.totalRaised.toNumber()).toEqual(99_999_999_999);
+        await testHelper.closeOfferOne(offerPda);
+    });
+
+    test("Undersubscribed offer should fill subscriptions in full", async () => {
+        // given
+        const { offerPda } = await makeOffer(true);
+        const first = createUser();
+        await testHelper.subscribe(offerPda, 40e9, first.user);
+        await testHelper.advanceClockBy(3600);
+
+        // when
+        await testHelper.claimSubscription(offerPda, first.user.publicKey);
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(first.userBuyTokenAccount, BigInt(40e9));
+        await testHelper.expectTokenAccountAmountToBe(first.userSellTokenAccount, BigInt(60e9));
+        await expect(testHelper.subscribe(offerPda, 10e9, first.user))
+            .rejects.toThrow(RegExp(".*InvalidCurrentTime.*"));
+    });
+});
@@ -49,7 +49,7 @@ describe("take offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -62,7 +62,7 @@ describe("take offer", () => {
         await testHelper.makeOfferOne({
             offerId, 
             buyTokenTotalAmount: 100e9, 
-            sellTokenStartAmount: 100e9, 
+            sellTokenStartAmount: 150e9,
             sellTokenEndAmount: 200e9, 
             offerStartTime, 
             offerEndTime, 
@@ -93,7 +93,7 @@ describe("take offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
         
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -143,7 +143,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(200e9)
@@ -194,7 +194,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(200e9)
@@ -210,7 +210,7 @@ describe("take offer", () => {
         await testHelper.makeOfferOne({
             offerId,
             buyTokenTotalAmount: 100e9,
-            sellTokenStartAmount: 50e9,
+            sellTokenStartAmount: 100e9,
             sellTokenEndAmount: 200e9,
             offerStartTime,
             offerEndTime,
@@ -244,7 +244,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(200e9)
@@ -297,7 +297,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(1000e9)
@@ -313,7 +313,7 @@ describe("take offer", () => {
         await testHelper.makeOfferOne({
             offerId,
             buyTokenTotalAmount: 1000e9,
-            sellTokenStartAmount: 50e9,
+            sellTokenStartAmount: 100e9,
             sellTokenEndAmount: 200e9,
             offerStartTime,
             offerEndTime,
@@ -347,7 +347,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda,offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(1000e9)
@@ -401,7 +401,7 @@ describe("take offer", () => {
         testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda } = testHelper.createOneTokenOfferAccounts( 
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             boss, BigInt(600e9)
@@ -442,7 +442,7 @@ describe("take offer", () => {
         const userBuyToken2Account = testHelper.createTokenAccount(buyToken2Mint, user.publicKey, BigInt(0), true);
         
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyToken1Pda, offerBuyToken2Pda } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyToken1Pda, offerBuyToken2Pda } = await testHelper.createTwoTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             buyToken2Mint, BigInt(0), 
@@ -457,7 +457,7 @@ describe("take offer", () => {
             offerId, 
             buyToken1TotalAmount: 100e9, 
             buyToken2TotalAmount: 1000e9, 
-            sellTokenStartAmount: 100e9, 
+            sellTokenStartAmount: 150e9,
             sellTokenEndAmount: 250e9, 
             offerStartTime, 
             offerEndTime, 
@@ -497,7 +497,7 @@ describe("take offer", () => {
         testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda } = testHelper.createTwoTokenOfferAccounts( 
+        const { offerId, offerPda } = await testHelper.createTwoTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             onreTokenMint, BigInt(0), 
@@ -554,7 +554,7 @@ describe("take offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -601,7 +601,7 @@ describe("take offer", () => {
         const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             sellTokenMint, BigInt(0), 
             buyToken1Mint, BigInt(0), 
             boss, BigInt(600e9)
@@ -648,7 +648,7 @@ describe("take offer", () => {
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
 
         // create offer accounts
-        const { offerId, offerPda, offerBuyToken1Pda, offerBuyToken2Pda } = testHelper.createTwoTokenOfferAccounts(
+        const { offerId, offerPda, offerBuyToken1Pda, offerBuyToken2Pda } = await testHelper.createTwoTokenOfferAccounts(
             onreTokenMint, BigInt(0), 
             usdcTokenMint, BigInt(0), 
             onreTokenMint, BigInt(0), 
@@ -666,7 +666,7 @@ describe("take offer", () => {
             offerId, 
             buyToken1TotalAmount: 100e9, 
             buyToken2TotalAmount: 100e9, 
-            sellTokenStartAmount: 150e9, 
+            sellTokenStartAmount: 200e9,
             sellTokenEndAmount: 500e9, 
             offerStartTime, 
             offerEndTime, 
@@ -725,7 +725,7 @@ describe("take offer", () => {
         const user2OnreTokenAccount = testHelper.createTokenAccount(sellTokenMint, user2.publicKey, BigInt(100e9), true);
         const user2UsdcTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user2.publicKey, BigInt(0), true);
 
-        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerSellTokenPda, offerBuyTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0),
             usdcTokenMint, BigInt(0),
             boss, BigInt(1000e9)
@@ -742,7 +742,7 @@ describe("take offer", () => {
         await testHelper.makeOfferOne({
             offerId,
             buyTokenTotalAmount: 120e9,
-            sellTokenStartAmount: 50e9,
+            sellTokenStartAmount: 60e9,
             sellTokenEndAmount: 180e9,
             offerStartTime,
             offerEndTime,
@@ -797,7 +797,7 @@ describe("take offer", () => {
         const userOnreTokenAccount = testHelper.createTokenAccount(onreTokenMint, user.publicKey, BigInt(300e9), true);
         const userUsdcTokenAccount = testHelper.createTokenAccount(usdcTokenMint, user.publicKey, BigInt(0), true);
         
-        const { offerId, offerPda, offerBuyTokenPda, offerSellTokenPda } = testHelper.createOneTokenOfferAccounts(
+        const { offerId, offerPda, offerBuyTokenPda, offerSellTokenPda } = await testHelper.createOneTokenOfferAccounts(
             onreTokenMint, BigInt(0),
             usdcTokenMint, BigInt(0),
             boss, BigInt(1000e9)
@@ -0,0 +1,115 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("take offer two permissionless", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+    let buyToken2Mint: PublicKey;
+
+    let boss: PublicKey;
+    let intermediaryAccounts: PublicKey[];
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken2Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+
+        // Create the routing channel and its intermediary accounts
+        await testHelper.initializePermissionlessAccount(0, "Basket partner");
+        const permissionlessAccount = testHelper.getPermissionlessAccountPda(0);
+        intermediaryAccounts = [sellTokenMint, buyToken1Mint, buyToken2Mint]
+            .map(mint => testHelper.createTokenAccount(mint, permissionlessAccount, BigInt(0), true));
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda, offerSellTokenPda } = await testHelper.createTwoTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            buyToken2Mint, BigInt(0),
+            boss, BigInt(100e9), BigInt(50e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferTwo({
+            offerId,
+            buyToken1TotalAmount: 100e9,
+            buyToken2TotalAmount: 50e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyToken1Mint,
+            buyToken2Mint,
+        });
+
+        return { offerPda, offerSellTokenPda };
+    }
+
+    function createUser() {
+        const user = testHelper.createUserAccount();
+        const userSellTokenAccount = testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyToken1Account = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        const userBuyToken2Account = testHelper.createTokenAccount(buyToken2Mint, user.publicKey, BigInt(0), true);
+        return { user, userSellTokenAccount, userBuyToken1Account, userBuyToken2Account };
+    }
+
+    test("Taking a two token offer via the permissionless route should deliver both buy tokens", async () => {
+        // given
+        const { offerPda, offerSellTokenPda } = await makeOffer();
+        const { user, userSellTokenAccount, userBuyToken1Account, userBuyToken2Account } = createUser();
+
+        // when
+        await testHelper.takeOfferTwoPermissionless({ intermediaryIndex: 0, sellTokenAmount: 40e9, offerPda, user });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userSellTokenAccount, BigInt(60e9));
+        await testHelper.expectTokenAccountAmountToBe(offerSellTokenPda, BigInt(40e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyToken1Account, BigInt(40e9));
+        await testHelper.expectTokenAccountAmountToBe(userBuyToken2Account, BigInt(20e9));
+        for (const intermediaryAccount of intermediaryAccounts) {
+            await testHelper.expectTokenAccountAmountToBe(intermediaryAccount, BigInt(0));
+        }
+    });
+
+    test("Taking via a disabled permissionless account should fail", async () => {
+        // given
+        const { offerPda } = await makeOffer();
+        const { user } = createUser();
+        await testHelper.setPermissionlessAccountEnabled(0, false);
+
+        // when / then
+        await expect(testHelper.takeOfferTwoPermissionless({ intermediaryIndex: 0, sellTokenAmount: 10e9, offerPda, user }))
+            .rejects.toThrow(RegExp(".*PermissionlessAccountDisabled.*"));
+        await testHelper.setPermissionlessAccountEnabled(0, true);
+    });
+});
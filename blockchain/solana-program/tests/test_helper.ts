@@ -1,7 +1,7 @@
 import { Clock, ProgramTestContext } from "solana-bankrun"
 import { OnreApp } from "../target/types/onre_app"
-import { BN, Program } from "@coral-xyz/anchor"
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { BN, IdlTypes, Program } from "@coral-xyz/anchor"
+import { Keypair, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
 import { ACCOUNT_SIZE, AccountLayout, getAssociatedTokenAddressSync, MINT_SIZE, MintLayout, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 
 export const ONREAPP_PROGRAM_ID = new PublicKey("onreuGhHHgVzMWSkj2oQDLDtvvGvoepBPkqyaubFcwe");
@@ -13,11 +13,13 @@ export class TestHelper {
 
     // accounts
     statePda: PublicKey;
+    offerRegistryPda: PublicKey;
 
     constructor(context: ProgramTestContext, program: Program<OnreApp>) {
         this.context = context;
         this.program = program;
         [this.statePda] = PublicKey.findProgramAddressSync([Buffer.from('state')], ONREAPP_PROGRAM_ID);
+        [this.offerRegistryPda] = PublicKey.findProgramAddressSync([Buffer.from('offer_registry')], ONREAPP_PROGRAM_ID);
     }
     
     createUserAccount(): Keypair {
@@ -55,6 +57,34 @@ export class TestHelper {
         return mintAddress
     };
 
+    createMintWithAuthority(mintAuthority: PublicKey, supply: bigint = BigInt(100_000e9), decimals: number = 9): PublicKey {
+        const mintData = Buffer.alloc(MINT_SIZE);
+        MintLayout.encode({
+            mintAuthorityOption: 1,
+            mintAuthority: mintAuthority,
+            supply: supply,
+            decimals: decimals,
+            isInitialized: true,
+            freezeAuthorityOption: 0,
+            freezeAuthority: PublicKey.default,
+        }, mintData)
+
+        const mintAddress = PublicKey.unique();
+        this.context.setAccount(mintAddress, {
+            executable: false,
+            data: mintData,
+            lamports: INITIAL_LAMPORTS,
+            owner: TOKEN_PROGRAM_ID,
+        });
+
+        return mintAddress
+    };
+
+    async getMintSupply(mint: PublicKey): Promise<bigint> {
+        const account = await this.context.banksClient.getAccount(mint);
+        return MintLayout.decode(account!.data).supply;
+    }
+
     createTokenAccount(mint: PublicKey, owner: PublicKey, amount: bigint, allowOwnerOffCurve: boolean = false): PublicKey {
         const tokenAccountData = Buffer.alloc(ACCOUNT_SIZE);
         AccountLayout.encode({
@@ -83,17 +113,16 @@ export class TestHelper {
         return tokenAccountAddress;
     }
 
-    createOneTokenOfferAccounts( 
+    async createOneTokenOfferAccounts(
         sellTokenMint: PublicKey, 
         offerSellTokenAmount: bigint = BigInt(0),
         buyTokenMint: PublicKey,
         offerBuyTokenAmount: bigint = BigInt(0),
         boss: PublicKey,
         bossBuyTokenAmount: bigint = BigInt(0),
-    ): OfferOneTokenAccounts {
-        const offerId = new BN(PublicKey.unique().toBytes());
-        const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from('offer_authority'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
-        const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from('offer'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+    ): Promise<OfferOneTokenAccounts> {
+        const offerId = await this.getNextOfferId();
+        const { offerPda, offerAuthority } = this.getOfferPdas(offerId);
         const offerSellTokenPda = this.createTokenAccount(sellTokenMint, offerAuthority, offerSellTokenAmount, true);
         const offerBuyTokenPda = this.createTokenAccount(buyTokenMint, offerAuthority, offerBuyTokenAmount, true);
         const bossBuyTokenAccount = this.createTokenAccount(buyTokenMint, boss, bossBuyTokenAmount);
@@ -108,7 +137,7 @@ export class TestHelper {
         }
     }
 
-    createTwoTokenOfferAccounts( 
+    async createTwoTokenOfferAccounts(
         sellTokenMint: PublicKey, 
         offerSellTokenAmount: bigint = BigInt(0),
         buyToken1Mint: PublicKey,
@@ -118,10 +147,9 @@ export class TestHelper {
         boss: PublicKey,
         bossBuyTokenAmount1: bigint = BigInt(0),
         bossBuyTokenAmount2: bigint = BigInt(0),
-    ): OfferTwoTokenAccounts {
-        const offerId = new BN(PublicKey.unique().toBytes());
-        const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from('offer_authority'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
-        const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from('offer'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+    ): Promise<OfferTwoTokenAccounts> {
+        const offerId = await this.getNextOfferId();
+        const { offerPda, offerAuthority } = this.getOfferPdas(offerId);
         const offerSellTokenPda = this.createTokenAccount(sellTokenMint, offerAuthority, offerSellTokenAmount, true);
         const offerBuyToken1Pda = this.createTokenAccount(buyToken1Mint, offerAuthority, offerBuyToken1Amount, true);
         const offerBuyToken2Pda = this.createTokenAccount(buyToken2Mint, offerAuthority, offerBuyToken2Amount, true);
@@ -146,30 +174,49 @@ export class TestHelper {
         expect(tokenAccountData.amount).toBe(amount);
     }
 
+    async getNextOfferId(): Promise<BN> {
+        const state = await this.program.account.state.fetch(this.statePda);
+        return state.nextOfferId;
+    }
+
+    async getListedOfferIds(): Promise<number[]> {
+        const offerRegistry = await this.program.account.offerRegistry.fetch(this.offerRegistryPda);
+        return offerRegistry.offerIds.map(offerId => offerId.toNumber());
+    }
+
+    getOfferPdas(offerId: BN): { offerPda: PublicKey, offerAuthority: PublicKey } {
+        const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from('offer_authority'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from('offer'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        return { offerPda, offerAuthority };
+    }
+
     async makeOfferOne(
         params: MakeOfferOneParams
     ) {
+        const { offerPda, offerAuthority } = this.getOfferPdas(params.offerId);
         return await this.program.methods
             .makeOfferOne(
-                params.offerId, 
                 new BN(params.buyTokenTotalAmount), 
                 new BN(params.sellTokenStartAmount), 
                 new BN(params.sellTokenEndAmount), 
                 new BN(params.offerStartTime), 
                 new BN(params.offerEndTime), 
                 new BN(params.priceFixDuration))
-            .accounts({
+            .accountsPartial({
+                offer: offerPda,
+                offerTokenAuthority: offerAuthority,
                 sellTokenMint: params.sellTokenMint,
                 buyToken1Mint: params.buyTokenMint,
                 state: this.statePda,
+                boss: this.program.provider.publicKey,
             })
             .rpc();
     }
 
     async makeOfferTwo(params: MakeOfferTwoParams) {
+        const { offerPda, offerAuthority } = this.getOfferPdas(params.offerId);
         return await this.program.methods
             .makeOfferTwo(
-                params.offerId, 
                 new BN(params.buyToken1TotalAmount), 
                 new BN(params.buyToken2TotalAmount), 
                 new BN(params.sellTokenStartAmount), 
@@ -177,11 +224,14 @@ export class TestHelper {
                 new BN(params.offerStartTime), 
                 new BN(params.offerEndTime), 
                 new BN(params.priceFixDuration))
-            .accounts({
+            .accountsPartial({
+                offer: offerPda,
+                offerTokenAuthority: offerAuthority,
                 sellTokenMint: params.sellTokenMint,
                 buyToken1Mint: params.buyToken1Mint,
                 buyToken2Mint: params.buyToken2Mint,
                 state: this.statePda,
+                boss: this.program.provider.publicKey,
             })
             .rpc();
     }
@@ -190,7 +240,7 @@ export class TestHelper {
         return await this.program.methods
             .takeOfferOne(
                 new BN(params.sellTokenAmount))
-            .accounts({ offer: params.offerPda, user: params.user.publicKey })
+            .accounts({ offer: params.offerPda, user: params.user.publicKey, navOracle: params.navOracle ?? null, kycRecord: params.kycRecord ?? null, feeVaultTokenAccount: params.feeVault ?? null, referrerAccount: params.referrerAccount ?? null, referralVaultTokenAccount: params.referralVault ?? null, vestingEscrow: params.vestingEscrow ?? null, vestingBuyToken1Account: params.vestingBuyToken1Account ?? null })
             .signers([params.user])
             .rpc();
     }
@@ -199,22 +249,716 @@ export class TestHelper {
         return await this.program.methods
             .takeOfferTwo(
                 new BN(params.sellTokenAmount))
-            .accounts({ offer: params.offerPda, user: params.user.publicKey })
+            .accounts({ offer: params.offerPda, user: params.user.publicKey, navOracle: params.navOracle ?? null, kycRecord: params.kycRecord ?? null, feeVaultTokenAccount: params.feeVault ?? null, referrerAccount: params.referrerAccount ?? null, referralVaultTokenAccount: params.referralVault ?? null, vestingEscrow: params.vestingEscrow ?? null, vestingBuyToken1Account: params.vestingBuyToken1Account ?? null, vestingBuyToken2Account: params.vestingBuyToken2Account ?? null })
+            .signers([params.user])
+            .rpc();
+    }
+
+    async takeOfferOnePermissionless(params: TakeOfferPermissionlessParams) {
+        const offer = await this.program.account.offer.fetch(params.offerPda);
+        return await this.program.methods
+            .takeOfferOnePermissionless(
+                new BN(params.intermediaryIndex),
+                new BN(params.sellTokenAmount))
+            .accounts({ offer: params.offerPda, buyToken1Mint: offer.buyToken1.mint, sellTokenMint: offer.sellTokenMint, user: params.user.publicKey, navOracle: params.navOracle ?? null, kycRecord: params.kycRecord ?? null, feeVaultTokenAccount: params.feeVault ?? null, referrerAccount: params.referrerAccount ?? null, referralVaultTokenAccount: params.referralVault ?? null, vestingEscrow: params.vestingEscrow ?? null, vestingBuyToken1Account: params.vestingBuyToken1Account ?? null })
+            .signers([params.user])
+            .rpc();
+    }
+
+    async takeOfferTwoPermissionless(params: TakeOfferPermissionlessParams) {
+        const offer = await this.program.account.offer.fetch(params.offerPda);
+        return await this.program.methods
+            .takeOfferTwoPermissionless(
+                new BN(params.intermediaryIndex),
+                new BN(params.sellTokenAmount))
+            .accounts({ offer: params.offerPda, buyToken1Mint: offer.buyToken1.mint, buyToken2Mint: offer.buyToken2.mint, sellTokenMint: offer.sellTokenMint, user: params.user.publicKey, navOracle: params.navOracle ?? null, kycRecord: params.kycRecord ?? null, feeVaultTokenAccount: params.feeVault ?? null, referrerAccount: params.referrerAccount ?? null, referralVaultTokenAccount: params.referralVault ?? null, vestingEscrow: params.vestingEscrow ?? null, vestingBuyToken1Account: params.vestingBuyToken1Account ?? null, vestingBuyToken2Account: params.vestingBuyToken2Account ?? null })
             .signers([params.user])
             .rpc();
     }
 
-    async closeOfferOne(offerPda: PublicKey) {
+    async quoteOffer(offerPda: PublicKey, amount: QuoteAmountParams, navOracle?: PublicKey) {
+        const quoteAmount = "sellTokenAmount" in amount
+            ? { exactSell: { sellTokenAmount: new BN(amount.sellTokenAmount) } }
+            : { exactBuy: { buyToken1Amount: new BN(amount.buyToken1Amount) } };
+        return await this.program.methods
+            .quoteOffer(quoteAmount)
+            .accounts({ offer: offerPda, navOracle: navOracle ?? null })
+            .view();
+    }
+
+    getPermissionlessAccountPda(index: number): PublicKey {
+        const [permissionlessAccountPda] = PublicKey.findProgramAddressSync([Buffer.from('permissionless'), new BN(index).toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        return permissionlessAccountPda;
+    }
+
+    async initializePermissionlessAccount(index: number, name: string) {
+        return await this.program.methods
+            .initializePermissionlessAccount(new BN(index), name)
+            .accountsPartial({ state: this.statePda, boss: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async setPermissionlessAccountEnabled(index: number, enabled: boolean) {
+        return await this.program.methods
+            .setPermissionlessAccountEnabled(enabled)
+            .accountsPartial({ permissionlessAccount: this.getPermissionlessAccountPda(index), state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async sweepIntermediary(index: number, mint: PublicKey) {
+        return await this.program.methods
+            .sweepIntermediary()
+            .accountsPartial({ permissionlessAccount: this.getPermissionlessAccountPda(index), mint, state: this.statePda, boss: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async closeIntermediary(index: number, tokenAccounts: PublicKey[]) {
+        return await this.program.methods
+            .closeIntermediary()
+            .accountsPartial({ permissionlessAccount: this.getPermissionlessAccountPda(index), state: this.statePda, boss: this.program.provider.publicKey })
+            .remainingAccounts(tokenAccounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: true })))
+            .rpc();
+    }
+
+    async closeOfferOne(offerPda: PublicKey, authority?: Keypair) {
         return await this.program.methods
             .closeOfferOne()
-            .accounts({ offer: offerPda, state: this.statePda })
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
             .rpc();
     }
 
     async closeOfferTwo(offerPda: PublicKey) {
         return await this.program.methods
             .closeOfferTwo()
-            .accounts({ offer: offerPda, state: this.statePda })
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async updateOffer(offerPda: PublicKey, params: UpdateOfferParams, authority?: Keypair) {
+        return await this.program.methods
+            .updateOffer(
+                new BN(params.sellTokenStartAmount),
+                new BN(params.sellTokenEndAmount),
+                new BN(params.offerEndTime),
+                new BN(params.priceFixDuration))
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async topUpOffer(offerPda: PublicKey, buyToken1Amount: number, buyToken2Accounts?: BuyToken2Accounts) {
+        return await this.program.methods
+            .topUpOffer(new BN(buyToken1Amount))
+            .accountsPartial({
+                offer: offerPda,
+                offerBuyToken2Account: buyToken2Accounts?.offerBuyToken2Account ?? null,
+                bossBuyToken2Account: buyToken2Accounts?.bossBuyToken2Account ?? null,
+                state: this.statePda,
+                boss: this.program.provider.publicKey,
+            })
+            .rpc();
+    }
+
+    async withdrawUnsold(offerPda: PublicKey, buyToken1Amount: number, buyToken2Accounts?: BuyToken2Accounts) {
+        return await this.program.methods
+            .withdrawUnsold(new BN(buyToken1Amount))
+            .accountsPartial({
+                offer: offerPda,
+                offerBuyToken2Account: buyToken2Accounts?.offerBuyToken2Account ?? null,
+                bossBuyToken2Account: buyToken2Accounts?.bossBuyToken2Account ?? null,
+                state: this.statePda,
+                authority: this.program.provider.publicKey,
+            })
+            .rpc();
+    }
+
+    async setTreasury(treasury: PublicKey) {
+        return await this.program.methods
+            .setTreasury(treasury)
+            .accounts({ authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async withdrawProceeds(offerPda: PublicKey, destination: PublicKey, amount: number, treasurer?: Keypair) {
+        return await this.program.methods
+            .withdrawProceeds(new BN(amount))
+            .accountsPartial({
+                offer: offerPda,
+                treasurySellTokenAccount: destination,
+                state: this.statePda,
+                treasurer: treasurer?.publicKey ?? this.program.provider.publicKey,
+            })
+            .signers(treasurer ? [treasurer] : [])
+            .rpc();
+    }
+
+    async closeExpiredOffer(offerPda: PublicKey, cranker: Keypair, buyToken2Accounts?: BuyToken2Accounts) {
+        const offer = await this.program.account.offer.fetch(offerPda);
+        return await this.program.methods
+            .closeExpiredOffer()
+            .accountsPartial({
+                offer: offerPda,
+                boss: offer.boss,
+                offerBuyToken2Account: buyToken2Accounts?.offerBuyToken2Account ?? null,
+                bossBuyToken2Account: buyToken2Accounts?.bossBuyToken2Account ?? null,
+                cranker: cranker.publicKey,
+            })
+            .signers([cranker])
+            .rpc();
+    }
+
+    async setCrankBounty(crankBountyLamports: number) {
+        return await this.program.methods
+            .setCrankBounty(new BN(crankBountyLamports))
+            .accounts({ authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async migrateOffer(offerPda: PublicKey) {
+        return await this.program.methods
+            .migrateOffer()
+            .accountsPartial({ offer: offerPda, state: this.statePda, boss: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async setNextOfferId(nextOfferId: BN, authority?: Keypair) {
+        return await this.program.methods
+            .setNextOfferId(nextOfferId)
+            .accounts({ authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async setOfferUniformPrice(offerPda: PublicKey, uniformPrice: boolean) {
+        return await this.program.methods
+            .setOfferUniformPrice(uniformPrice)
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    getAuctionSettlementPda(offerPda: PublicKey): PublicKey {
+        const [settlementPda] = PublicKey.findProgramAddressSync([Buffer.from('auction_settlement'), offerPda.toBuffer()], ONREAPP_PROGRAM_ID);
+        return settlementPda;
+    }
+
+    async settleAuction(offerPda: PublicKey, payer?: Keypair) {
+        const offer = await this.program.account.offer.fetch(offerPda);
+        return await this.program.methods
+            .settleAuction()
+            .accountsPartial({
+                offer: offerPda,
+                sellTokenMint: offer.sellTokenMint,
+                payer: payer?.publicKey ?? this.program.provider.publicKey,
+            })
+            .signers(payer ? [payer] : [])
+            .rpc();
+    }
+
+    async claimRebate(offerPda: PublicKey, user: Keypair) {
+        const settlementPda = this.getAuctionSettlementPda(offerPda);
+        const settlement = await this.program.account.auctionSettlement.fetch(settlementPda);
+        return await this.program.methods
+            .claimRebate()
+            .accountsPartial({
+                settlement: settlementPda,
+                position: this.getPositionPda(offerPda, user.publicKey),
+                rebateVaultTokenAccount: getAssociatedTokenAddressSync(settlement.sellTokenMint, settlementPda, true),
+                userSellTokenAccount: getAssociatedTokenAddressSync(settlement.sellTokenMint, user.publicKey),
+                user: user.publicKey,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    async setOfferSubscription(offerPda: PublicKey, subscription: boolean) {
+        return await this.program.methods
+            .setOfferSubscription(subscription)
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    getSubscriptionPda(offerPda: PublicKey, user: PublicKey): PublicKey {
+        const [subscriptionPda] = PublicKey.findProgramAddressSync([Buffer.from('subscription'), offerPda.toBuffer(), user.toBuffer()], ONREAPP_PROGRAM_ID);
+        return subscriptionPda;
+    }
+
+    async subscribe(offerPda: PublicKey, sellTokenAmount: number, user: Keypair, kycRecord?: PublicKey) {
+        return await this.program.methods
+            .subscribe(new BN(sellTokenAmount))
+            .accounts({ offer: offerPda, user: user.publicKey, kycRecord: kycRecord ?? null })
+            .signers([user])
+            .rpc();
+    }
+
+    async claimSubscription(offerPda: PublicKey, user: PublicKey, payer?: Keypair, buyToken2Accounts?: UserBuyToken2Accounts) {
+        return await this.program.methods
+            .claimSubscription()
+            .accountsPartial({
+                offer: offerPda,
+                user,
+                offerBuyToken2Account: buyToken2Accounts?.offerBuyToken2Account ?? null,
+                userBuyToken2Account: buyToken2Accounts?.userBuyToken2Account ?? null,
+                payer: payer?.publicKey ?? this.program.provider.publicKey,
+            })
+            .signers(payer ? [payer] : [])
+            .rpc();
+    }
+
+    async setOfferMinRaise(offerPda: PublicKey, minRaise: number) {
+        return await this.program.methods
+            .setOfferMinRaise(new BN(minRaise))
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async claimReceipt(offerPda: PublicKey, user: PublicKey, payer?: Keypair, buyToken2Accounts?: UserBuyToken2Accounts) {
+        return await this.program.methods
+            .claimReceipt()
+            .accountsPartial({
+                offer: offerPda,
+                user,
+                offerBuyToken2Account: buyToken2Accounts?.offerBuyToken2Account ?? null,
+                userBuyToken2Account: buyToken2Accounts?.userBuyToken2Account ?? null,
+                payer: payer?.publicKey ?? this.program.provider.publicKey,
+            })
+            .signers(payer ? [payer] : [])
+            .rpc();
+    }
+
+    async setOfferVesting(offerPda: PublicKey, startTime: number, cliffDuration: number, duration: number) {
+        return await this.program.methods
+            .setOfferVesting(new BN(startTime), new BN(cliffDuration), new BN(duration))
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    getVestingEscrowPda(offerPda: PublicKey, user: PublicKey): PublicKey {
+        const [vestingEscrowPda] = PublicKey.findProgramAddressSync([Buffer.from('vesting_escrow'), offerPda.toBuffer(), user.toBuffer()], ONREAPP_PROGRAM_ID);
+        return vestingEscrowPda;
+    }
+
+    async openVestingEscrow(offerPda: PublicKey, user: Keypair) {
+        return await this.program.methods
+            .openVestingEscrow()
+            .accounts({ offer: offerPda, user: user.publicKey })
+            .signers([user])
+            .rpc();
+    }
+
+    async claimVested(vestingEscrow: PublicKey, user: Keypair, buyToken2Accounts?: VestingBuyToken2Accounts) {
+        return await this.program.methods
+            .claimVested()
+            .accountsPartial({
+                vestingEscrow,
+                user: user.publicKey,
+                vestingBuyToken2Account: buyToken2Accounts?.vestingBuyToken2Account ?? null,
+                userBuyToken2Account: buyToken2Accounts?.userBuyToken2Account ?? null,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    getNavOraclePda(poolId: BN): PublicKey {
+        const [navOraclePda] = PublicKey.findProgramAddressSync([Buffer.from('nav_oracle'), poolId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        return navOraclePda;
+    }
+
+    async initializeNavOracle(poolId: BN, oracleAuthority: PublicKey, maxStaleness: number) {
+        return await this.program.methods
+            .initializeNavOracle(poolId, oracleAuthority, new BN(maxStaleness))
+            .accountsPartial({ state: this.statePda, boss: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async updateNav(navOracle: PublicKey, nav: number, oracleAuthority: Keypair) {
+        return await this.program.methods
+            .updateNav(new BN(nav))
+            .accounts({ navOracle, oracleAuthority: oracleAuthority.publicKey })
+            .signers([oracleAuthority])
+            .rpc();
+    }
+
+    async configureNavOracle(navOracle: PublicKey, oracleAuthority: PublicKey, maxStaleness: number, isActive: boolean) {
+        return await this.program.methods
+            .configureNavOracle(oracleAuthority, new BN(maxStaleness), isActive)
+            .accountsPartial({ navOracle, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async setOfferNavPricing(offerPda: PublicKey, navOracle: PublicKey | null, navSpreadBps: number) {
+        return await this.program.methods
+            .setOfferNavPricing(navSpreadBps)
+            .accountsPartial({ offer: offerPda, navOracle, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async getNavOracleAccount(account: PublicKey) {
+        return await this.program.account.navOracle.fetch(account);
+    }
+
+    createRedemptionOfferAccounts(
+        redeemTokenMint: PublicKey,
+        payoutTokenMint: PublicKey,
+        boss: PublicKey,
+        bossPayoutTokenAmount: bigint = BigInt(0),
+    ): RedemptionOfferAccounts {
+        const offerId = new BN(PublicKey.unique().toBytes());
+        const [offerAuthority] = PublicKey.findProgramAddressSync([Buffer.from('redemption_offer_authority'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        const [offerPda] = PublicKey.findProgramAddressSync([Buffer.from('redemption_offer'), offerId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        const offerPayoutTokenPda = this.createTokenAccount(payoutTokenMint, offerAuthority, BigInt(0), true);
+        const offerRedeemTokenPda = this.createTokenAccount(redeemTokenMint, offerAuthority, BigInt(0), true);
+        const bossPayoutTokenAccount = this.createTokenAccount(payoutTokenMint, boss, bossPayoutTokenAmount);
+
+        return {
+            offerId,
+            offerAuthority,
+            offerPda,
+            offerPayoutTokenPda,
+            offerRedeemTokenPda,
+            bossPayoutTokenAccount,
+        }
+    }
+
+    async makeRedemptionOffer(params: MakeRedemptionOfferParams) {
+        return await this.program.methods
+            .makeRedemptionOffer(params.offerId, {
+                payoutTokenTotalAmount: new BN(params.payoutTokenTotalAmount),
+                priceStart: new BN(params.priceStart),
+                priceEnd: new BN(params.priceEnd),
+                offerStartTime: new BN(params.offerStartTime),
+                offerEndTime: new BN(params.offerEndTime),
+                priceFixDuration: new BN(params.priceFixDuration),
+                navSpreadBps: params.navSpreadBps ?? 0,
+                epochDuration: new BN(params.epochDuration),
+                epochCap: new BN(params.epochCap),
+            })
+            .accountsPartial({
+                redeemTokenMint: params.redeemTokenMint,
+                payoutTokenMint: params.payoutTokenMint,
+                navOracle: params.navOracle ?? null,
+                state: this.statePda,
+                boss: this.program.provider.publicKey,
+            })
+            .rpc();
+    }
+
+    async takeRedemptionOffer(params: TakeRedemptionOfferParams) {
+        return await this.program.methods
+            .takeRedemptionOffer(new BN(params.redeemTokenAmount))
+            .accounts({
+                redemptionOffer: params.offerPda,
+                redeemTokenMint: params.redeemTokenMint,
+                navOracle: params.navOracle ?? null,
+                user: params.user.publicKey,
+            })
+            .signers([params.user])
+            .rpc();
+    }
+
+    async closeRedemptionOffer(offerPda: PublicKey) {
+        return await this.program.methods
+            .closeRedemptionOffer()
+            .accountsPartial({ redemptionOffer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    createRedemptionQueueAccounts(redeemTokenMint: PublicKey, payoutTokenMint: PublicKey): RedemptionQueueAccounts {
+        const queueId = new BN(PublicKey.unique().toBytes());
+        const [queueAuthority] = PublicKey.findProgramAddressSync([Buffer.from('redemption_queue_authority'), queueId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        const [queuePda] = PublicKey.findProgramAddressSync([Buffer.from('redemption_queue'), queueId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        const queueRedeemTokenPda = this.createTokenAccount(redeemTokenMint, queueAuthority, BigInt(0), true);
+        const queuePayoutTokenPda = this.createTokenAccount(payoutTokenMint, queueAuthority, BigInt(0), true);
+
+        return {
+            queueId,
+            queueAuthority,
+            queuePda,
+            queueRedeemTokenPda,
+            queuePayoutTokenPda,
+        }
+    }
+
+    getRedemptionEpochPda(queuePda: PublicKey, epoch: number): PublicKey {
+        const [redemptionEpochPda] = PublicKey.findProgramAddressSync([Buffer.from('redemption_epoch'), queuePda.toBuffer(), new BN(epoch).toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        return redemptionEpochPda;
+    }
+
+    getRedemptionRequestPda(queuePda: PublicKey, user: PublicKey): PublicKey {
+        const [redemptionRequestPda] = PublicKey.findProgramAddressSync([Buffer.from('redemption_request'), queuePda.toBuffer(), user.toBuffer()], ONREAPP_PROGRAM_ID);
+        return redemptionRequestPda;
+    }
+
+    async initializeRedemptionQueue(queueId: BN, redeemTokenMint: PublicKey, payoutTokenMint: PublicKey) {
+        return await this.program.methods
+            .initializeRedemptionQueue(queueId)
+            .accountsPartial({ redeemTokenMint, payoutTokenMint, state: this.statePda, boss: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async requestRedemption(queuePda: PublicKey, amount: number, user: Keypair) {
+        const queue = await this.program.account.redemptionQueue.fetch(queuePda);
+        return await this.program.methods
+            .requestRedemption(new BN(amount))
+            .accountsPartial({
+                redemptionQueue: queuePda,
+                redemptionEpoch: this.getRedemptionEpochPda(queuePda, queue.currentEpoch.toNumber()),
+                user: user.publicKey,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    async cancelRedemption(queuePda: PublicKey, user: Keypair) {
+        const request = await this.program.account.redemptionRequest.fetch(this.getRedemptionRequestPda(queuePda, user.publicKey));
+        return await this.program.methods
+            .cancelRedemption()
+            .accountsPartial({
+                redemptionQueue: queuePda,
+                redemptionEpoch: this.getRedemptionEpochPda(queuePda, request.epoch.toNumber()),
+                user: user.publicKey,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    async settleRedemptionEpoch(queuePda: PublicKey, payoutTokenAmount: number, price: number) {
+        const queue = await this.program.account.redemptionQueue.fetch(queuePda);
+        const epoch = queue.currentEpoch.toNumber();
+        return await this.program.methods
+            .settleRedemptionEpoch(new BN(payoutTokenAmount), new BN(price))
+            .accountsPartial({
+                redemptionQueue: queuePda,
+                redemptionEpoch: this.getRedemptionEpochPda(queuePda, epoch),
+                nextRedemptionEpoch: this.getRedemptionEpochPda(queuePda, epoch + 1),
+                state: this.statePda,
+                boss: this.program.provider.publicKey,
+            })
+            .rpc();
+    }
+
+    async claimRedemption(queuePda: PublicKey, redeemTokenMint: PublicKey, user: Keypair) {
+        const request = await this.program.account.redemptionRequest.fetch(this.getRedemptionRequestPda(queuePda, user.publicKey));
+        return await this.program.methods
+            .claimRedemption()
+            .accountsPartial({
+                redemptionQueue: queuePda,
+                redemptionEpoch: this.getRedemptionEpochPda(queuePda, request.epoch.toNumber()),
+                redeemTokenMint,
+                state: this.statePda,
+                user: user.publicKey,
+            })
+            .signers([user])
+            .rpc();
+    }
+
+    getKycRecordPda(wallet: PublicKey): PublicKey {
+        const [kycRecordPda] = PublicKey.findProgramAddressSync([Buffer.from('kyc'), wallet.toBuffer()], ONREAPP_PROGRAM_ID);
+        return kycRecordPda;
+    }
+
+    async approveKyc(wallets: PublicKey[], level: number, expiry: number, jurisdiction: number, compliance?: Keypair) {
+        return await this.program.methods
+            .approveKyc(wallets, level, new BN(expiry), jurisdiction)
+            .accountsPartial({ state: this.statePda, compliance: compliance?.publicKey ?? this.program.provider.publicKey })
+            .remainingAccounts(wallets.map(wallet => ({ pubkey: this.getKycRecordPda(wallet), isSigner: false, isWritable: true })))
+            .signers(compliance ? [compliance] : [])
+            .rpc();
+    }
+
+    async revokeKyc(wallets: PublicKey[]) {
+        return await this.program.methods
+            .revokeKyc(wallets)
+            .accountsPartial({ state: this.statePda, compliance: this.program.provider.publicKey })
+            .remainingAccounts(wallets.map(wallet => ({ pubkey: this.getKycRecordPda(wallet), isSigner: false, isWritable: true })))
+            .rpc();
+    }
+
+    async setOfferMinKycLevel(offerPda: PublicKey, minKycLevel: number) {
+        return await this.program.methods
+            .setOfferMinKycLevel(minKycLevel)
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    getPositionPda(offerPda: PublicKey, user: PublicKey): PublicKey {
+        const [positionPda] = PublicKey.findProgramAddressSync([Buffer.from('position'), offerPda.toBuffer(), user.toBuffer()], ONREAPP_PROGRAM_ID);
+        return positionPda;
+    }
+
+    async setOfferLimits(offerPda: PublicKey, limits: OfferLimits) {
+        return await this.program.methods
+            .setOfferLimits(
+                new BN(limits.maxPerUser ?? 0),
+                new BN(limits.maxPerTx ?? 0),
+                new BN(limits.minPerTx ?? 0),
+                new BN(limits.raiseCap ?? 0))
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async setPaused(paused: boolean, authority?: Keypair) {
+        return await this.program.methods
+            .setPaused(paused)
+            .accounts({ authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async setOfferPaused(offerPda: PublicKey, paused: boolean, authority?: Keypair) {
+        return await this.program.methods
+            .setOfferPaused(paused)
+            .accounts({ offer: offerPda, authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async grantRole(role: Role, holder: PublicKey, authority?: Keypair) {
+        return await this.program.methods
+            .grantRole(role, holder)
+            .accounts({ authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async revokeRole(role: Role, holder: PublicKey, authority?: Keypair) {
+        return await this.program.methods
+            .revokeRole(role, holder)
+            .accounts({ authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    getMultisigPda(): PublicKey {
+        const [multisigPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig')], ONREAPP_PROGRAM_ID);
+        return multisigPda;
+    }
+
+    getMultisigAuthorityPda(): PublicKey {
+        const [multisigAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('multisig_authority')], ONREAPP_PROGRAM_ID);
+        return multisigAuthorityPda;
+    }
+
+    getProposalPda(proposalId: BN): PublicKey {
+        const [proposalPda] = PublicKey.findProgramAddressSync([Buffer.from('proposal'), this.getMultisigPda().toBuffer(), proposalId.toArrayLike(Buffer, 'le', 8)], ONREAPP_PROGRAM_ID);
+        return proposalPda;
+    }
+
+    async createMultisig(signers: PublicKey[], threshold: number) {
+        return await this.program.methods
+            .createMultisig(signers, threshold)
+            .accounts({ state: this.statePda })
+            .rpc();
+    }
+
+    async createProposal(instruction: TransactionInstruction, proposer: Keypair): Promise<PublicKey> {
+        const multisig = await this.program.account.multisig.fetch(this.getMultisigPda());
+        await this.program.methods
+            .createProposal(
+                instruction.keys.map(key => ({ pubkey: key.pubkey, isSigner: key.isSigner, isWritable: key.isWritable })),
+                instruction.data)
+            .accounts({ proposer: proposer.publicKey })
+            .signers([proposer])
+            .rpc();
+        return this.getProposalPda(multisig.proposalCount);
+    }
+
+    async approveProposal(proposalPda: PublicKey, signer: Keypair) {
+        return await this.program.methods
+            .approveProposal()
+            .accounts({ proposal: proposalPda, signer: signer.publicKey })
+            .signers([signer])
+            .rpc();
+    }
+
+    async rejectProposal(proposalPda: PublicKey, signer: Keypair) {
+        return await this.program.methods
+            .rejectProposal()
+            .accounts({ proposal: proposalPda, signer: signer.publicKey })
+            .signers([signer])
+            .rpc();
+    }
+
+    async executeProposal(proposalPda: PublicKey) {
+        const proposal = await this.program.account.proposal.fetch(proposalPda);
+        return await this.program.methods
+            .executeProposal()
+            .accounts({ proposal: proposalPda, executor: this.program.provider.publicKey })
+            .remainingAccounts(proposal.accounts.map(account => ({ pubkey: account.pubkey, isSigner: false, isWritable: account.isWritable })))
+            .rpc();
+    }
+
+    async closeProposal(proposalPda: PublicKey) {
+        const proposal = await this.program.account.proposal.fetch(proposalPda);
+        return await this.program.methods
+            .closeProposal()
+            .accountsPartial({ proposal: proposalPda, proposer: proposal.proposer })
+            .rpc();
+    }
+
+    getFeeVaultAuthorityPda(): PublicKey {
+        const [feeVaultAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('fee_vault_authority')], ONREAPP_PROGRAM_ID);
+        return feeVaultAuthorityPda;
+    }
+
+    async setProtocolFee(feeBps: number, authority?: Keypair) {
+        return await this.program.methods
+            .setProtocolFee(feeBps)
+            .accounts({ authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+    }
+
+    async setOfferFee(offerPda: PublicKey, feeBps: number | null) {
+        return await this.program.methods
+            .setOfferFee(feeBps)
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async withdrawFees(mint: PublicKey, destination: PublicKey, amount: number, treasurer?: Keypair) {
+        return await this.program.methods
+            .withdrawFees(new BN(amount))
+            .accountsPartial({
+                mint,
+                destinationTokenAccount: destination,
+                treasurer: treasurer?.publicKey ?? this.program.provider.publicKey,
+            })
+            .signers(treasurer ? [treasurer] : [])
+            .rpc();
+    }
+
+    getReferrerAccountPda(referrer: PublicKey, mint: PublicKey): PublicKey {
+        const [referrerAccountPda] = PublicKey.findProgramAddressSync([Buffer.from('referrer'), referrer.toBuffer(), mint.toBuffer()], ONREAPP_PROGRAM_ID);
+        return referrerAccountPda;
+    }
+
+    getReferralVaultAuthorityPda(): PublicKey {
+        const [referralVaultAuthorityPda] = PublicKey.findProgramAddressSync([Buffer.from('referral_vault_authority')], ONREAPP_PROGRAM_ID);
+        return referralVaultAuthorityPda;
+    }
+
+    async registerReferrer(mint: PublicKey, referrer: Keypair, authority?: Keypair): Promise<PublicKey> {
+        await this.program.methods
+            .registerReferrer()
+            .accounts({ mint, referrer: referrer.publicKey, authority: authority?.publicKey ?? this.program.provider.publicKey })
+            .signers(authority ? [authority] : [])
+            .rpc();
+        return this.getReferrerAccountPda(referrer.publicKey, mint);
+    }
+
+    async setOfferReferralFee(offerPda: PublicKey, referralFeeBps: number) {
+        return await this.program.methods
+            .setOfferReferralFee(referralFeeBps)
+            .accountsPartial({ offer: offerPda, state: this.statePda, authority: this.program.provider.publicKey })
+            .rpc();
+    }
+
+    async claimReferralFees(mint: PublicKey, destination: PublicKey, referrer: Keypair) {
+        return await this.program.methods
+            .claimReferralFees()
+            .accountsPartial({ mint, referrerTokenAccount: destination, referrer: referrer.publicKey })
+            .signers([referrer])
             .rpc();
     }
 
@@ -287,8 +1031,94 @@ type MakeOfferTwoParams = {
     buyToken2Mint: PublicKey;
 }
 
+type BuyToken2Accounts = {
+    offerBuyToken2Account: PublicKey;
+    bossBuyToken2Account: PublicKey;
+}
+
+type UserBuyToken2Accounts = {
+    offerBuyToken2Account: PublicKey;
+    userBuyToken2Account: PublicKey;
+}
+
+type VestingBuyToken2Accounts = {
+    vestingBuyToken2Account: PublicKey;
+    userBuyToken2Account: PublicKey;
+}
+
+type UpdateOfferParams = {
+    sellTokenStartAmount: number;
+    sellTokenEndAmount: number;
+    offerEndTime: number;
+    priceFixDuration: number;
+}
+
 type TakeOfferParams = {
     sellTokenAmount: number;
     offerPda: PublicKey;
     user: Keypair;
+    navOracle?: PublicKey;
+    kycRecord?: PublicKey;
+    feeVault?: PublicKey;
+    referrerAccount?: PublicKey;
+    referralVault?: PublicKey;
+    vestingEscrow?: PublicKey;
+    vestingBuyToken1Account?: PublicKey;
+    vestingBuyToken2Account?: PublicKey;
 }
+
+type QuoteAmountParams = { sellTokenAmount: number } | { buyToken1Amount: number };
+
+type TakeOfferPermissionlessParams = TakeOfferParams & {
+    intermediaryIndex: number;
+}
+
+type RedemptionOfferAccounts = {
+    offerId: BN;
+    offerAuthority: PublicKey;
+    offerPda: PublicKey;
+    offerPayoutTokenPda: PublicKey;
+    offerRedeemTokenPda: PublicKey;
+    bossPayoutTokenAccount: PublicKey;
+}
+
+type MakeRedemptionOfferParams = {
+    offerId: BN;
+    payoutTokenTotalAmount: number;
+    priceStart: number;
+    priceEnd: number;
+    offerStartTime: number;
+    offerEndTime: number;
+    priceFixDuration: number;
+    navSpreadBps?: number;
+    epochDuration: number;
+    epochCap: number;
+    redeemTokenMint: PublicKey;
+    payoutTokenMint: PublicKey;
+    navOracle?: PublicKey;
+}
+
+type TakeRedemptionOfferParams = {
+    redeemTokenAmount: number;
+    offerPda: PublicKey;
+    redeemTokenMint: PublicKey;
+    user: Keypair;
+    navOracle?: PublicKey;
+}
+
+type RedemptionQueueAccounts = {
+    queueId: BN;
+    queueAuthority: PublicKey;
+    queuePda: PublicKey;
+    queueRedeemTokenPda: PublicKey;
+    queuePayoutTokenPda: PublicKey;
+}
+
+type OfferLimits = {
+    maxPerUser?: number;
+    maxPerTx?: number;
+    minPerTx?: number;
+    raiseCap?: number;
+}
+
+export type Role = IdlTypes<OnreApp>["role"];
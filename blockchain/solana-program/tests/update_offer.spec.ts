@@ -0,0 +1,193 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("update offer", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, offerStartTime };
+    }
+
+    function createUser(): { user: Keypair, userBuyTokenAccount: PublicKey } {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        return { user, userBuyTokenAccount };
+    }
+
+    test("Update offer should keep the current interval price and start the new schedule at the next interval", async () => {
+        // given
+        const { user, userBuyTokenAccount } = createUser();
+        const { offerPda, offerStartTime } = await makeOffer();
+
+        // when
+        await testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 50e9,
+            sellTokenEndAmount: 50e9,
+            offerEndTime: offerStartTime + 14400,
+            priceFixDuration: 10800,
+        });
+
+        // then
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.sellTokenStartAmount.toNumber()).toEqual(50e9);
+        expect(offer.offerEndTime.toNumber()).toEqual(offerStartTime + 14400);
+        expect(offer.priceFixDuration.toNumber()).toEqual(10800);
+        expect(offer.scheduleStartTime.toNumber()).toEqual(offerStartTime + 3600);
+        expect(offer.carriedSellTokenAmount.toNumber()).toEqual(100e9);
+
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(10e9));
+
+        await testHelper.advanceClockBy(3600);
+        await testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user });
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(30e9));
+    });
+
+    test("Update offer before it starts should replace the schedule from the start", async () => {
+        // given
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+        const offerStartTime = await testHelper.getCurrentClockTime() + 3600;
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        // when
+        await testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 50e9,
+            sellTokenEndAmount: 50e9,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 7200,
+        });
+
+        // then
+        const offer = await testHelper.getOfferAccount(offerPda);
+        expect(offer.scheduleStartTime.toNumber()).toEqual(0);
+        expect(offer.carriedSellTokenAmount.toNumber()).toEqual(0);
+    });
+
+    test("Update offer with an invalid schedule should fail", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 0,
+            sellTokenEndAmount: 100e9,
+            offerEndTime: offerStartTime + 7200,
+            priceFixDuration: 3600,
+        })).rejects.toThrow(RegExp(".*InvalidAmount.*"));
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerEndTime: offerStartTime + 10800,
+            priceFixDuration: 5000,
+        })).rejects.toThrow(RegExp(".*InvalidOfferTime.*"));
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+        })).rejects.toThrow(RegExp(".*InvalidOfferTime.*"));
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerEndTime: offerStartTime,
+            priceFixDuration: 3600,
+        })).rejects.toThrow(RegExp(".*InvalidOfferEndTime.*"));
+    });
+
+    test("Update uniform price offer should fail", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+        await testHelper.setOfferUniformPrice(offerPda, true);
+
+        // when / then
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerEndTime: offerStartTime + 14400,
+            priceFixDuration: 3600,
+        })).rejects.toThrow(RegExp(".*UniformPriceOffer.*"));
+    });
+
+    test("Update offer by a key without the offer manager role should fail", async () => {
+        // given
+        const outsider = testHelper.createUserAccount();
+        const { offerPda, offerStartTime } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.updateOffer(offerPda, {
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 200e9,
+            offerEndTime: offerStartTime + 14400,
+            priceFixDuration: 3600,
+        }, outsider)).rejects.toThrow(RegExp(".*MissingRole.*"));
+    });
+});
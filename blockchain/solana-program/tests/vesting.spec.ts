@@ -0,0 +1,152 @@
+import { AddedProgram, startAnchor } from "solana-bankrun";
+import { BankrunProvider } from "anchor-bankrun";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { OnreApp } from "../target/types/onre_app";
+import idl from "../target/idl/onre_app.json";
+import { ONREAPP_PROGRAM_ID, TestHelper } from "./test_helper";
+
+describe("vesting", () => {
+    let testHelper: TestHelper;
+
+    let sellTokenMint: PublicKey;
+    let buyToken1Mint: PublicKey;
+
+    let boss: PublicKey;
+
+    beforeAll(async () => {
+        const programInfo: AddedProgram = {
+            programId: ONREAPP_PROGRAM_ID,
+            name: "onreapp",
+        };
+
+        const context = await startAnchor("", [programInfo], []);
+
+        const provider = new BankrunProvider(context);
+        const program = new Program<OnreApp>(
+            idl,
+            provider,
+        );
+
+        testHelper = new TestHelper(context, program);
+
+        boss = provider.wallet.publicKey;
+
+        // Create mints
+        sellTokenMint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        buyToken1Mint = testHelper.createMint(boss, BigInt(100_000e9), 9);
+        testHelper.createTokenAccount(sellTokenMint, boss, BigInt(0));
+
+        await program.methods.initialize().accounts({ boss }).rpc();
+    });
+
+    async function makeOffer() {
+        const { offerId, offerPda } = await testHelper.createOneTokenOfferAccounts(
+            sellTokenMint, BigInt(0),
+            buyToken1Mint, BigInt(0),
+            boss, BigInt(100e9)
+        );
+
+        const offerStartTime = await testHelper.getCurrentClockTime();
+        await testHelper.makeOfferOne({
+            offerId,
+            buyTokenTotalAmount: 100e9,
+            sellTokenStartAmount: 100e9,
+            sellTokenEndAmount: 100e9,
+            offerStartTime,
+            offerEndTime: offerStartTime + 3600,
+            priceFixDuration: 3600,
+            sellTokenMint,
+            buyTokenMint: buyToken1Mint,
+        });
+
+        return { offerPda, offerStartTime };
+    }
+
+    async function createVestingUser(offerPda: PublicKey) {
+        const user = testHelper.createUserAccount();
+        testHelper.createTokenAccount(sellTokenMint, user.publicKey, BigInt(100e9), true);
+        const userBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, user.publicKey, BigInt(0), true);
+        await testHelper.openVestingEscrow(offerPda, user);
+        const vestingEscrow = testHelper.getVestingEscrowPda(offerPda, user.publicKey);
+        const vestingBuyTokenAccount = testHelper.createTokenAccount(buyToken1Mint, vestingEscrow, BigInt(0), true);
+        return { user, userBuyTokenAccount, vestingEscrow, vestingBuyTokenAccount };
+    }
+
+    test("Bought tokens should be locked and released linearly after the cliff", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+        await testHelper.setOfferVesting(offerPda, offerStartTime, 1000, 4000);
+        const { user, userBuyTokenAccount, vestingEscrow, vestingBuyTokenAccount } = await createVestingUser(offerPda);
+
+        // when
+        await testHelper.takeOfferOne({
+            sellTokenAmount: 40e9,
+            offerPda,
+            user,
+            vestingEscrow,
+            vestingBuyToken1Account: vestingBuyTokenAccount,
+        });
+
+        // then
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(0));
+        await testHelper.expectTokenAccountAmountToBe(vestingBuyTokenAccount, BigInt(40e9));
+        await expect(testHelper.claimVested(vestingEscrow, user))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+
+        await testHelper.advanceClockBy(2000);
+        await testHelper.claimVested(vestingEscrow, user);
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(20e9));
+        await testHelper.expectTokenAccountAmountToBe(vestingBuyTokenAccount, BigInt(20e9));
+
+        await testHelper.advanceClockBy(3000);
+        await testHelper.claimVested(vestingEscrow, user);
+        await testHelper.expectTokenAccountAmountToBe(userBuyTokenAccount, BigInt(40e9));
+        await testHelper.expectTokenAccountAmountToBe(vestingBuyTokenAccount, BigInt(0));
+        await expect(testHelper.claimVested(vestingEscrow, user))
+            .rejects.toThrow(RegExp(".*NothingToClaim.*"));
+    });
+
+    test("Taking a vesting offer without the user's own vesting escrow should fail", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+        await testHelper.setOfferVesting(offerPda, offerStartTime, 0, 1000);
+        const first = await createVestingUser(offerPda);
+        const second = await createVestingUser(offerPda);
+
+        // when / then
+        await expect(testHelper.takeOfferOne({ sellTokenAmount: 10e9, offerPda, user: first.user }))
+            .rejects.toThrow(RegExp(".*MissingVestingAccounts.*"));
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user: first.user,
+            vestingEscrow: second.vestingEscrow,
+            vestingBuyToken1Account: second.vestingBuyTokenAccount,
+        })).rejects.toThrow(RegExp(".*InvalidVestingEscrow.*"));
+        await expect(testHelper.takeOfferOne({
+            sellTokenAmount: 10e9,
+            offerPda,
+            user: first.user,
+            vestingEscrow: first.vestingEscrow,
+            vestingBuyToken1Account: second.vestingBuyTokenAccount,
+        })).rejects.toThrow(RegExp(".*InvalidVestingAccount.*"));
+    });
+
+    test("Vesting schedules should be validated and exclusive of other settlement modes", async () => {
+        // given
+        const { offerPda, offerStartTime } = await makeOffer();
+
+        // when / then
+        await expect(testHelper.setOfferVesting(offerPda, offerStartTime, 2000, 1000))
+            .rejects.toThrow(RegExp(".*InvalidVestingSchedule.*"));
+        await expect(testHelper.openVestingEscrow(offerPda, testHelper.createUserAccount()))
+            .rejects.toThrow(RegExp(".*NotVestingOffer.*"));
+
+        await testHelper.setOfferVesting(offerPda, offerStartTime, 0, 1000);
+        await expect(testHelper.setOfferMinRaise(offerPda, 10e9))
+            .rejects.toThrow(RegExp(".*VestingOffer.*"));
+        await expect(testHelper.setOfferSubscription(offerPda, true))
+            .rejects.toThrow(RegExp(".*VestingOffer.*"));
+    });
+});